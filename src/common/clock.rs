@@ -0,0 +1,101 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Pluggable clock for time-dependent logic
+//!
+//! Code that reads the current time (token expiry, cache TTLs) takes a
+//! `&dyn Clock` so tests can use `MockClock` instead of sleeping.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use chrono::{DateTime, Utc};
+
+/// Source of the current time
+pub trait Clock: Send + Sync {
+    /// Current time in UTC
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Current Unix timestamp in seconds
+    fn timestamp(&self) -> i64 {
+        self.now().timestamp()
+    }
+
+    /// Current Unix timestamp in microseconds
+    fn timestamp_micros(&self) -> i64 {
+        self.now().timestamp_micros()
+    }
+}
+
+/// Clock backed by the system time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Manually controlled clock for tests
+#[derive(Debug, Default)]
+pub struct MockClock {
+    micros: AtomicI64,
+}
+
+impl MockClock {
+    /// Create a mock clock fixed at the given Unix timestamp (seconds)
+    pub fn new(timestamp: i64) -> Self {
+        Self {
+            micros: AtomicI64::new(timestamp * 1_000_000),
+        }
+    }
+
+    /// Set the current Unix timestamp (seconds)
+    pub fn set(&self, timestamp: i64) {
+        self.micros.store(timestamp * 1_000_000, Ordering::SeqCst);
+    }
+
+    /// Advance the clock
+    pub fn advance(&self, duration: std::time::Duration) {
+        self.micros
+            .fetch_add(duration.as_micros() as i64, Ordering::SeqCst);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        DateTime::from_timestamp_micros(self.micros.load(Ordering::SeqCst)).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_system_clock() {
+        let before = Utc::now().timestamp();
+        let now = SystemClock.timestamp();
+        assert!(now >= before);
+    }
+
+    #[test]
+    fn test_mock_clock() {
+        let clock = MockClock::new(1_700_000_000);
+        assert_eq!(clock.timestamp(), 1_700_000_000);
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(clock.timestamp(), 1_700_000_090);
+
+        clock.advance(Duration::from_millis(1500));
+        assert_eq!(clock.timestamp_micros(), 1_700_000_091_500_000);
+
+        clock.set(42);
+        assert_eq!(clock.timestamp(), 42);
+    }
+}
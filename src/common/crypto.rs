@@ -0,0 +1,177 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Encryption of sensitive config values
+//!
+//! Values are encrypted with AES-256-GCM using `VisdataConfig::encryption_key`
+//! (base64, 32 bytes). Ciphertexts are `base64(nonce || ciphertext || tag)`
+//! with a random 96-bit nonce per value.
+
+use std::fmt;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+
+use crate::Visdata;
+
+/// AES-GCM nonce length in bytes
+const NONCE_LEN: usize = 12;
+
+/// Result type for crypto operations
+pub type Result<T> = std::result::Result<T, CryptoError>;
+
+/// Encryption errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum CryptoError {
+    /// `encryption_key` is not configured
+    MissingKey,
+    /// `encryption_key` is not base64 for 32 bytes
+    InvalidKey(String),
+    /// Encryption failed
+    Encrypt,
+    /// Ciphertext is malformed or was not encrypted with this key
+    Decrypt(String),
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CryptoError::MissingKey => {
+                write!(f, "encryption_key is not configured; cannot encrypt or decrypt secrets")
+            }
+            CryptoError::InvalidKey(msg) => write!(f, "Invalid encryption_key: {}", msg),
+            CryptoError::Encrypt => write!(f, "Encryption failed"),
+            CryptoError::Decrypt(msg) => write!(f, "Decryption failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
+/// Encrypt a secret with the configured `encryption_key`
+pub async fn encrypt(plaintext: &str) -> Result<String> {
+    encrypt_with_key(&configured_key().await?, plaintext)
+}
+
+/// Decrypt a value produced by `encrypt` with the configured `encryption_key`
+pub async fn decrypt(ciphertext: &str) -> Result<String> {
+    decrypt_with_key(&configured_key().await?, ciphertext)
+}
+
+/// The configured `encryption_key`
+async fn configured_key() -> Result<String> {
+    let visdata = Visdata::try_global().ok_or(CryptoError::MissingKey)?;
+    visdata.config().await.encryption_key.ok_or(CryptoError::MissingKey)
+}
+
+/// Encrypt `plaintext` with a base64-encoded 256-bit key
+pub fn encrypt_with_key(key: &str, plaintext: &str) -> Result<String> {
+    let cipher = cipher(key)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| CryptoError::Encrypt)?;
+
+    let mut out = nonce.to_vec();
+    out.extend_from_slice(&ciphertext);
+    Ok(base64::engine::general_purpose::STANDARD.encode(out))
+}
+
+/// Decrypt a value produced by `encrypt_with_key`
+pub fn decrypt_with_key(key: &str, ciphertext: &str) -> Result<String> {
+    let cipher = cipher(key)?;
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(ciphertext)
+        .map_err(|e| CryptoError::Decrypt(format!("invalid base64: {}", e)))?;
+    if data.len() < NONCE_LEN {
+        return Err(CryptoError::Decrypt("ciphertext too short".to_string()));
+    }
+
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| CryptoError::Decrypt("wrong key or corrupted ciphertext".to_string()))?;
+    String::from_utf8(plaintext).map_err(|e| CryptoError::Decrypt(e.to_string()))
+}
+
+/// Check that a key is base64 for exactly 32 bytes
+pub fn validate_key(key: &str) -> Result<()> {
+    decode_key(key).map(|_| ())
+}
+
+/// Decode a base64-encoded 256-bit key
+fn decode_key(key: &str) -> Result<Vec<u8>> {
+    let key = base64::engine::general_purpose::STANDARD
+        .decode(key)
+        .map_err(|e| CryptoError::InvalidKey(format!("invalid base64: {}", e)))?;
+    if key.len() != 32 {
+        return Err(CryptoError::InvalidKey(format!(
+            "expected 32 bytes, got {}",
+            key.len()
+        )));
+    }
+    Ok(key)
+}
+
+/// Build the cipher for a base64-encoded 256-bit key
+fn cipher(key: &str) -> Result<Aes256Gcm> {
+    let key = decode_key(key)?;
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key(byte: u8) -> String {
+        base64::engine::general_purpose::STANDARD.encode([byte; 32])
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let key = test_key(7);
+        let ciphertext = encrypt_with_key(&key, "ldap-bind-secret").unwrap();
+        assert!(!ciphertext.contains("ldap-bind-secret"));
+        assert_eq!(decrypt_with_key(&key, &ciphertext).unwrap(), "ldap-bind-secret");
+
+        // A fresh nonce per value means equal secrets encrypt differently
+        assert_ne!(encrypt_with_key(&key, "ldap-bind-secret").unwrap(), ciphertext);
+    }
+
+    #[test]
+    fn test_decrypt_rejects_wrong_key_and_garbage() {
+        let ciphertext = encrypt_with_key(&test_key(1), "secret").unwrap();
+        assert!(matches!(
+            decrypt_with_key(&test_key(2), &ciphertext),
+            Err(CryptoError::Decrypt(_))
+        ));
+        assert!(matches!(decrypt_with_key(&test_key(1), "AAAA"), Err(CryptoError::Decrypt(_))));
+        assert!(matches!(decrypt_with_key(&test_key(1), "%%%"), Err(CryptoError::Decrypt(_))));
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        let short = base64::engine::general_purpose::STANDARD.encode([0u8; 16]);
+        assert_eq!(
+            encrypt_with_key(&short, "secret"),
+            Err(CryptoError::InvalidKey("expected 32 bytes, got 16".to_string()))
+        );
+        assert!(matches!(
+            encrypt_with_key("not base64!", "secret"),
+            Err(CryptoError::InvalidKey(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_without_key_is_an_error() {
+        // VisData is never initialized in unit tests, so no key is configured
+        let err = encrypt("secret").await.unwrap_err();
+        assert_eq!(err, CryptoError::MissingKey);
+        assert!(err.to_string().contains("encryption_key is not configured"));
+    }
+}
@@ -7,6 +7,7 @@
 
 //! ID generation utilities using KSUID
 
+use sha2::{Digest, Sha256};
 use svix_ksuid::{Ksuid, KsuidLike};
 
 /// Generate a new KSUID
@@ -14,6 +15,21 @@ pub fn generate_id() -> String {
     Ksuid::new(None, None).to_string()
 }
 
+/// Derive a stable ID from a namespace and name
+///
+/// Returns the first 16 bytes of `sha256(namespace || 0x00 || name)` as 32
+/// hex characters, so the same entity always maps to the same ID.
+pub fn deterministic_id(namespace: &str, name: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(namespace.as_bytes());
+    hasher.update([0u8]);
+    hasher.update(name.as_bytes());
+    hasher.finalize()[..16]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,4 +45,19 @@ mod tests {
         // KSUID is 27 characters
         assert_eq!(id1.len(), 27);
     }
+
+    #[test]
+    fn test_deterministic_id() {
+        let id = deterministic_id("group:default", "developers");
+        assert_eq!(id, deterministic_id("group:default", "developers"));
+        assert_eq!(id.len(), 32);
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+
+        assert_ne!(id, deterministic_id("group:other", "developers"));
+        // Namespace and name are separated so shifting characters changes the ID
+        assert_ne!(
+            deterministic_id("group:a_b", "c"),
+            deterministic_id("group:a", "b_c")
+        );
+    }
 }
@@ -7,6 +7,12 @@
 
 //! Common utilities shared across VisData modules
 
+pub mod clock;
+pub mod crypto;
 pub mod id;
+pub mod redact;
+pub mod telemetry;
+pub mod tls;
 
-pub use id::generate_id;
+pub use clock::{Clock, MockClock, SystemClock};
+pub use id::{deterministic_id, generate_id};
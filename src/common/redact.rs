@@ -0,0 +1,38 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Redaction of secrets in `Debug` output
+//!
+//! Config structs holding tokens, passwords or keys implement `Debug` by hand
+//! and print their secrets through these helpers, so logging a config shows
+//! whether a secret is set but never its value.
+
+/// Placeholder printed instead of a secret
+pub const REDACTED: &str = "[redacted]";
+
+/// Debug form of a secret: empty if unset, `REDACTED` otherwise
+pub fn secret(value: &str) -> &'static str {
+    if value.is_empty() { "" } else { REDACTED }
+}
+
+/// Debug form of an optional secret: `None` if unset, `REDACTED` otherwise
+pub fn optional_secret(value: &Option<String>) -> Option<&'static str> {
+    value.as_ref().map(|_| REDACTED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redaction() {
+        assert_eq!(secret(""), "");
+        assert_eq!(secret("hunter2"), REDACTED);
+        assert_eq!(optional_secret(&None), None);
+        assert_eq!(optional_secret(&Some("hunter2".to_string())), Some(REDACTED));
+    }
+}
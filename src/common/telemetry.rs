@@ -0,0 +1,65 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Span helpers for outbound OpenFGA and Dex calls
+//!
+//! Outbound calls run in `#[tracing::instrument]` spans that declare empty
+//! `status` and `elapsed_ms` fields; these helpers fill them in. Recording a
+//! field the current span does not declare is a no-op.
+
+use std::time::Instant;
+
+use tracing::Span;
+
+/// Record the HTTP status of a response on the current span
+pub fn record_status(status: u16) {
+    Span::current().record("status", status);
+}
+
+/// Records `elapsed_ms` on the span it was started in when dropped
+///
+/// Start it at the top of an instrumented call so every return path,
+/// including `?`, reports its duration.
+pub struct CallTimer {
+    span: Span,
+    started: Instant,
+}
+
+impl CallTimer {
+    /// Start timing the current span
+    pub fn start() -> Self {
+        Self {
+            span: Span::current(),
+            started: Instant::now(),
+        }
+    }
+
+    /// Milliseconds since the timer started
+    pub fn elapsed_ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+}
+
+impl Drop for CallTimer {
+    fn drop(&mut self) {
+        self.span.record("elapsed_ms", self.elapsed_ms());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_timer_outside_span() {
+        let timer = CallTimer::start();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        assert!(timer.elapsed_ms() >= 5);
+        record_status(200);
+        drop(timer);
+    }
+}
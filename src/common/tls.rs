@@ -0,0 +1,132 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! TLS settings for outbound HTTP clients (OpenFGA, Dex)
+//!
+//! Lets deployments behind an internal PKI trust a custom CA, or, as an
+//! explicit opt-in, skip certificate verification entirely.
+
+use std::fmt;
+
+use reqwest::{Certificate, ClientBuilder};
+
+/// PEM marker every CA file must contain at least once
+const PEM_CERT_MARKER: &str = "-----BEGIN CERTIFICATE-----";
+
+/// Result type for TLS configuration
+pub type Result<T> = std::result::Result<T, TlsError>;
+
+/// TLS configuration errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum TlsError {
+    /// The CA certificate file could not be read
+    ReadCaCert { path: String, reason: String },
+    /// The CA certificate file is not a PEM certificate
+    InvalidCaCert { path: String, reason: String },
+}
+
+impl fmt::Display for TlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TlsError::ReadCaCert { path, reason } => {
+                write!(f, "Failed to read CA certificate {}: {}", path, reason)
+            }
+            TlsError::InvalidCaCert { path, reason } => {
+                write!(f, "Invalid CA certificate {}: {}", path, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for TlsError {}
+
+/// Apply TLS settings to an HTTP client builder
+///
+/// `ca_cert_path` adds a PEM CA (or bundle) to the trusted roots. With
+/// `skip_verify`, certificates are not verified at all; `service` names the
+/// peer in the warning logged for that case.
+pub fn configure(
+    builder: ClientBuilder,
+    ca_cert_path: Option<&str>,
+    skip_verify: bool,
+    service: &str,
+) -> Result<ClientBuilder> {
+    let mut builder = builder;
+    if let Some(path) = ca_cert_path.filter(|p| !p.is_empty()) {
+        let pem = std::fs::read(path).map_err(|e| TlsError::ReadCaCert {
+            path: path.to_string(),
+            reason: e.to_string(),
+        })?;
+        builder = builder.add_root_certificate(root_certificate(path, &pem)?);
+    }
+    if skip_verify {
+        tracing::warn!(
+            "[VISDATA] TLS certificate verification is DISABLED for {}; connections are \
+             open to interception. Do not use tls_skip_verify in production",
+            service
+        );
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    Ok(builder)
+}
+
+/// Parse a PEM CA certificate read from `path`
+fn root_certificate(path: &str, pem: &[u8]) -> Result<Certificate> {
+    let invalid = |reason: String| TlsError::InvalidCaCert {
+        path: path.to_string(),
+        reason,
+    };
+    // rustls parses lazily and silently ignores input without certificates
+    if !String::from_utf8_lossy(pem).contains(PEM_CERT_MARKER) {
+        return Err(invalid("no PEM certificate found".to_string()));
+    }
+    Certificate::from_pem(pem).map_err(|e| invalid(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBizCCATGgAwIBAgIUUZCXR3MYsVVy9ez1W/XcBzGI6gkwCgYIKoZIzj0EAwIw
+GjEYMBYGA1UEAwwPdmlzZGF0YS10ZXN0LWNhMCAXDTI2MTAxNzIxMTcwN1oYDzIx
+MjYwOTIzMjExNzA3WjAaMRgwFgYDVQQDDA92aXNkYXRhLXRlc3QtY2EwWTATBgcq
+hkjOPQIBBggqhkjOPQMBBwNCAASN2VGvtKj/n0Uu7pfCf0egrsYD/O7ez7xS8cGB
+I3qSbrhbxFu/pTwvD10K3kaieLm9c8swFBwcQUc3I+NJPJ8go1MwUTAdBgNVHQ4E
+FgQUxnNXOAA/Z3/or+Os9SiXxyEqlgIwHwYDVR0jBBgwFoAUxnNXOAA/Z3/or+Os
+9SiXxyEqlgIwDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNIADBFAiEAz1UY
+8WE/CwrLetrIJ1Yb5HkUp6kJIolm0QDsaWrfrtoCIH+xwn6UY2XTDDPj/tEK1w/S
+/7qK5S5O8c4Q5IDXSnDK
+-----END CERTIFICATE-----
+";
+
+    #[test]
+    fn test_root_certificate() {
+        let cert = root_certificate("ca.pem", TEST_CA.as_bytes()).unwrap();
+        reqwest::Client::builder().add_root_certificate(cert).build().unwrap();
+
+        assert_eq!(
+            root_certificate("ca.pem", b"not a certificate").unwrap_err(),
+            TlsError::InvalidCaCert {
+                path: "ca.pem".to_string(),
+                reason: "no PEM certificate found".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_configure() {
+        // No settings and skip-verify alone both leave a buildable client
+        configure(reqwest::Client::builder(), None, false, "test").unwrap().build().unwrap();
+        configure(reqwest::Client::builder(), Some(""), true, "test").unwrap().build().unwrap();
+
+        let missing = Some("/nonexistent/ca.pem");
+        let err = configure(reqwest::Client::builder(), missing, false, "test").unwrap_err();
+        assert!(matches!(&err, TlsError::ReadCaCert { path, .. } if path == "/nonexistent/ca.pem"));
+        assert!(err.to_string().starts_with("Failed to read CA certificate /nonexistent/ca.pem"));
+    }
+}
@@ -21,13 +21,13 @@ pub async fn get_config() -> VisdataConfig {
     }
 }
 
-/// Get the OpenFGA configuration as a reference
-pub fn get_openfga_config() -> Option<&'static crate::openfga::OpenFGAConfig> {
+/// Get the current OpenFGA configuration
+pub fn get_openfga_config() -> Option<std::sync::Arc<crate::openfga::OpenFGAConfig>> {
     Visdata::try_global().map(|v| v.openfga_config())
 }
 
-/// Get the Dex configuration as a reference
-pub fn get_dex_config() -> Option<&'static crate::dex::DexConfig> {
+/// Get the current Dex configuration
+pub fn get_dex_config() -> Option<std::sync::Arc<crate::dex::DexConfig>> {
     Visdata::try_global().map(|v| v.dex_config())
 }
 
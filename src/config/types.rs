@@ -8,10 +8,14 @@
 //! Configuration for VisData module
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::common::redact;
+use crate::openfga::config::OpenFGAAuthMethod;
 
 /// Main configuration for VisData module
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct VisdataConfig {
     /// Whether RBAC is enabled
     pub rbac_enabled: bool,
@@ -34,6 +38,73 @@ pub struct VisdataConfig {
     #[serde(default = "default_openfga_store_name")]
     pub openfga_store_name: String,
 
+    /// OpenFGA API token (`VISDATA_OPENFGA_API_TOKEN`); never serialized
+    #[serde(default = "default_openfga_api_token", skip_serializing)]
+    pub openfga_api_token: Option<String>,
+
+    /// OpenFGA auth method (defaults to bearer when an API token is set)
+    #[serde(default = "default_openfga_auth_method")]
+    pub openfga_auth_method: OpenFGAAuthMethod,
+
+    /// Overrides for the system role -> OpenFGA org relation mapping
+    /// (e.g., "auditor" -> "viewer")
+    #[serde(default)]
+    pub role_relation_overrides: HashMap<String, String>,
+
+    /// Root user granted admin when a new OpenFGA store is bootstrapped
+    #[serde(default = "default_root_email")]
+    pub root_email: String,
+
+    /// Users (emails) that bypass all permission checks, in addition to
+    /// `root_email`
+    #[serde(default)]
+    pub root_emails: HashSet<String>,
+
+    /// Bootstrap the internal `_meta` organization in a new OpenFGA store
+    #[serde(default = "default_true")]
+    pub openfga_include_meta_org: bool,
+
+    /// Upgrade the OpenFGA authorization model on startup when it is outdated
+    #[serde(default)]
+    pub openfga_auto_upgrade_model: bool,
+
+    /// Cloud deployment (hides non-cloud resources in the permissions UI)
+    #[serde(default)]
+    pub is_cloud: bool,
+
+    /// Trust the "root" role string for the RBAC bypass without verifying
+    /// `_meta` admin in OpenFGA (deprecated; list root users in `root_emails`
+    /// instead)
+    ///
+    /// Defaults to false, which is a breaking change for deployments that
+    /// relied on the old default of true; see `OpenFGAConfig::trust_root_role_string`.
+    #[serde(default)]
+    pub trust_root_role_string: bool,
+
+    /// Maximum number of concurrent outbound OpenFGA requests
+    #[serde(default = "default_openfga_max_concurrent_requests")]
+    pub openfga_max_concurrent_requests: usize,
+
+    /// Number of initial tuple batches written concurrently on OpenFGA bootstrap
+    #[serde(default = "default_openfga_init_write_concurrency")]
+    pub openfga_init_write_concurrency: usize,
+
+    /// Maximum number of retries for failed OpenFGA requests
+    #[serde(default = "default_openfga_max_retries")]
+    pub openfga_max_retries: u32,
+
+    /// Base delay for OpenFGA retry backoff, in milliseconds
+    #[serde(default = "default_openfga_retry_base_delay_ms")]
+    pub openfga_retry_base_delay_ms: u64,
+
+    /// PEM CA certificate trusted for OpenFGA connections
+    #[serde(default)]
+    pub openfga_tls_ca_cert_path: Option<String>,
+
+    /// Skip TLS certificate verification for OpenFGA (insecure)
+    #[serde(default)]
+    pub openfga_tls_skip_verify: bool,
+
     /// Dex gRPC URL
     #[serde(default = "default_dex_grpc_url")]
     pub dex_grpc_url: String,
@@ -50,10 +121,38 @@ pub struct VisdataConfig {
     #[serde(default)]
     pub dex_client_secret: String,
 
+    /// Other trusted OAuth2 client IDs accepted as token audiences
+    #[serde(default)]
+    pub dex_additional_audiences: Vec<String>,
+
     /// Dex OAuth2 redirect URI
     #[serde(default = "default_dex_redirect_uri")]
     pub dex_redirect_uri: String,
 
+    /// Where the identity provider redirects after single logout
+    #[serde(default)]
+    pub dex_post_logout_redirect_uri: Option<String>,
+
+    /// How long Dex JWKS signing keys are cached, in seconds
+    #[serde(default = "default_dex_jwks_cache_ttl_seconds")]
+    pub dex_jwks_cache_ttl_seconds: u64,
+
+    /// Mapping from SSO groups to internal custom roles, applied on login
+    #[serde(default)]
+    pub dex_group_role_mappings: HashMap<String, String>,
+
+    /// Dex connector IDs whose users are internal (native-password) users
+    #[serde(default = "default_dex_internal_connector_ids")]
+    pub dex_internal_connector_ids: Vec<String>,
+
+    /// PEM CA certificate trusted for Dex connections
+    #[serde(default)]
+    pub dex_tls_ca_cert_path: Option<String>,
+
+    /// Skip TLS certificate verification for Dex (insecure)
+    #[serde(default)]
+    pub dex_tls_skip_verify: bool,
+
     // ========================================================================
     // Log Patterns Configuration
     // ========================================================================
@@ -83,14 +182,136 @@ pub struct VisdataConfig {
     pub log_patterns_max_clusters: usize,
 }
 
+impl fmt::Debug for VisdataConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Destructured so a new field cannot be left out
+        let Self {
+            rbac_enabled,
+            sso_enabled,
+            cache,
+            encryption_key,
+            openfga_url,
+            openfga_store_name,
+            openfga_api_token,
+            openfga_auth_method,
+            role_relation_overrides,
+            root_email,
+            root_emails,
+            openfga_include_meta_org,
+            openfga_auto_upgrade_model,
+            is_cloud,
+            trust_root_role_string,
+            openfga_max_concurrent_requests,
+            openfga_init_write_concurrency,
+            openfga_max_retries,
+            openfga_retry_base_delay_ms,
+            openfga_tls_ca_cert_path,
+            openfga_tls_skip_verify,
+            dex_grpc_url,
+            dex_issuer_url,
+            dex_client_id,
+            dex_client_secret,
+            dex_additional_audiences,
+            dex_redirect_uri,
+            dex_post_logout_redirect_uri,
+            dex_jwks_cache_ttl_seconds,
+            dex_group_role_mappings,
+            dex_internal_connector_ids,
+            dex_tls_ca_cert_path,
+            dex_tls_skip_verify,
+            log_patterns_max_logs,
+            log_patterns_min_cluster_size,
+            log_patterns_similarity_threshold,
+            log_patterns_drain_depth,
+            log_patterns_drain_max_child,
+            log_patterns_max_clusters,
+        } = self;
+        f.debug_struct("VisdataConfig")
+            .field("rbac_enabled", rbac_enabled)
+            .field("sso_enabled", sso_enabled)
+            .field("cache", cache)
+            .field("encryption_key", &redact::optional_secret(encryption_key))
+            .field("openfga_url", openfga_url)
+            .field("openfga_store_name", openfga_store_name)
+            .field("openfga_api_token", &redact::optional_secret(openfga_api_token))
+            .field("openfga_auth_method", openfga_auth_method)
+            .field("role_relation_overrides", role_relation_overrides)
+            .field("root_email", root_email)
+            .field("root_emails", root_emails)
+            .field("openfga_include_meta_org", openfga_include_meta_org)
+            .field("openfga_auto_upgrade_model", openfga_auto_upgrade_model)
+            .field("is_cloud", is_cloud)
+            .field("trust_root_role_string", trust_root_role_string)
+            .field("openfga_max_concurrent_requests", openfga_max_concurrent_requests)
+            .field("openfga_init_write_concurrency", openfga_init_write_concurrency)
+            .field("openfga_max_retries", openfga_max_retries)
+            .field("openfga_retry_base_delay_ms", openfga_retry_base_delay_ms)
+            .field("openfga_tls_ca_cert_path", openfga_tls_ca_cert_path)
+            .field("openfga_tls_skip_verify", openfga_tls_skip_verify)
+            .field("dex_grpc_url", dex_grpc_url)
+            .field("dex_issuer_url", dex_issuer_url)
+            .field("dex_client_id", dex_client_id)
+            .field("dex_client_secret", &redact::secret(dex_client_secret))
+            .field("dex_additional_audiences", dex_additional_audiences)
+            .field("dex_redirect_uri", dex_redirect_uri)
+            .field("dex_post_logout_redirect_uri", dex_post_logout_redirect_uri)
+            .field("dex_jwks_cache_ttl_seconds", dex_jwks_cache_ttl_seconds)
+            .field("dex_group_role_mappings", dex_group_role_mappings)
+            .field("dex_internal_connector_ids", dex_internal_connector_ids)
+            .field("dex_tls_ca_cert_path", dex_tls_ca_cert_path)
+            .field("dex_tls_skip_verify", dex_tls_skip_verify)
+            .field("log_patterns_max_logs", log_patterns_max_logs)
+            .field("log_patterns_min_cluster_size", log_patterns_min_cluster_size)
+            .field("log_patterns_similarity_threshold", log_patterns_similarity_threshold)
+            .field("log_patterns_drain_depth", log_patterns_drain_depth)
+            .field("log_patterns_drain_max_child", log_patterns_drain_max_child)
+            .field("log_patterns_max_clusters", log_patterns_max_clusters)
+            .finish()
+    }
+}
+
 fn default_openfga_url() -> String {
     "http://localhost:8080".to_string()
 }
 
+fn default_root_email() -> String {
+    "root@visdata.com".to_string()
+}
+
 fn default_openfga_store_name() -> String {
     "openobserve".to_string()
 }
 
+fn default_openfga_api_token() -> Option<String> {
+    std::env::var("VISDATA_OPENFGA_API_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty())
+}
+
+fn default_openfga_auth_method() -> OpenFGAAuthMethod {
+    if default_openfga_api_token().is_some() {
+        OpenFGAAuthMethod::Bearer
+    } else {
+        OpenFGAAuthMethod::None
+    }
+}
+
+fn default_openfga_max_concurrent_requests() -> usize {
+    50
+}
+
+fn default_openfga_init_write_concurrency() -> usize {
+    4
+}
+
+fn default_openfga_max_retries() -> u32 {
+    3
+}
+
+fn default_openfga_retry_base_delay_ms() -> u64 {
+    100
+}
+
 fn default_dex_grpc_url() -> String {
     "http://localhost:5557".to_string()
 }
@@ -107,6 +328,14 @@ fn default_dex_redirect_uri() -> String {
     "http://localhost:5080/config/redirect".to_string()
 }
 
+fn default_dex_jwks_cache_ttl_seconds() -> u64 {
+    300
+}
+
+fn default_dex_internal_connector_ids() -> Vec<String> {
+    vec!["local".to_string()]
+}
+
 // Log Patterns defaults
 fn default_log_patterns_max_logs() -> usize {
     10000
@@ -142,11 +371,33 @@ impl Default for VisdataConfig {
             // Enterprise defaults
             openfga_url: default_openfga_url(),
             openfga_store_name: default_openfga_store_name(),
+            openfga_api_token: default_openfga_api_token(),
+            openfga_auth_method: default_openfga_auth_method(),
+            role_relation_overrides: HashMap::new(),
+            root_email: default_root_email(),
+            root_emails: HashSet::new(),
+            openfga_include_meta_org: true,
+            openfga_auto_upgrade_model: false,
+            is_cloud: false,
+            trust_root_role_string: false,
+            openfga_max_concurrent_requests: default_openfga_max_concurrent_requests(),
+            openfga_init_write_concurrency: default_openfga_init_write_concurrency(),
+            openfga_max_retries: default_openfga_max_retries(),
+            openfga_retry_base_delay_ms: default_openfga_retry_base_delay_ms(),
+            openfga_tls_ca_cert_path: None,
+            openfga_tls_skip_verify: false,
             dex_grpc_url: default_dex_grpc_url(),
             dex_issuer_url: default_dex_issuer_url(),
             dex_client_id: default_dex_client_id(),
             dex_client_secret: String::new(),
+            dex_additional_audiences: Vec::new(),
             dex_redirect_uri: default_dex_redirect_uri(),
+            dex_post_logout_redirect_uri: None,
+            dex_jwks_cache_ttl_seconds: default_dex_jwks_cache_ttl_seconds(),
+            dex_group_role_mappings: HashMap::new(),
+            dex_internal_connector_ids: default_dex_internal_connector_ids(),
+            dex_tls_ca_cert_path: None,
+            dex_tls_skip_verify: false,
             // Log Patterns defaults
             log_patterns_max_logs: default_log_patterns_max_logs(),
             log_patterns_min_cluster_size: default_log_patterns_min_cluster_size(),
@@ -158,6 +409,149 @@ impl Default for VisdataConfig {
     }
 }
 
+impl VisdataConfig {
+    /// Build a configuration from `VISDATA_*` environment variables
+    ///
+    /// Unset variables keep their defaults:
+    /// - `VISDATA_RBAC_ENABLED`, `VISDATA_SSO_ENABLED`, `VISDATA_ENCRYPTION_KEY`,
+    ///   `VISDATA_ROOT_EMAIL`, `VISDATA_ROOT_EMAILS` (comma-separated)
+    /// - `VISDATA_OPENFGA_URL`, `VISDATA_OPENFGA_STORE`, `VISDATA_OPENFGA_TLS_CA_CERT`,
+    ///   `VISDATA_OPENFGA_TLS_SKIP_VERIFY`
+    /// - `VISDATA_DEX_GRPC_URL`, `VISDATA_DEX_ISSUER_URL`, `VISDATA_DEX_CLIENT_ID`,
+    ///   `VISDATA_DEX_CLIENT_SECRET`, `VISDATA_DEX_REDIRECT_URI`,
+    ///   `VISDATA_DEX_POST_LOGOUT_REDIRECT_URI`, `VISDATA_DEX_TLS_CA_CERT`,
+    ///   `VISDATA_DEX_TLS_SKIP_VERIFY`
+    /// - `VISDATA_LOG_PATTERNS_MAX_LOGS`, `VISDATA_LOG_PATTERNS_MIN_CLUSTER_SIZE`,
+    ///   `VISDATA_LOG_PATTERNS_SIMILARITY_THRESHOLD`, `VISDATA_LOG_PATTERNS_DRAIN_DEPTH`,
+    ///   `VISDATA_LOG_PATTERNS_DRAIN_MAX_CHILD`, `VISDATA_LOG_PATTERNS_MAX_CLUSTERS`
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Build a configuration from a variable lookup (see `from_env`)
+    ///
+    /// Values that fail to parse are logged and keep their defaults.
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        fn set<T: std::str::FromStr>(
+            lookup: &impl Fn(&str) -> Option<String>,
+            key: &str,
+            field: &mut T,
+        ) {
+            let Some(value) = lookup(key) else {
+                return;
+            };
+            match value.trim().parse() {
+                Ok(parsed) => *field = parsed,
+                Err(_) => {
+                    tracing::warn!("[VISDATA] Ignoring invalid value for {}: {:?}", key, value)
+                }
+            }
+        }
+
+        let mut cfg = Self::default();
+        set(&lookup, "VISDATA_RBAC_ENABLED", &mut cfg.rbac_enabled);
+        set(&lookup, "VISDATA_SSO_ENABLED", &mut cfg.sso_enabled);
+        if let Some(key) = lookup("VISDATA_ENCRYPTION_KEY") {
+            cfg.encryption_key = Some(key);
+        }
+        set(&lookup, "VISDATA_OPENFGA_URL", &mut cfg.openfga_url);
+        set(&lookup, "VISDATA_OPENFGA_STORE", &mut cfg.openfga_store_name);
+        set(&lookup, "VISDATA_ROOT_EMAIL", &mut cfg.root_email);
+        if let Some(emails) = lookup("VISDATA_ROOT_EMAILS") {
+            cfg.root_emails = emails
+                .split(',')
+                .map(str::trim)
+                .filter(|email| !email.is_empty())
+                .map(str::to_string)
+                .collect();
+        }
+        if let Some(path) = lookup("VISDATA_OPENFGA_TLS_CA_CERT") {
+            cfg.openfga_tls_ca_cert_path = Some(path);
+        }
+        set(&lookup, "VISDATA_OPENFGA_TLS_SKIP_VERIFY", &mut cfg.openfga_tls_skip_verify);
+        set(&lookup, "VISDATA_DEX_GRPC_URL", &mut cfg.dex_grpc_url);
+        set(&lookup, "VISDATA_DEX_ISSUER_URL", &mut cfg.dex_issuer_url);
+        set(&lookup, "VISDATA_DEX_CLIENT_ID", &mut cfg.dex_client_id);
+        set(&lookup, "VISDATA_DEX_CLIENT_SECRET", &mut cfg.dex_client_secret);
+        set(&lookup, "VISDATA_DEX_REDIRECT_URI", &mut cfg.dex_redirect_uri);
+        if let Some(uri) = lookup("VISDATA_DEX_POST_LOGOUT_REDIRECT_URI") {
+            cfg.dex_post_logout_redirect_uri = Some(uri);
+        }
+        if let Some(path) = lookup("VISDATA_DEX_TLS_CA_CERT") {
+            cfg.dex_tls_ca_cert_path = Some(path);
+        }
+        set(&lookup, "VISDATA_DEX_TLS_SKIP_VERIFY", &mut cfg.dex_tls_skip_verify);
+        set(&lookup, "VISDATA_LOG_PATTERNS_MAX_LOGS", &mut cfg.log_patterns_max_logs);
+        set(
+            &lookup,
+            "VISDATA_LOG_PATTERNS_MIN_CLUSTER_SIZE",
+            &mut cfg.log_patterns_min_cluster_size,
+        );
+        set(
+            &lookup,
+            "VISDATA_LOG_PATTERNS_SIMILARITY_THRESHOLD",
+            &mut cfg.log_patterns_similarity_threshold,
+        );
+        set(&lookup, "VISDATA_LOG_PATTERNS_DRAIN_DEPTH", &mut cfg.log_patterns_drain_depth);
+        set(
+            &lookup,
+            "VISDATA_LOG_PATTERNS_DRAIN_MAX_CHILD",
+            &mut cfg.log_patterns_drain_max_child,
+        );
+        set(&lookup, "VISDATA_LOG_PATTERNS_MAX_CLUSTERS", &mut cfg.log_patterns_max_clusters);
+        cfg
+    }
+
+    /// Validate the configuration, returning every problem found
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        let urls = [
+            ("openfga_url", &self.openfga_url),
+            ("dex_grpc_url", &self.dex_grpc_url),
+            ("dex_issuer_url", &self.dex_issuer_url),
+            ("dex_redirect_uri", &self.dex_redirect_uri),
+        ];
+        let post_logout = self
+            .dex_post_logout_redirect_uri
+            .as_ref()
+            .map(|uri| ("dex_post_logout_redirect_uri", uri));
+        for (name, value) in urls.into_iter().chain(post_logout) {
+            let problem = match url::Url::parse(value) {
+                Ok(url) if !matches!(url.scheme(), "http" | "https") => {
+                    Some("scheme must be http or https".to_string())
+                }
+                Ok(url) if !url.has_host() => Some("missing host".to_string()),
+                Ok(_) => None,
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(problem) = problem {
+                errors.push(format!("{} is not a valid URL ({:?}): {}", name, value, problem));
+            }
+        }
+
+        if !(0.0..=1.0).contains(&self.log_patterns_similarity_threshold) {
+            errors.push(format!(
+                "log_patterns_similarity_threshold must be within 0.0-1.0, got {}",
+                self.log_patterns_similarity_threshold
+            ));
+        }
+        if self.log_patterns_drain_depth == 0 {
+            errors.push("log_patterns_drain_depth must be nonzero".to_string());
+        }
+        if self.log_patterns_drain_max_child == 0 {
+            errors.push("log_patterns_drain_max_child must be nonzero".to_string());
+        }
+
+        let key_check = self.encryption_key.as_deref().map(crate::common::crypto::validate_key);
+        if let Some(Err(e)) = key_check {
+            errors.push(e.to_string());
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+}
+
 /// Cache configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheConfig {
@@ -180,7 +574,7 @@ impl Default for CacheConfig {
 }
 
 /// OIDC provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OIDCConfig {
     /// OIDC issuer URL
     pub issuer_url: String,
@@ -211,6 +605,38 @@ pub struct OIDCConfig {
     pub default_role: Option<String>,
 }
 
+impl fmt::Debug for OIDCConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Destructured so a new field cannot be left out
+        let Self {
+            issuer_url,
+            client_id,
+            client_secret,
+            scopes,
+            redirect_uri,
+            email_claim,
+            name_claim,
+            groups_claim,
+            group_role_mappings,
+            auto_create_users,
+            default_role,
+        } = self;
+        f.debug_struct("OIDCConfig")
+            .field("issuer_url", issuer_url)
+            .field("client_id", client_id)
+            .field("client_secret", &redact::secret(client_secret))
+            .field("scopes", scopes)
+            .field("redirect_uri", redirect_uri)
+            .field("email_claim", email_claim)
+            .field("name_claim", name_claim)
+            .field("groups_claim", groups_claim)
+            .field("group_role_mappings", group_role_mappings)
+            .field("auto_create_users", auto_create_users)
+            .field("default_role", default_role)
+            .finish()
+    }
+}
+
 fn default_oidc_scopes() -> Vec<String> {
     vec![
         "openid".to_string(),
@@ -232,7 +658,7 @@ fn default_true() -> bool {
 }
 
 /// LDAP provider configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct LDAPConfig {
     /// LDAP server URL (e.g., ldap://ldap.example.com:389 or ldaps://...)
     pub server_url: String,
@@ -274,6 +700,44 @@ pub struct LDAPConfig {
     pub timeout_seconds: u64,
 }
 
+impl fmt::Debug for LDAPConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Destructured so a new field cannot be left out
+        let Self {
+            server_url,
+            bind_dn,
+            bind_password,
+            user_base_dn,
+            user_filter,
+            user_attr_email,
+            user_attr_name,
+            group_base_dn,
+            group_filter,
+            group_attr_name,
+            group_role_mappings,
+            use_ssl,
+            skip_ssl_verify,
+            timeout_seconds,
+        } = self;
+        f.debug_struct("LDAPConfig")
+            .field("server_url", server_url)
+            .field("bind_dn", bind_dn)
+            .field("bind_password", &redact::secret(bind_password))
+            .field("user_base_dn", user_base_dn)
+            .field("user_filter", user_filter)
+            .field("user_attr_email", user_attr_email)
+            .field("user_attr_name", user_attr_name)
+            .field("group_base_dn", group_base_dn)
+            .field("group_filter", group_filter)
+            .field("group_attr_name", group_attr_name)
+            .field("group_role_mappings", group_role_mappings)
+            .field("use_ssl", use_ssl)
+            .field("skip_ssl_verify", skip_ssl_verify)
+            .field("timeout_seconds", timeout_seconds)
+            .finish()
+    }
+}
+
 fn default_user_filter() -> String {
     "(&(objectClass=person)(uid={0}))".to_string()
 }
@@ -337,6 +801,100 @@ mod tests {
     // VisdataConfig Tests
     // ========================================================================
 
+    #[test]
+    fn test_visdata_config_from_lookup() {
+        let vars: HashMap<&str, &str> = [
+            ("VISDATA_RBAC_ENABLED", "false"),
+            ("VISDATA_ENCRYPTION_KEY", "a2V5"),
+            ("VISDATA_OPENFGA_URL", "http://openfga:8080"),
+            ("VISDATA_OPENFGA_STORE", "o2"),
+            ("VISDATA_DEX_ISSUER_URL", "https://dex.example.com"),
+            ("VISDATA_DEX_CLIENT_SECRET", "s3cret"),
+            ("VISDATA_DEX_POST_LOGOUT_REDIRECT_URI", "https://o2.example.com/web/"),
+            ("VISDATA_ROOT_EMAILS", "root@example.com, ops@example.com,"),
+            ("VISDATA_DEX_TLS_CA_CERT", "/etc/pki/internal-ca.pem"),
+            ("VISDATA_DEX_TLS_SKIP_VERIFY", "true"),
+            ("VISDATA_LOG_PATTERNS_MAX_LOGS", " 500 "),
+            ("VISDATA_LOG_PATTERNS_SIMILARITY_THRESHOLD", "0.8"),
+            // Invalid values keep their defaults
+            ("VISDATA_SSO_ENABLED", "maybe"),
+            ("VISDATA_LOG_PATTERNS_DRAIN_DEPTH", "-1"),
+        ]
+        .into_iter()
+        .collect();
+        let config = VisdataConfig::from_lookup(|key| vars.get(key).map(|v| v.to_string()));
+
+        assert!(!config.rbac_enabled);
+        assert!(config.sso_enabled);
+        assert_eq!(config.encryption_key.as_deref(), Some("a2V5"));
+        assert_eq!(config.openfga_url, "http://openfga:8080");
+        assert_eq!(config.openfga_store_name, "o2");
+        assert_eq!(config.dex_issuer_url, "https://dex.example.com");
+        assert_eq!(config.dex_client_secret, "s3cret");
+        assert_eq!(
+            config.dex_post_logout_redirect_uri.as_deref(),
+            Some("https://o2.example.com/web/")
+        );
+        let root_emails: HashSet<String> =
+            ["root@example.com", "ops@example.com"].into_iter().map(String::from).collect();
+        assert_eq!(config.root_emails, root_emails);
+        assert_eq!(config.dex_tls_ca_cert_path.as_deref(), Some("/etc/pki/internal-ca.pem"));
+        assert!(config.dex_tls_skip_verify);
+        assert_eq!(config.log_patterns_max_logs, 500);
+        assert!((config.log_patterns_similarity_threshold - 0.8).abs() < f64::EPSILON);
+        assert_eq!(config.log_patterns_drain_depth, 4);
+
+        // Unset variables keep their defaults
+        assert_eq!(config.dex_grpc_url, "http://localhost:5557");
+        assert_eq!(config.dex_client_id, "openobserve");
+        assert!(config.openfga_tls_ca_cert_path.is_none());
+    }
+
+    #[test]
+    fn test_visdata_config_debug_redacts_secrets() {
+        let config = VisdataConfig {
+            encryption_key: Some("a2V5LXNlY3JldA==".to_string()),
+            openfga_api_token: Some("fga-token".to_string()),
+            dex_client_secret: "dex-secret".to_string(),
+            ..Default::default()
+        };
+        let debug = format!("{:?}", config);
+        for secret in ["a2V5LXNlY3JldA==", "fga-token", "dex-secret"] {
+            assert!(!debug.contains(secret), "{} leaked", secret);
+        }
+        assert!(debug.contains(redact::REDACTED));
+        assert!(debug.contains("http://localhost:8080"));
+    }
+
+    #[test]
+    fn test_visdata_config_validate() {
+        assert_eq!(VisdataConfig::default().validate(), Ok(()));
+        let no_sso = VisdataConfig { sso_enabled: false, ..Default::default() };
+        assert_eq!(no_sso.validate(), Ok(()));
+
+        let valid_key = VisdataConfig {
+            encryption_key: Some("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(valid_key.validate(), Ok(()));
+
+        let config = VisdataConfig {
+            openfga_url: "openfga:8080/api".to_string(),
+            dex_issuer_url: "not a url".to_string(),
+            log_patterns_similarity_threshold: 1.5,
+            log_patterns_drain_depth: 0,
+            log_patterns_drain_max_child: 0,
+            encryption_key: Some("c2hvcnQ=".to_string()),
+            ..Default::default()
+        };
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 6, "{:?}", errors);
+        assert!(errors[0].starts_with("openfga_url is not a valid URL"));
+        assert!(errors[1].starts_with("dex_issuer_url is not a valid URL"));
+        assert!(errors[2].contains("log_patterns_similarity_threshold"));
+        assert_eq!(errors[5], "Invalid encryption_key: expected 32 bytes, got 5");
+    }
+
     #[test]
     fn test_visdata_config_default() {
         let config = VisdataConfig::default();
@@ -348,6 +906,19 @@ mod tests {
         // OpenFGA defaults
         assert_eq!(config.openfga_url, "http://localhost:8080");
         assert_eq!(config.openfga_store_name, "openobserve");
+        assert!(config.role_relation_overrides.is_empty());
+        assert_eq!(config.root_email, "root@visdata.com");
+        assert!(config.root_emails.is_empty());
+        assert!(config.openfga_include_meta_org);
+        assert!(!config.openfga_auto_upgrade_model);
+        assert!(!config.is_cloud);
+        assert!(!config.trust_root_role_string);
+        assert_eq!(config.openfga_max_concurrent_requests, 50);
+        assert_eq!(config.openfga_init_write_concurrency, 4);
+        assert_eq!(config.openfga_max_retries, 3);
+        assert_eq!(config.openfga_retry_base_delay_ms, 100);
+        assert!(config.openfga_tls_ca_cert_path.is_none());
+        assert!(!config.openfga_tls_skip_verify);
 
         // Dex defaults
         assert_eq!(config.dex_grpc_url, "http://localhost:5557");
@@ -355,6 +926,9 @@ mod tests {
         assert_eq!(config.dex_client_id, "openobserve");
         assert_eq!(config.dex_client_secret, "");
         assert_eq!(config.dex_redirect_uri, "http://localhost:5080/config/redirect");
+        assert_eq!(config.dex_jwks_cache_ttl_seconds, 300);
+        assert!(config.dex_tls_ca_cert_path.is_none());
+        assert!(!config.dex_tls_skip_verify);
 
         // Log patterns defaults
         assert_eq!(config.log_patterns_max_logs, 10000);
@@ -8,14 +8,19 @@
 //! Dex HTTP client (using REST API instead of gRPC for compatibility)
 //!
 //! Note: Dex also provides a gRPC API, but this implementation uses HTTP
-//! for better compatibility with the existing infrastructure.
+//! for better compatibility with the existing infrastructure. Connector
+//! management, which has no HTTP equivalent, goes through gRPC.
 
 use std::time::Duration;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use tracing::field::Empty;
 
+use crate::common::telemetry::{self, CallTimer};
+use crate::common::tls;
 use super::config::DexConfig;
 use super::error::{Error, Result};
+use super::proto;
 
 /// Dex HTTP client wrapper
 pub struct DexClient {
@@ -54,10 +59,15 @@ pub struct RefreshTokenRef {
 impl DexClient {
     /// Create a new Dex client
     pub async fn new(config: &DexConfig) -> Result<Self> {
-        let http = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_seconds))
-            .build()
-            .map_err(|e| Error::HttpError(e.to_string()))?;
+        let builder = Client::builder().timeout(Duration::from_secs(config.timeout_seconds));
+        let http = tls::configure(
+            builder,
+            config.tls_ca_cert_path.as_deref(),
+            config.tls_skip_verify,
+            "Dex",
+        )?
+        .build()
+        .map_err(|e| Error::HttpError(e.to_string()))?;
 
         Ok(Self {
             http,
@@ -70,12 +80,22 @@ impl DexClient {
         &self.config
     }
 
+    /// HTTP client for calls to the issuer, built with the Dex TLS settings
+    ///
+    /// Cheap to clone; it shares the connection pool with this client.
+    pub fn http_client(&self) -> Client {
+        self.http.clone()
+    }
+
     // ========================================================================
-    // Connector Management (via Dex API if available)
+    // Connector Management (via the Dex gRPC API)
     // ========================================================================
 
     /// Create a new connector
-    /// Note: This requires Dex API access which may need to be configured
+    ///
+    /// Dex only serves connector writes with `DEX_API_CONNECTORS_CRUD=true`.
+    /// `config_json` is handed to Dex as is, so secrets in it must be
+    /// plaintext. Returns `false` if a connector with `id` already exists.
     pub async fn create_connector(
         &mut self,
         id: &str,
@@ -83,22 +103,21 @@ impl DexClient {
         name: &str,
         config_json: &str,
     ) -> Result<bool> {
-        // Dex doesn't have a standard HTTP API for connector management
-        // This is typically done through configuration files
-        // For now, we log and return success
-        tracing::info!(
-            "[Auth] Connector creation requested: {} ({}) - Note: Dex connectors are typically configured via config file",
-            id,
-            connector_type
-        );
-
-        // Store connector info for reference
-        let _ = (name, config_json);
-
-        Ok(true)
+        let req = proto::CreateConnectorReq {
+            connector: Some(proto::Connector {
+                id: id.to_string(),
+                r#type: connector_type.to_string(),
+                name: name.to_string(),
+                config: config_json.as_bytes().to_vec(),
+            }),
+        };
+        let resp: proto::CreateConnectorResp =
+            self.grpc_unary(proto::CREATE_CONNECTOR_PATH, req).await?;
+
+        Ok(!resp.already_exists)
     }
 
-    /// Update an existing connector
+    /// Update an existing connector, replacing its type, name and config
     pub async fn update_connector(
         &mut self,
         id: &str,
@@ -106,45 +125,41 @@ impl DexClient {
         name: &str,
         config_json: &str,
     ) -> Result<()> {
-        tracing::info!(
-            "[Auth] Connector update requested: {} - Note: Dex connectors are typically configured via config file",
-            id
-        );
-
-        let _ = (connector_type, name, config_json);
-
+        let req = proto::UpdateConnectorReq {
+            id: id.to_string(),
+            new_type: connector_type.to_string(),
+            new_name: name.to_string(),
+            new_config: config_json.as_bytes().to_vec(),
+        };
+        let resp: proto::UpdateConnectorResp =
+            self.grpc_unary(proto::UPDATE_CONNECTOR_PATH, req).await?;
+
+        if resp.not_found {
+            return Err(Error::ConnectorNotFound(id.to_string()));
+        }
         Ok(())
     }
 
     /// Delete a connector
     pub async fn delete_connector(&mut self, id: &str) -> Result<()> {
-        tracing::info!(
-            "[Auth] Connector deletion requested: {} - Note: Dex connectors are typically configured via config file",
-            id
-        );
+        let req = proto::DeleteConnectorReq { id: id.to_string() };
+        let resp: proto::DeleteConnectorResp =
+            self.grpc_unary(proto::DELETE_CONNECTOR_PATH, req).await?;
 
+        if resp.not_found {
+            return Err(Error::ConnectorNotFound(id.to_string()));
+        }
         Ok(())
     }
 
     /// List all connectors
-    /// Returns connectors from Dex's well-known discovery endpoint
+    /// Uses the Dex gRPC `ListConnectors` RPC
     pub async fn list_connectors(&mut self) -> Result<Vec<Connector>> {
-        // Try to get connectors from Dex discovery
-        let discovery_url = format!("{}/.well-known/openid-configuration", self.config.issuer_url);
-
-        let resp = self.http.get(&discovery_url).send().await;
-
-        match resp {
-            Ok(response) if response.status().is_success() => {
-                // Dex doesn't expose connectors via HTTP API
-                // Return empty list - connectors should be managed via config
-                Ok(vec![])
-            }
-            _ => {
-                // Return empty list if Dex is not reachable
-                Ok(vec![])
-            }
-        }
+        let resp: proto::ListConnectorResp = self
+            .grpc_unary(proto::LIST_CONNECTORS_PATH, proto::ListConnectorReq {})
+            .await?;
+
+        Ok(resp.connectors.into_iter().map(connector_from_proto).collect())
     }
 
     // ========================================================================
@@ -153,7 +168,15 @@ impl DexClient {
 
     /// Verify user password (for native login)
     /// This uses the token endpoint with password grant
+    ///
+    /// The span carries no user fields; the credentials stay out of traces.
+    #[tracing::instrument(
+        name = "dex.verify_password",
+        skip_all,
+        fields(operation = "verify_password", status = Empty, elapsed_ms = Empty)
+    )]
     pub async fn verify_password(&mut self, email: &str, password: &str) -> Result<bool> {
+        let _timer = CallTimer::start();
         let token_url = format!("{}/token", self.config.issuer_url);
 
         let params = [
@@ -169,6 +192,7 @@ impl DexClient {
             .form(&params)
             .send()
             .await?;
+        telemetry::record_status(resp.status().as_u16());
 
         if resp.status().is_success() {
             Ok(true)
@@ -183,53 +207,82 @@ impl DexClient {
     }
 
     /// Create a password entry (for native login)
-    /// Note: This typically requires Dex gRPC API access
+    ///
+    /// `password_hash` must be a bcrypt hash. Returns `false` if a password
+    /// for `email` already exists.
     pub async fn create_password(
         &mut self,
         email: &str,
-        _password_hash: &[u8],
+        password_hash: &[u8],
         username: &str,
         user_id: &str,
     ) -> Result<bool> {
-        tracing::info!(
-            "[Auth] Password creation requested for: {} - Note: Dex password management requires gRPC API",
-            email
-        );
-
-        let _ = (username, user_id);
-
-        Ok(true)
+        let req = proto::CreatePasswordReq {
+            password: Some(proto::Password {
+                email: email.to_string(),
+                hash: password_hash.to_vec(),
+                username: username.to_string(),
+                user_id: user_id.to_string(),
+            }),
+        };
+        let resp: proto::CreatePasswordResp =
+            self.grpc_unary(proto::CREATE_PASSWORD_PATH, req).await?;
+
+        Ok(!resp.already_exists)
     }
 
     /// Update a password
+    ///
+    /// `new_hash` must be a bcrypt hash; an empty hash keeps the current one.
     pub async fn update_password(
         &mut self,
         email: &str,
-        _new_hash: &[u8],
-        _new_username: Option<&str>,
+        new_hash: &[u8],
+        new_username: Option<&str>,
     ) -> Result<()> {
-        tracing::info!(
-            "[Auth] Password update requested for: {} - Note: Dex password management requires gRPC API",
-            email
-        );
-
+        let req = proto::UpdatePasswordReq {
+            email: email.to_string(),
+            new_hash: new_hash.to_vec(),
+            new_username: new_username.unwrap_or_default().to_string(),
+        };
+        let resp: proto::UpdatePasswordResp =
+            self.grpc_unary(proto::UPDATE_PASSWORD_PATH, req).await?;
+
+        if resp.not_found {
+            return Err(Error::UserNotFound(email.to_string()));
+        }
         Ok(())
     }
 
     /// Delete a password entry
     pub async fn delete_password(&mut self, email: &str) -> Result<()> {
-        tracing::info!(
-            "[Auth] Password deletion requested for: {} - Note: Dex password management requires gRPC API",
-            email
-        );
-
+        let req = proto::DeletePasswordReq {
+            email: email.to_string(),
+        };
+        let resp: proto::DeletePasswordResp =
+            self.grpc_unary(proto::DELETE_PASSWORD_PATH, req).await?;
+
+        if resp.not_found {
+            return Err(Error::UserNotFound(email.to_string()));
+        }
         Ok(())
     }
 
-    /// List all passwords
+    /// List all passwords (hashes are not returned)
     pub async fn list_passwords(&mut self) -> Result<Vec<Password>> {
-        // Dex doesn't expose passwords via HTTP API
-        Ok(vec![])
+        let resp: proto::ListPasswordResp = self
+            .grpc_unary(proto::LIST_PASSWORDS_PATH, proto::ListPasswordReq {})
+            .await?;
+
+        Ok(resp
+            .passwords
+            .into_iter()
+            .map(|p| Password {
+                email: p.email,
+                username: p.username,
+                user_id: p.user_id,
+            })
+            .collect())
     }
 
     // ========================================================================
@@ -289,39 +342,99 @@ impl DexClient {
         Ok(vec![])
     }
 
-    /// Revoke a refresh token
-    pub async fn revoke_refresh_token(&mut self, user_id: &str, client_id: &str) -> Result<()> {
-        // Try to revoke via token revocation endpoint if available
+    /// Revoke a refresh token at the token revocation endpoint (RFC 7009)
+    ///
+    /// Fails if Dex does not answer with a 2xx status.
+    pub async fn revoke_refresh_token(&self, refresh_token: &str) -> Result<()> {
         let revoke_url = format!("{}/token/revoke", self.config.issuer_url);
+        let params = revocation_params(&self.config, refresh_token);
 
-        let _ = self.http
-            .post(&revoke_url)
-            .form(&[
-                ("token_type_hint", "refresh_token"),
-                ("client_id", &self.config.client_id),
-            ])
-            .send()
-            .await;
+        let resp = self.http.post(&revoke_url).form(&params).send().await?;
 
-        tracing::info!(
-            "[Auth] Refresh token revocation requested for user: {}, client: {}",
-            user_id,
-            client_id
-        );
+        if !resp.status().is_success() {
+            return Err(Error::HttpError(format!(
+                "Token revocation failed: {}",
+                resp.status()
+            )));
+        }
 
         Ok(())
     }
 
+    // ========================================================================
+    // Logout
+    // ========================================================================
+
+    /// Build the URL that ends the user's session at the identity provider
+    ///
+    /// The endpoint is taken from `end_session_endpoint` in the discovery
+    /// document, falling back to `{issuer_url}/logout`.
+    pub async fn end_session_url(
+        &self,
+        id_token_hint: Option<&str>,
+        post_logout_redirect_uri: Option<&str>,
+    ) -> Result<String> {
+        let endpoint = discover_endpoint(
+            &self.http,
+            &self.config.issuer_url,
+            "end_session_endpoint",
+            "logout",
+        )
+        .await;
+        end_session_params(&endpoint, id_token_hint, post_logout_redirect_uri)
+    }
+
+    // ========================================================================
+    // gRPC
+    // ========================================================================
+
+    /// Make a unary call to the Dex gRPC API
+    async fn grpc_unary<Req, Resp>(&self, path: &'static str, req: Req) -> Result<Resp>
+    where
+        Req: prost::Message + Send + Sync + 'static,
+        Resp: prost::Message + Default + Send + Sync + 'static,
+    {
+        let channel = tonic::transport::Endpoint::from_shared(self.config.grpc_url.clone())
+            .map_err(|e| Error::ConfigError(format!("Invalid Dex gRPC URL: {}", e)))?
+            .timeout(Duration::from_secs(self.config.timeout_seconds))
+            .connect()
+            .await
+            .map_err(|e| Error::GrpcError(format!("Failed to connect to Dex: {}", e)))?;
+
+        let mut grpc = tonic::client::Grpc::new(channel);
+        grpc.ready()
+            .await
+            .map_err(|e| Error::GrpcError(format!("Dex gRPC not ready: {}", e)))?;
+
+        let resp = grpc
+            .unary(
+                tonic::Request::new(req),
+                proto::method_path(path),
+                tonic_prost::ProstCodec::<Req, Resp>::default(),
+            )
+            .await
+            .map_err(|status| Error::GrpcError(format!("{} failed: {}", path, status.message())))?;
+
+        Ok(resp.into_inner())
+    }
+
     // ========================================================================
     // Version / Health
     // ========================================================================
 
     /// Get Dex server version (via health check)
+    #[tracing::instrument(
+        name = "dex.get_version",
+        skip_all,
+        fields(operation = "get_version", status = Empty, elapsed_ms = Empty)
+    )]
     pub async fn get_version(&mut self) -> Result<(String, i32)> {
+        let _timer = CallTimer::start();
         // Check if Dex is healthy
         let health_url = format!("{}/healthz", self.config.issuer_url);
 
         let resp = self.http.get(&health_url).send().await?;
+        telemetry::record_status(resp.status().as_u16());
 
         if resp.status().is_success() {
             // Return placeholder version - Dex doesn't expose version via HTTP
@@ -345,6 +458,82 @@ impl DexClient {
     }
 }
 
+/// Discover an endpoint URL from the issuer's OIDC discovery document
+///
+/// Falls back to `{issuer_url}/{default_path}` (the Dex default) when
+/// discovery fails or the document lacks `field`.
+pub(crate) async fn discover_endpoint(
+    client: &Client,
+    issuer_url: &str,
+    field: &str,
+    default_path: &str,
+) -> String {
+    let discovery_url = format!("{}/.well-known/openid-configuration", issuer_url);
+
+    let discovered = match client.get(&discovery_url).send().await {
+        Ok(resp) if resp.status().is_success() => resp
+            .json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|config| config[field].as_str().map(|s| s.to_string())),
+        _ => None,
+    };
+    discovered.unwrap_or_else(|| format!("{}/{}", issuer_url, default_path))
+}
+
+/// Append the RP-initiated logout parameters to an end-session endpoint
+fn end_session_params(
+    endpoint: &str,
+    id_token_hint: Option<&str>,
+    post_logout_redirect_uri: Option<&str>,
+) -> Result<String> {
+    let mut url = reqwest::Url::parse(endpoint)
+        .map_err(|e| Error::ConfigError(format!("Invalid end session URL {}: {}", endpoint, e)))?;
+    let params = [
+        ("id_token_hint", id_token_hint),
+        ("post_logout_redirect_uri", post_logout_redirect_uri),
+    ];
+    for (name, value) in params {
+        if let Some(value) = value {
+            url.query_pairs_mut().append_pair(name, value);
+        }
+    }
+    Ok(url.into())
+}
+
+/// Convert a Dex API connector, parsing its JSON config
+///
+/// A config that is not valid JSON is kept as a string.
+fn connector_from_proto(connector: proto::Connector) -> Connector {
+    let config = serde_json::from_slice(&connector.config).unwrap_or_else(|_| {
+        if connector.config.is_empty() {
+            serde_json::Value::Null
+        } else {
+            serde_json::Value::String(String::from_utf8_lossy(&connector.config).into_owned())
+        }
+    });
+
+    Connector {
+        id: connector.id,
+        connector_type: connector.r#type,
+        name: connector.name,
+        config,
+    }
+}
+
+/// Form fields for an RFC 7009 refresh token revocation request
+fn revocation_params<'a>(config: &'a DexConfig, refresh_token: &'a str) -> Vec<(&'static str, &'a str)> {
+    let mut params = vec![
+        ("token", refresh_token),
+        ("token_type_hint", "refresh_token"),
+        ("client_id", config.client_id.as_str()),
+    ];
+    if !config.client_secret.is_empty() {
+        params.push(("client_secret", config.client_secret.as_str()));
+    }
+    params
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -355,4 +544,75 @@ mod tests {
         assert_eq!(config.grpc_url, "http://localhost:5557");
         assert!(config.native_login_enabled);
     }
+
+    #[test]
+    fn test_end_session_params() {
+        let url = end_session_params(
+            "https://idp.example.com/logout?client=o2",
+            Some("id-token"),
+            Some("https://o2.example.com/web/"),
+        )
+        .unwrap();
+        assert_eq!(
+            url,
+            "https://idp.example.com/logout?client=o2&id_token_hint=id-token\
+             &post_logout_redirect_uri=https%3A%2F%2Fo2.example.com%2Fweb%2F"
+        );
+
+        let url = end_session_params("http://dex:5556/dex/logout", None, None).unwrap();
+        assert_eq!(url, "http://dex:5556/dex/logout");
+        assert!(end_session_params("not a url", None, None).is_err());
+    }
+
+    #[test]
+    fn test_revocation_params() {
+        let config = DexConfig::default();
+        let params = revocation_params(&config, "refresh-abc");
+        assert!(params.contains(&("token", "refresh-abc")));
+        assert!(params.contains(&("client_id", "openobserve")));
+        assert!(!params.iter().any(|(k, _)| *k == "client_secret"));
+
+        let config = config.with_client("openobserve", "s3cret");
+        let params = revocation_params(&config, "refresh-abc");
+        assert!(params.contains(&("client_secret", "s3cret")));
+    }
+
+    #[test]
+    fn test_connector_from_proto() {
+        let connector = connector_from_proto(proto::Connector {
+            id: "github".to_string(),
+            r#type: "github".to_string(),
+            name: "GitHub".to_string(),
+            config: br#"{"clientID":"abc"}"#.to_vec(),
+        });
+        assert_eq!(connector.id, "github");
+        assert_eq!(connector.connector_type, "github");
+        assert_eq!(connector.name, "GitHub");
+        assert_eq!(connector.config["clientID"], "abc");
+
+        let connector = connector_from_proto(proto::Connector {
+            config: b"not json".to_vec(),
+            ..Default::default()
+        });
+        assert_eq!(connector.config, serde_json::Value::String("not json".to_string()));
+
+        let connector = connector_from_proto(proto::Connector::default());
+        assert_eq!(connector.config, serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_list_connector_resp_decodes() {
+        use prost::Message;
+
+        let resp = proto::ListConnectorResp {
+            connectors: vec![proto::Connector {
+                id: "ldap".to_string(),
+                r#type: "ldap".to_string(),
+                name: "LDAP".to_string(),
+                config: b"{}".to_vec(),
+            }],
+        };
+        let decoded = proto::ListConnectorResp::decode(resp.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, resp);
+    }
 }
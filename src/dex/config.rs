@@ -8,9 +8,13 @@
 //! Dex authentication configuration
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::common::redact;
 
 /// Dex authentication configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct DexConfig {
     /// Dex gRPC API URL (e.g., "http://dex:5557")
     pub grpc_url: String,
@@ -21,12 +25,20 @@ pub struct DexConfig {
     /// OAuth2 Client Secret
     pub client_secret: String,
 
+    /// Other trusted client IDs whose tokens are accepted (e.g., a CLI client)
+    #[serde(default)]
+    pub additional_audiences: Vec<String>,
+
     /// OIDC Issuer URL (e.g., "https://dex.example.com")
     pub issuer_url: String,
 
     /// OAuth2 Redirect URI
     pub redirect_uri: String,
 
+    /// Where the identity provider sends the browser after single logout
+    #[serde(default)]
+    pub post_logout_redirect_uri: Option<String>,
+
     /// Default organization for new users
     pub default_org: String,
 
@@ -44,6 +56,87 @@ pub struct DexConfig {
 
     /// gRPC connection timeout in seconds
     pub timeout_seconds: u64,
+
+    /// PEM CA certificate (or bundle) trusted in addition to the system roots
+    #[serde(default)]
+    pub tls_ca_cert_path: Option<String>,
+
+    /// Skip TLS certificate verification (insecure; for testing only)
+    #[serde(default)]
+    pub tls_skip_verify: bool,
+
+    /// How long fetched JWKS signing keys are cached, in seconds
+    #[serde(default = "default_jwks_cache_ttl_seconds")]
+    pub jwks_cache_ttl_seconds: u64,
+
+    /// Read user groups from the UserInfo endpoint when the ID token lacks them
+    #[serde(default)]
+    pub userinfo_groups_fallback: bool,
+
+    /// Mapping from external (IdP) groups to internal custom roles, applied on SSO login
+    /// in orgs the user already belongs to
+    #[serde(default)]
+    pub group_role_mappings: HashMap<String, String>,
+
+    /// Dex connector IDs whose users are internal (native-password) users
+    #[serde(default = "default_internal_connector_ids")]
+    pub internal_connector_ids: Vec<String>,
+}
+
+impl fmt::Debug for DexConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Destructured so a new field cannot be left out
+        let Self {
+            grpc_url,
+            client_id,
+            client_secret,
+            additional_audiences,
+            issuer_url,
+            redirect_uri,
+            post_logout_redirect_uri,
+            default_org,
+            default_role,
+            native_login_enabled,
+            group_claim,
+            scopes,
+            timeout_seconds,
+            tls_ca_cert_path,
+            tls_skip_verify,
+            jwks_cache_ttl_seconds,
+            userinfo_groups_fallback,
+            group_role_mappings,
+            internal_connector_ids,
+        } = self;
+        f.debug_struct("DexConfig")
+            .field("grpc_url", grpc_url)
+            .field("client_id", client_id)
+            .field("client_secret", &redact::secret(client_secret))
+            .field("additional_audiences", additional_audiences)
+            .field("issuer_url", issuer_url)
+            .field("redirect_uri", redirect_uri)
+            .field("post_logout_redirect_uri", post_logout_redirect_uri)
+            .field("default_org", default_org)
+            .field("default_role", default_role)
+            .field("native_login_enabled", native_login_enabled)
+            .field("group_claim", group_claim)
+            .field("scopes", scopes)
+            .field("timeout_seconds", timeout_seconds)
+            .field("tls_ca_cert_path", tls_ca_cert_path)
+            .field("tls_skip_verify", tls_skip_verify)
+            .field("jwks_cache_ttl_seconds", jwks_cache_ttl_seconds)
+            .field("userinfo_groups_fallback", userinfo_groups_fallback)
+            .field("group_role_mappings", group_role_mappings)
+            .field("internal_connector_ids", internal_connector_ids)
+            .finish()
+    }
+}
+
+fn default_jwks_cache_ttl_seconds() -> u64 {
+    300
+}
+
+fn default_internal_connector_ids() -> Vec<String> {
+    vec!["local".to_string()]
 }
 
 impl Default for DexConfig {
@@ -52,8 +145,10 @@ impl Default for DexConfig {
             grpc_url: "http://localhost:5557".to_string(),
             client_id: "openobserve".to_string(),
             client_secret: String::new(),
+            additional_audiences: Vec::new(),
             issuer_url: "http://localhost:5556".to_string(),
             redirect_uri: "http://localhost:5080/config/redirect".to_string(),
+            post_logout_redirect_uri: None,
             default_org: "default".to_string(),
             default_role: "viewer".to_string(),
             native_login_enabled: true,
@@ -66,6 +161,12 @@ impl Default for DexConfig {
                 "offline_access".to_string(),
             ],
             timeout_seconds: 30,
+            tls_ca_cert_path: None,
+            tls_skip_verify: false,
+            jwks_cache_ttl_seconds: default_jwks_cache_ttl_seconds(),
+            userinfo_groups_fallback: false,
+            group_role_mappings: HashMap::new(),
+            internal_connector_ids: default_internal_connector_ids(),
         }
     }
 }
@@ -92,12 +193,57 @@ impl DexConfig {
         self
     }
 
+    /// Set additional trusted audiences accepted alongside the client ID
+    pub fn with_additional_audiences(mut self, audiences: Vec<String>) -> Self {
+        self.additional_audiences = audiences;
+        self
+    }
+
+    /// Audiences accepted in token validation: the client ID, then any additional ones
+    pub fn accepted_audiences(&self) -> Vec<&str> {
+        std::iter::once(self.client_id.as_str())
+            .chain(self.additional_audiences.iter().map(String::as_str))
+            .filter(|aud| !aud.is_empty())
+            .collect()
+    }
+
     /// Set redirect URI
     pub fn with_redirect_uri(mut self, uri: &str) -> Self {
         self.redirect_uri = uri.to_string();
         self
     }
 
+    /// Set the post-logout redirect URI sent on single logout
+    pub fn with_post_logout_redirect_uri(mut self, uri: Option<String>) -> Self {
+        self.post_logout_redirect_uri = uri;
+        self
+    }
+
+    /// Set the TLS CA certificate path and whether verification is skipped
+    pub fn with_tls(mut self, ca_cert_path: Option<String>, skip_verify: bool) -> Self {
+        self.tls_ca_cert_path = ca_cert_path;
+        self.tls_skip_verify = skip_verify;
+        self
+    }
+
+    /// Set the JWKS cache TTL in seconds
+    pub fn with_jwks_cache_ttl(mut self, seconds: u64) -> Self {
+        self.jwks_cache_ttl_seconds = seconds;
+        self
+    }
+
+    /// Set the external group -> internal role mappings
+    pub fn with_group_role_mappings(mut self, mappings: HashMap<String, String>) -> Self {
+        self.group_role_mappings = mappings;
+        self
+    }
+
+    /// Set the connector IDs whose users count as internal
+    pub fn with_internal_connector_ids(mut self, ids: Vec<String>) -> Self {
+        self.internal_connector_ids = ids;
+        self
+    }
+
     /// Validate configuration
     pub fn validate(&self) -> Result<(), String> {
         if self.grpc_url.is_empty() {
@@ -112,3 +258,17 @@ impl DexConfig {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accepted_audiences() {
+        let config = DexConfig::default();
+        assert_eq!(config.accepted_audiences(), vec!["openobserve"]);
+
+        let config = config.with_additional_audiences(vec!["o2-cli".to_string(), String::new()]);
+        assert_eq!(config.accepted_audiences(), vec!["openobserve", "o2-cli"]);
+    }
+}
@@ -24,12 +24,18 @@ pub enum Error {
     TokenExpired,
     /// User not found
     UserNotFound(String),
+    /// User already exists
+    UserExists(String),
     /// Connector not found
     ConnectorNotFound(String),
     /// Connector already exists
     ConnectorExists(String),
     /// Invalid connector configuration
     InvalidConnector(String),
+    /// Connector is disabled
+    ConnectorDisabled(String),
+    /// Token lacks a required scope
+    PermissionDenied(String),
     /// gRPC communication error
     GrpcError(String),
     /// HTTP communication error
@@ -47,9 +53,12 @@ impl fmt::Display for Error {
             Error::InvalidToken(msg) => write!(f, "Invalid token: {}", msg),
             Error::TokenExpired => write!(f, "Token has expired"),
             Error::UserNotFound(user) => write!(f, "User not found: {}", user),
+            Error::UserExists(user) => write!(f, "User already exists: {}", user),
             Error::ConnectorNotFound(id) => write!(f, "Connector not found: {}", id),
             Error::ConnectorExists(id) => write!(f, "Connector already exists: {}", id),
             Error::InvalidConnector(msg) => write!(f, "Invalid connector configuration: {}", msg),
+            Error::ConnectorDisabled(id) => write!(f, "Connector is disabled: {}", id),
+            Error::PermissionDenied(msg) => write!(f, "Permission denied: {}", msg),
             Error::GrpcError(msg) => write!(f, "gRPC error: {}", msg),
             Error::HttpError(msg) => write!(f, "HTTP error: {}", msg),
             Error::ConfigError(msg) => write!(f, "Configuration error: {}", msg),
@@ -67,9 +76,12 @@ impl ResponseError for Error {
             Error::InvalidToken(_) => (actix_web::http::StatusCode::UNAUTHORIZED, 401),
             Error::TokenExpired => (actix_web::http::StatusCode::UNAUTHORIZED, 401),
             Error::UserNotFound(_) => (actix_web::http::StatusCode::NOT_FOUND, 404),
+            Error::UserExists(_) => (actix_web::http::StatusCode::CONFLICT, 409),
             Error::ConnectorNotFound(_) => (actix_web::http::StatusCode::NOT_FOUND, 404),
             Error::ConnectorExists(_) => (actix_web::http::StatusCode::CONFLICT, 409),
             Error::InvalidConnector(_) => (actix_web::http::StatusCode::BAD_REQUEST, 400),
+            Error::ConnectorDisabled(_) => (actix_web::http::StatusCode::FORBIDDEN, 403),
+            Error::PermissionDenied(_) => (actix_web::http::StatusCode::FORBIDDEN, 403),
             Error::GrpcError(_) => (actix_web::http::StatusCode::BAD_GATEWAY, 502),
             Error::HttpError(_) => (actix_web::http::StatusCode::BAD_GATEWAY, 502),
             Error::ConfigError(_) => (actix_web::http::StatusCode::INTERNAL_SERVER_ERROR, 500),
@@ -107,6 +119,25 @@ impl From<jsonwebtoken::errors::Error> for Error {
     }
 }
 
+impl From<crate::common::tls::TlsError> for Error {
+    fn from(err: crate::common::tls::TlsError) -> Self {
+        Error::ConfigError(err.to_string())
+    }
+}
+
+impl From<crate::common::crypto::CryptoError> for Error {
+    fn from(err: crate::common::crypto::CryptoError) -> Self {
+        use crate::common::crypto::CryptoError;
+
+        match err {
+            CryptoError::MissingKey | CryptoError::InvalidKey(_) => {
+                Error::ConfigError(err.to_string())
+            }
+            CryptoError::Encrypt | CryptoError::Decrypt(_) => Error::Internal(err.to_string()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,6 +171,12 @@ mod tests {
         assert_eq!(format!("{}", err), "User not found: user@example.com");
     }
 
+    #[test]
+    fn test_error_display_user_exists() {
+        let err = Error::UserExists("user@example.com".to_string());
+        assert_eq!(format!("{}", err), "User already exists: user@example.com");
+    }
+
     #[test]
     fn test_error_display_connector_not_found() {
         let err = Error::ConnectorNotFound("ldap-prod".to_string());
@@ -161,6 +198,12 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_error_display_connector_disabled() {
+        let err = Error::ConnectorDisabled("okta".to_string());
+        assert_eq!(format!("{}", err), "Connector is disabled: okta");
+    }
+
     #[test]
     fn test_error_display_grpc_error() {
         let err = Error::GrpcError("connection refused".to_string());
@@ -217,6 +260,13 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::NOT_FOUND);
     }
 
+    #[test]
+    fn test_error_response_user_exists() {
+        let err = Error::UserExists("taken@test.com".to_string());
+        let resp = err.error_response();
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+    }
+
     #[test]
     fn test_error_response_connector_not_found() {
         let err = Error::ConnectorNotFound("missing-connector".to_string());
@@ -238,6 +288,21 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[test]
+    fn test_error_response_connector_disabled() {
+        let err = Error::ConnectorDisabled("okta".to_string());
+        let resp = err.error_response();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_error_response_permission_denied() {
+        let err = Error::PermissionDenied("missing scope: write".to_string());
+        assert_eq!(err.to_string(), "Permission denied: missing scope: write");
+        let resp = err.error_response();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    }
+
     #[test]
     fn test_error_response_grpc_error() {
         let err = Error::GrpcError("unavailable".to_string());
@@ -322,6 +387,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_crypto_error() {
+        use crate::common::crypto::CryptoError;
+
+        let err: Error = CryptoError::MissingKey.into();
+        match err {
+            Error::ConfigError(msg) => assert!(msg.contains("encryption_key is not configured")),
+            _ => panic!("Expected ConfigError"),
+        }
+
+        let err: Error = CryptoError::Decrypt("bad tag".to_string()).into();
+        assert!(matches!(err, Error::Internal(_)));
+    }
+
     // ========================================================================
     // std::error::Error Trait Tests
     // ========================================================================
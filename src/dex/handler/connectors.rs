@@ -88,6 +88,21 @@ pub async fn get_provider(path: web::Path<(String, String)>) -> Result<HttpRespo
     })))
 }
 
+/// GET /{org_id}/sso/providers/{provider_id}/config - Get provider config for editing
+///
+/// Secrets in the config are redacted.
+#[get("/{org_id}/sso/providers/{provider_id}/config")]
+pub async fn get_provider_config(path: web::Path<(String, String)>) -> Result<HttpResponse> {
+    let (_org_id, provider_id) = path.into_inner();
+
+    let connector = connector::get_connector_details(&provider_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "code": 200,
+        "data": connector
+    })))
+}
+
 /// PUT /{org_id}/sso/providers/{provider_id} - Update provider
 #[put("/{org_id}/sso/providers/{provider_id}")]
 pub async fn update_provider(
@@ -97,14 +112,12 @@ pub async fn update_provider(
     let (_org_id, provider_id) = path.into_inner();
     let req = body.into_inner();
 
-    // Get existing provider first
-    let existing = connector::get_connector(&provider_id).await?;
-
-    // Build update config
-    let config = req.config.map(|c| c.to_string()).unwrap_or_default();
-    let name = req.name.unwrap_or(existing.name);
+    // Merge the partial config into the stored one
+    connector::update_connector(&provider_id, req.name.as_deref(), req.config).await?;
 
-    connector::update_connector(&provider_id, &existing.provider_type, &name, &config).await?;
+    if let Some(enabled) = req.enabled {
+        connector::set_connector_enabled(&provider_id, enabled).await?;
+    }
 
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "code": 200,
@@ -112,6 +125,32 @@ pub async fn update_provider(
     })))
 }
 
+/// PUT /{org_id}/sso/providers/{provider_id}/enable - Enable provider
+#[put("/{org_id}/sso/providers/{provider_id}/enable")]
+pub async fn enable_provider(path: web::Path<(String, String)>) -> Result<HttpResponse> {
+    let (_org_id, provider_id) = path.into_inner();
+
+    connector::set_connector_enabled(&provider_id, true).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "code": 200,
+        "message": "Provider enabled successfully"
+    })))
+}
+
+/// PUT /{org_id}/sso/providers/{provider_id}/disable - Disable provider without deleting it
+#[put("/{org_id}/sso/providers/{provider_id}/disable")]
+pub async fn disable_provider(path: web::Path<(String, String)>) -> Result<HttpResponse> {
+    let (_org_id, provider_id) = path.into_inner();
+
+    connector::set_connector_enabled(&provider_id, false).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "code": 200,
+        "message": "Provider disabled successfully"
+    })))
+}
+
 /// DELETE /{org_id}/sso/providers/{provider_id} - Delete provider
 #[delete("/{org_id}/sso/providers/{provider_id}")]
 pub async fn delete_provider(path: web::Path<(String, String)>) -> Result<HttpResponse> {
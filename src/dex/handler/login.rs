@@ -9,10 +9,12 @@
 
 use actix_web::{get, post, web, HttpRequest, HttpResponse, cookie::Cookie};
 
+use crate::openfga::roles;
 use super::super::error::{Error, Result};
-use super::super::service::token;
+use super::super::service::{connector, token};
 use super::super::types::{
-    SignInUser, SignInResponse, SsoCallbackQuery, RefreshTokenRequest,
+    ClientCredentialsRequest, CurrentUserResponse, SignInUser, SignInResponse, SsoCallbackQuery,
+    RefreshTokenRequest,
 };
 
 /// POST /auth/login - Native login with username/password
@@ -31,7 +33,8 @@ pub async fn post_login(body: web::Json<SignInUser>) -> Result<HttpResponse> {
     }
 
     // Generate pre-login (this will redirect to Dex for actual token)
-    let pre_login = token::pre_login(Some("local")).await?;
+    let default_org = &crate::Visdata::global().dex_config().default_org;
+    let pre_login = token::pre_login(Some("local"), default_org).await?;
 
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "status": true,
@@ -43,19 +46,19 @@ pub async fn post_login(body: web::Json<SignInUser>) -> Result<HttpResponse> {
 /// GET /auth/login - Get auth cookie/status
 #[get("/auth/login")]
 pub async fn get_login(req: HttpRequest) -> Result<HttpResponse> {
-    // Check for existing auth cookie
-    if let Some(cookie) = req.cookie("auth_token") {
-        let token = cookie.value();
-
+    // Check for a bearer token or an existing auth cookie
+    if let Some(token) = extract_token(&req) {
         // Verify the token
-        match token::verify_token(token).await {
+        match token::verify_token(&token).await {
             Ok(validation) => {
                 return Ok(HttpResponse::Ok().json(serde_json::json!({
                     "status": true,
                     "user": {
                         "email": validation.user_email,
                         "name": validation.user_name,
-                    }
+                    },
+                    "expires_at": validation.expires_at,
+                    "issued_at": validation.issued_at
                 })));
             }
             Err(_) => {
@@ -83,6 +86,95 @@ pub async fn get_login(req: HttpRequest) -> Result<HttpResponse> {
     }))
 }
 
+/// GET /auth/userinfo - Current user's identity and roles in an org
+///
+/// The token is read with `extract_token`.
+#[get("/auth/userinfo")]
+pub async fn get_userinfo(
+    req: HttpRequest,
+    query: web::Query<UserInfoQuery>,
+) -> Result<HttpResponse> {
+    let Some(token) = extract_token(&req) else {
+        return Ok(HttpResponse::Unauthorized().json(SignInResponse {
+            status: false,
+            message: "Not authenticated".to_string(),
+        }));
+    };
+
+    let validation = match token::verify_token(&token).await {
+        Ok(validation) => validation,
+        Err(_) => {
+            return Ok(HttpResponse::Unauthorized().json(SignInResponse {
+                status: false,
+                message: "Token expired or invalid".to_string(),
+            }));
+        }
+    };
+
+    let roles = roles::get_roles_for_org_user(&query.org_id, &validation.user_email)
+        .await
+        .map_err(|e| Error::Internal(format!("Failed to get user roles: {}", e)))?;
+
+    Ok(HttpResponse::Ok().json(CurrentUserResponse {
+        email: validation.user_email,
+        name: validation.user_name,
+        roles,
+        is_internal_user: validation.is_internal_user,
+    }))
+}
+
+/// Get the auth token of a request
+///
+/// Checks the `Authorization: Bearer` header first (API clients and the
+/// mobile app), then the `auth_token` cookie. The `Bearer` scheme is matched
+/// case-insensitively; an empty header or cookie counts as absent.
+pub fn extract_token(req: &HttpRequest) -> Option<String> {
+    let bearer = req
+        .headers()
+        .get(actix_web::http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(bearer_token);
+    bearer.or_else(|| {
+        req.cookie("auth_token")
+            .map(|cookie| cookie.value().to_string())
+            .filter(|t| !t.is_empty())
+    })
+}
+
+/// Strip a case-insensitive `Bearer ` scheme from an `Authorization` header value
+fn bearer_token(header: &str) -> Option<String> {
+    authorization_param(header, "bearer").map(|t| t.to_string())
+}
+
+/// Client ID and secret from an HTTP Basic `Authorization` header
+///
+/// Both parts are form-urlencoded before base64 encoding (RFC 6749 section 2.3.1).
+fn basic_credentials(req: &HttpRequest) -> Option<(String, String)> {
+    use base64::Engine;
+
+    let header = req
+        .headers()
+        .get(actix_web::http::header::AUTHORIZATION)?
+        .to_str()
+        .ok()?;
+    let encoded = authorization_param(header, "basic")?;
+    let decoded = base64::engine::general_purpose::STANDARD.decode(encoded).ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (id, secret) = decoded.split_once(':')?;
+    let id = urlencoding::decode(id).ok()?.into_owned();
+    let secret = urlencoding::decode(secret).ok()?.into_owned();
+    Some((id, secret)).filter(|(id, _)| !id.is_empty())
+}
+
+/// The parameter of an `Authorization` header value with the given scheme (case-insensitive)
+fn authorization_param<'a>(header: &'a str, scheme: &str) -> Option<&'a str> {
+    let (found, param) = header.trim().split_once(' ')?;
+    if !found.eq_ignore_ascii_case(scheme) {
+        return None;
+    }
+    Some(param.trim()).filter(|p| !p.is_empty())
+}
+
 /// POST /auth/refresh - Refresh access token
 #[post("/auth/refresh")]
 pub async fn refresh_token_handler(
@@ -98,16 +190,52 @@ pub async fn refresh_token_handler(
     })))
 }
 
+/// POST /auth/token - Client credentials grant for service accounts
+///
+/// Credentials are taken from HTTP Basic auth, or from the form body.
+#[post("/auth/token")]
+pub async fn client_token_handler(
+    req: HttpRequest,
+    body: web::Form<ClientCredentialsRequest>,
+) -> Result<HttpResponse> {
+    let body = body.into_inner();
+
+    if let Some(grant_type) = body.grant_type.as_deref().filter(|g| *g != "client_credentials") {
+        return Err(Error::InvalidCredentials(format!(
+            "Unsupported grant type: {}",
+            grant_type
+        )));
+    }
+
+    let (client_id, client_secret) = basic_credentials(&req)
+        .or(body.client_id.zip(body.client_secret))
+        .ok_or_else(|| Error::InvalidCredentials("Missing client credentials".to_string()))?;
+    let scopes: Vec<String> = body
+        .scope
+        .as_deref()
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|s| s.to_string())
+        .collect();
+
+    let tokens = token::client_credentials(&client_id, &client_secret, &scopes).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "code": 200,
+        "data": tokens
+    })))
+}
+
 /// GET /{org_id}/sso/login - Initiate SSO login
 #[get("/{org_id}/sso/login")]
 pub async fn sso_login(
     path: web::Path<String>,
     query: web::Query<SsoLoginQuery>,
 ) -> Result<HttpResponse> {
-    let _org_id = path.into_inner();
+    let org_id = path.into_inner();
     let connector_id = query.connector_id.as_deref();
 
-    let pre_login = token::pre_login(connector_id).await?;
+    let pre_login = token::pre_login(connector_id, &org_id).await?;
 
     Ok(HttpResponse::Found()
         .insert_header(("Location", pre_login.auth_url))
@@ -120,9 +248,26 @@ pub async fn sso_callback(
     path: web::Path<String>,
     query: web::Query<SsoCallbackQuery>,
 ) -> Result<HttpResponse> {
-    let _org_id = path.into_inner();
-    let params = query.into_inner();
+    complete_sso_login(path.into_inner(), query.into_inner()).await
+}
+
+/// GET /{org_id}/saml/callback - SAML login landing endpoint
+///
+/// SAML assertions are consumed by Dex itself: the connector's `redirectURI`
+/// (ACS URL) must point at Dex's `/callback`. Dex then federates the SAML
+/// login into its own OIDC tokens and redirects here with an authorization
+/// code, so this completes exactly like `sso_callback`. Use this URL as the
+/// Dex client redirect URI for SAML-only deployments.
+#[get("/{org_id}/saml/callback")]
+pub async fn saml_callback(
+    path: web::Path<String>,
+    query: web::Query<SsoCallbackQuery>,
+) -> Result<HttpResponse> {
+    complete_sso_login(path.into_inner(), query.into_inner()).await
+}
 
+/// Exchange the authorization code from Dex and set the auth cookies
+async fn complete_sso_login(org_id: String, params: SsoCallbackQuery) -> Result<HttpResponse> {
     // Check for error
     if let Some(error) = params.error {
         let description = params.error_description.unwrap_or_default();
@@ -137,8 +282,37 @@ pub async fn sso_callback(
         Error::InvalidToken("Missing state parameter".to_string())
     })?;
 
-    // Exchange code for tokens
-    let tokens = token::exchange_code(&code, &state).await?;
+    // Exchange code for tokens; the state must have been issued for this org
+    let tokens = token::exchange_code(&code, &state, &org_id).await?;
+
+    // The connector is only known up front when the login named one, so a
+    // disabled connector is rejected here before any cookie is set
+    if let Some(connector_id) = token::id_token_connector_id(&tokens) {
+        connector::ensure_connector_enabled(&connector_id).await?;
+    }
+
+    // First-time users get the default org and role; like the group role
+    // sync below, a provisioning failure does not fail the login
+    if let Some(email) = token::id_token_email(&tokens) {
+        let name = token::id_token_name(&tokens).unwrap_or_else(|| email.clone());
+        if let Err(e) = token::provision_user_on_login(&email, &name).await {
+            tracing::warn!("[Auth] Failed to provision SSO user {}: {}", email, e);
+        }
+    }
+
+    // Group role sync must not block the login itself. Skip it when the
+    // groups could not be resolved, so a UserInfo outage doesn't revoke roles.
+    match (token::resolve_user_groups(&tokens).await, token::id_token_email(&tokens)) {
+        (Ok(groups), Some(email)) => {
+            if let Err(e) = token::apply_group_role_mappings(&org_id, &email, &groups).await {
+                tracing::warn!("[Auth] Failed to apply group role mappings for {}: {}", email, e);
+            }
+        }
+        (Ok(_), None) => {
+            tracing::warn!("[Auth] ID token has no verified email, skipping group role mappings")
+        }
+        (Err(e), _) => tracing::warn!("[Auth] Failed to resolve user groups: {}", e),
+    }
 
     // Build response with cookie
     let mut response = HttpResponse::Found()
@@ -167,16 +341,78 @@ pub async fn sso_callback(
         response.add_cookie(&refresh_cookie).ok();
     }
 
+    // Keep the ID token as the id_token_hint for single logout
+    if let Some(ref id_token) = tokens.id_token {
+        let id_token_cookie = Cookie::build("id_token", id_token)
+            .path("/auth")
+            .http_only(true)
+            .secure(true)
+            .same_site(actix_web::cookie::SameSite::Strict)
+            .max_age(actix_web::cookie::time::Duration::days(30))
+            .finish();
+        response.add_cookie(&id_token_cookie).ok();
+    }
+
     Ok(response)
 }
 
-/// POST /auth/logout - Logout and clear cookies
+/// POST /auth/logout - Revoke the refresh token and clear cookies
+///
+/// With `?sso=true` the response also redirects to the identity provider's
+/// end-session endpoint, ending the SSO session there. The ID token kept at
+/// login is sent as `id_token_hint`. If the endpoint cannot be resolved the
+/// local logout still completes.
 #[post("/auth/logout")]
-pub async fn logout() -> Result<HttpResponse> {
-    let mut response = HttpResponse::Ok().json(SignInResponse {
-        status: true,
-        message: "Logged out successfully".to_string(),
-    });
+pub async fn logout(req: HttpRequest, query: web::Query<LogoutQuery>) -> Result<HttpResponse> {
+    let refresh_token = req.cookie("refresh_token").map(|c| c.value().to_string());
+    let id_token = req.cookie("id_token").map(|c| c.value().to_string());
+    let end_session_url = if query.sso {
+        let id_token_hint = id_token.as_deref().filter(|t| !t.is_empty());
+        match token::end_session_url(id_token_hint).await {
+            Ok(url) => Some(url),
+            Err(e) => {
+                tracing::warn!("[Auth] Failed to build end-session URL on logout: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    Ok(logout_response(refresh_token, end_session_url, |token| async move {
+        token::revoke_refresh_token(&token).await
+    })
+    .await)
+}
+
+/// Revoke the refresh token (if any) and build the cookie-clearing response
+///
+/// Revocation failures are logged and do not prevent the cookies from being cleared.
+/// The response redirects to `end_session_url` when given.
+async fn logout_response<F, Fut>(
+    refresh_token: Option<String>,
+    end_session_url: Option<String>,
+    revoke: F,
+) -> HttpResponse
+where
+    F: FnOnce(String) -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    let revocation = match refresh_token.filter(|t| !t.is_empty()) {
+        Some(token) => revoke(token).await,
+        None => Ok(()),
+    };
+    if let Err(e) = revocation {
+        tracing::warn!("[Auth] Failed to revoke refresh token on logout: {}", e);
+    }
+
+    let mut response = match end_session_url {
+        Some(url) => HttpResponse::Found().insert_header(("Location", url)).finish(),
+        None => HttpResponse::Ok().json(SignInResponse {
+            status: true,
+            message: "Logged out successfully".to_string(),
+        }),
+    };
 
     // Clear auth cookie
     let auth_cookie = Cookie::build("auth_token", "")
@@ -192,7 +428,14 @@ pub async fn logout() -> Result<HttpResponse> {
         .finish();
     response.add_cookie(&refresh_cookie).ok();
 
-    Ok(response)
+    // Clear ID token cookie
+    let id_token_cookie = Cookie::build("id_token", "")
+        .path("/auth")
+        .max_age(actix_web::cookie::time::Duration::ZERO)
+        .finish();
+    response.add_cookie(&id_token_cookie).ok();
+
+    response
 }
 
 /// Query parameters for SSO login
@@ -200,3 +443,163 @@ pub async fn logout() -> Result<HttpResponse> {
 pub struct SsoLoginQuery {
     pub connector_id: Option<String>,
 }
+
+/// Query parameters for logout
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct LogoutQuery {
+    /// Also end the session at the identity provider
+    #[serde(default)]
+    pub sso: bool,
+}
+
+/// Query parameters for the current user's info
+#[derive(Debug, serde::Deserialize)]
+pub struct UserInfoQuery {
+    pub org_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn cleared_cookies(resp: &HttpResponse) -> Vec<String> {
+        resp.cookies()
+            .filter(|c| c.value().is_empty())
+            .map(|c| c.name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_token_prefers_bearer_header_then_cookie() {
+        use actix_web::test::TestRequest;
+
+        let req = TestRequest::default()
+            .cookie(Cookie::new("auth_token", "cookie-token"))
+            .insert_header(("Authorization", "Bearer header-token"))
+            .to_http_request();
+        assert_eq!(extract_token(&req).as_deref(), Some("header-token"));
+
+        // An empty or non-bearer header falls back to the cookie
+        for header in ["", "Bearer ", "Basic dXNlcjpwYXNz"] {
+            let req = TestRequest::default()
+                .cookie(Cookie::new("auth_token", "cookie-token"))
+                .insert_header(("Authorization", header))
+                .to_http_request();
+            assert_eq!(extract_token(&req).as_deref(), Some("cookie-token"), "header {:?}", header);
+        }
+
+        let req = TestRequest::default()
+            .insert_header(("Authorization", "Basic dXNlcjpwYXNz"))
+            .to_http_request();
+        assert_eq!(extract_token(&req), None);
+        assert_eq!(extract_token(&TestRequest::default().to_http_request()), None);
+    }
+
+    #[test]
+    fn test_bearer_token_scheme_is_case_insensitive() {
+        assert_eq!(bearer_token("Bearer abc").as_deref(), Some("abc"));
+        assert_eq!(bearer_token("bearer abc").as_deref(), Some("abc"));
+        assert_eq!(bearer_token("BEARER  abc ").as_deref(), Some("abc"));
+        assert_eq!(bearer_token("Bearer"), None);
+        assert_eq!(bearer_token("Bearer   "), None);
+        assert_eq!(bearer_token("Token abc"), None);
+    }
+
+    #[test]
+    fn test_basic_credentials() {
+        use actix_web::test::TestRequest;
+        use base64::Engine;
+
+        let encode = |s: &str| base64::engine::general_purpose::STANDARD.encode(s);
+        let req = TestRequest::default()
+            .insert_header(("Authorization", format!("Basic {}", encode("svc-reports:s%3Acret"))))
+            .to_http_request();
+        assert_eq!(
+            basic_credentials(&req),
+            Some(("svc-reports".to_string(), "s:cret".to_string()))
+        );
+
+        let invalid = [
+            "Bearer abc".to_string(),
+            format!("Basic {}", encode("no-colon")),
+            "Basic !!".to_string(),
+        ];
+        for header in invalid {
+            let req = TestRequest::default()
+                .insert_header(("Authorization", header.clone()))
+                .to_http_request();
+            assert_eq!(basic_credentials(&req), None, "header {:?}", header);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sso_login_rejects_idp_error_and_missing_params() {
+        let params = SsoCallbackQuery {
+            code: None,
+            state: None,
+            error: Some("access_denied".to_string()),
+            error_description: Some("user cancelled".to_string()),
+        };
+        let err = complete_sso_login("default".to_string(), params).await.unwrap_err();
+        assert_eq!(err.to_string(), "Invalid token: access_denied: user cancelled");
+
+        let params = SsoCallbackQuery {
+            code: Some("code-123".to_string()),
+            state: None,
+            error: None,
+            error_description: None,
+        };
+        let err = complete_sso_login("default".to_string(), params).await.unwrap_err();
+        assert!(err.to_string().contains("Missing state parameter"));
+    }
+
+    #[tokio::test]
+    async fn test_logout_revokes_refresh_token() {
+        let revoked = Arc::new(Mutex::new(None));
+        let recorder = revoked.clone();
+
+        let resp = logout_response(Some("refresh-abc".to_string()), None, |token| async move {
+            *recorder.lock().unwrap() = Some(token);
+            Ok(())
+        })
+        .await;
+
+        assert_eq!(revoked.lock().unwrap().as_deref(), Some("refresh-abc"));
+        let cleared = cleared_cookies(&resp);
+        assert!(cleared.contains(&"auth_token".to_string()));
+        assert!(cleared.contains(&"refresh_token".to_string()));
+        assert!(cleared.contains(&"id_token".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_logout_clears_cookies_when_revocation_fails() {
+        let resp = logout_response(Some("refresh-abc".to_string()), None, |_| async {
+            Err(Error::HttpError("unreachable".to_string()))
+        })
+        .await;
+
+        assert!(resp.status().is_success());
+        assert_eq!(cleared_cookies(&resp).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_logout_without_refresh_token_skips_revocation() {
+        let resp = logout_response(None, None, |_| async {
+            panic!("revocation should not be attempted without a refresh token")
+        })
+        .await;
+
+        assert!(resp.status().is_success());
+    }
+
+    #[tokio::test]
+    async fn test_sso_logout_redirects_to_end_session() {
+        let url = "https://idp.example.com/logout".to_string();
+        let resp = logout_response(None, Some(url.clone()), |_| async { Ok(()) }).await;
+
+        assert_eq!(resp.status(), actix_web::http::StatusCode::FOUND);
+        assert_eq!(resp.headers().get("Location").unwrap().to_str().unwrap(), url);
+        assert_eq!(cleared_cookies(&resp).len(), 3);
+    }
+}
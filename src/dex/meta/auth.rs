@@ -15,102 +15,13 @@ use std::collections::HashSet;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use crate::openfga::types::ResourceObject;
+
 /// Permission enum (compatible with o2_dex::meta::auth::Permission)
 ///
-/// Represents the different permission levels that can be granted
-/// on resources. These correspond to the ALLOW_* relations in OpenFGA.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
-#[serde(rename_all = "PascalCase")]
-pub enum Permission {
-    /// Full access to the resource
-    AllowAll,
-    /// Permission to list resources
-    AllowList,
-    /// Permission to read/get resources
-    AllowGet,
-    /// Permission to create resources
-    AllowPost,
-    /// Permission to update resources
-    AllowPut,
-    /// Permission to delete resources
-    AllowDelete,
-}
-
-impl Permission {
-    /// Convert to OpenFGA relation name
-    pub fn to_relation(&self) -> &'static str {
-        match self {
-            Permission::AllowAll => "ALLOW_ALL",
-            Permission::AllowList => "ALLOW_LIST",
-            Permission::AllowGet => "ALLOW_GET",
-            Permission::AllowPost => "ALLOW_POST",
-            Permission::AllowPut => "ALLOW_PUT",
-            Permission::AllowDelete => "ALLOW_DELETE",
-        }
-    }
-
-    /// Convert to internal relation name used in OpenFGA tuples
-    pub fn to_internal_relation(&self) -> &'static str {
-        match self {
-            Permission::AllowAll => "admin",
-            Permission::AllowList => "can_list",
-            Permission::AllowGet => "can_read",
-            Permission::AllowPost => "can_create",
-            Permission::AllowPut => "can_update",
-            Permission::AllowDelete => "can_delete",
-        }
-    }
-
-    /// Create from HTTP method
-    pub fn from_method(method: &str, is_list: bool) -> Self {
-        match method.to_uppercase().as_str() {
-            "GET" if is_list => Permission::AllowList,
-            "GET" => Permission::AllowGet,
-            "POST" => Permission::AllowPost,
-            "PUT" | "PATCH" => Permission::AllowPut,
-            "DELETE" => Permission::AllowDelete,
-            _ => Permission::AllowGet,
-        }
-    }
-
-    /// Parse from string
-    pub fn from_str(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
-            "allowall" | "allow_all" | "admin" => Some(Permission::AllowAll),
-            "allowlist" | "allow_list" | "can_list" => Some(Permission::AllowList),
-            "allowget" | "allow_get" | "can_read" => Some(Permission::AllowGet),
-            "allowpost" | "allow_post" | "can_create" => Some(Permission::AllowPost),
-            "allowput" | "allow_put" | "can_update" => Some(Permission::AllowPut),
-            "allowdelete" | "allow_delete" | "can_delete" => Some(Permission::AllowDelete),
-            _ => None,
-        }
-    }
-
-    /// Get all permissions
-    pub fn all() -> Vec<Permission> {
-        vec![
-            Permission::AllowAll,
-            Permission::AllowList,
-            Permission::AllowGet,
-            Permission::AllowPost,
-            Permission::AllowPut,
-            Permission::AllowDelete,
-        ]
-    }
-}
-
-impl std::fmt::Display for Permission {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Permission::AllowAll => write!(f, "AllowAll"),
-            Permission::AllowList => write!(f, "AllowList"),
-            Permission::AllowGet => write!(f, "AllowGet"),
-            Permission::AllowPost => write!(f, "AllowPost"),
-            Permission::AllowPut => write!(f, "AllowPut"),
-            Permission::AllowDelete => write!(f, "AllowDelete"),
-        }
-    }
-}
+/// Shared with the OpenFGA layer; `to_allow_relation` gives the `ALLOW_*`
+/// relation stored on role tuples.
+pub use crate::openfga::types::Permission;
 
 /// Entity authorization (compatible with o2_dex::meta::auth::O2EntityAuthorization)
 ///
@@ -119,28 +30,26 @@ impl std::fmt::Display for Permission {
 pub struct O2EntityAuthorization {
     /// Object identifier in format "resource_type:entity_id"
     /// e.g., "logs:my_stream" or "dashboard:_all_default"
-    pub object: String,
+    #[schema(value_type = String)]
+    pub object: ResourceObject,
     /// Permission level
     pub permission: Permission,
 }
 
 impl O2EntityAuthorization {
     /// Create a new entity authorization
-    pub fn new(object: impl Into<String>, permission: Permission) -> Self {
-        Self {
-            object: object.into(),
-            permission,
-        }
+    pub fn new(object: ResourceObject, permission: Permission) -> Self {
+        Self { object, permission }
     }
 
     /// Parse the resource type from the object
     pub fn resource_type(&self) -> Option<&str> {
-        self.object.split(':').next()
+        Some(self.object.resource_type())
     }
 
     /// Parse the entity ID from the object
     pub fn entity_id(&self) -> Option<&str> {
-        self.object.split(':').nth(1)
+        Some(self.object.entity_id())
     }
 }
 
@@ -239,13 +148,13 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_permission_to_relation() {
-        assert_eq!(Permission::AllowAll.to_relation(), "ALLOW_ALL");
-        assert_eq!(Permission::AllowGet.to_relation(), "ALLOW_GET");
-        assert_eq!(Permission::AllowList.to_relation(), "ALLOW_LIST");
-        assert_eq!(Permission::AllowPost.to_relation(), "ALLOW_POST");
-        assert_eq!(Permission::AllowPut.to_relation(), "ALLOW_PUT");
-        assert_eq!(Permission::AllowDelete.to_relation(), "ALLOW_DELETE");
+    fn test_permission_to_allow_relation() {
+        assert_eq!(Permission::AllowAll.to_allow_relation(), "ALLOW_ALL");
+        assert_eq!(Permission::AllowGet.to_allow_relation(), "ALLOW_GET");
+        assert_eq!(Permission::AllowList.to_allow_relation(), "ALLOW_LIST");
+        assert_eq!(Permission::AllowPost.to_allow_relation(), "ALLOW_POST");
+        assert_eq!(Permission::AllowPut.to_allow_relation(), "ALLOW_PUT");
+        assert_eq!(Permission::AllowDelete.to_allow_relation(), "ALLOW_DELETE");
     }
 
     #[test]
@@ -259,23 +168,42 @@ mod tests {
 
     #[test]
     fn test_permission_from_str() {
-        assert_eq!(Permission::from_str("AllowAll"), Some(Permission::AllowAll));
-        assert_eq!(Permission::from_str("allow_get"), Some(Permission::AllowGet));
-        assert_eq!(Permission::from_str("can_read"), Some(Permission::AllowGet));
-        assert_eq!(Permission::from_str("invalid"), None);
+        assert_eq!("AllowAll".parse::<Permission>().ok(), Some(Permission::AllowAll));
+        assert_eq!("allow_get".parse::<Permission>().ok(), Some(Permission::AllowGet));
+        assert_eq!("can_read".parse::<Permission>().ok(), Some(Permission::AllowGet));
+        assert_eq!("invalid".parse::<Permission>().ok(), None);
+    }
+
+    #[test]
+    fn test_permission_ordering() {
+        for permission in Permission::all() {
+            assert_eq!(permission.to_string().parse::<Permission>().ok(), Some(permission));
+        }
+
+        let mut sorted = Permission::all();
+        sorted.sort();
+        assert_eq!(sorted.last(), Some(&Permission::AllowAll));
+        assert_eq!(Permission::AllowAll.expand().len(), 5);
+        assert!("invalid".parse::<Permission>().is_err());
     }
 
     #[test]
     fn test_entity_authorization() {
-        let auth = O2EntityAuthorization::new("logs:my_stream", Permission::AllowGet);
+        let auth = O2EntityAuthorization::new("logs:my_stream".parse().unwrap(), Permission::AllowGet);
         assert_eq!(auth.resource_type(), Some("logs"));
         assert_eq!(auth.entity_id(), Some("my_stream"));
     }
 
+    #[test]
+    fn test_entity_authorization_rejects_malformed_object() {
+        let json = r#"{"object":"logs-my_stream","permission":"AllowGet"}"#;
+        assert!(serde_json::from_str::<O2EntityAuthorization>(json).is_err());
+    }
+
     #[test]
     fn test_role_request() {
         let request = RoleRequest::new()
-            .with_add(O2EntityAuthorization::new("logs:test", Permission::AllowGet));
+            .with_add(O2EntityAuthorization::new("logs:test".parse().unwrap(), Permission::AllowGet));
 
         assert!(!request.is_empty());
         assert_eq!(request.add.len(), 1);
@@ -16,6 +16,7 @@
 //! - `service` - Token validation, connector management
 //! - `config` - Dex configuration
 //! - `client` - Dex HTTP/gRPC client
+//! - `proto` - Dex gRPC API messages
 //! - `types` - Request/Response types
 
 pub mod client;
@@ -23,6 +24,7 @@ pub mod config;
 pub mod error;
 pub mod handler;
 pub mod meta;
+pub mod proto;
 pub mod service;
 pub mod types;
 
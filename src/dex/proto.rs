@@ -0,0 +1,190 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Dex gRPC API messages (subset of dex/api/v2/api.proto)
+//!
+//! Hand-written prost messages for the RPCs this crate calls, so no protoc
+//! build step is needed. Field tags must match the upstream proto.
+
+use tonic::codegen::http::uri::PathAndQuery;
+
+/// `api.Dex/ListConnectors` method path
+pub const LIST_CONNECTORS_PATH: &str = "/api.Dex/ListConnectors";
+/// `api.Dex/CreateConnector` method path
+pub const CREATE_CONNECTOR_PATH: &str = "/api.Dex/CreateConnector";
+/// `api.Dex/UpdateConnector` method path
+pub const UPDATE_CONNECTOR_PATH: &str = "/api.Dex/UpdateConnector";
+/// `api.Dex/DeleteConnector` method path
+pub const DELETE_CONNECTOR_PATH: &str = "/api.Dex/DeleteConnector";
+/// `api.Dex/CreatePassword` method path
+pub const CREATE_PASSWORD_PATH: &str = "/api.Dex/CreatePassword";
+/// `api.Dex/UpdatePassword` method path
+pub const UPDATE_PASSWORD_PATH: &str = "/api.Dex/UpdatePassword";
+/// `api.Dex/DeletePassword` method path
+pub const DELETE_PASSWORD_PATH: &str = "/api.Dex/DeletePassword";
+/// `api.Dex/ListPasswords` method path
+pub const LIST_PASSWORDS_PATH: &str = "/api.Dex/ListPasswords";
+
+/// Connector as returned by the Dex API
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Connector {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub r#type: String,
+    #[prost(string, tag = "3")]
+    pub name: String,
+    /// Connector configuration as JSON bytes
+    #[prost(bytes = "vec", tag = "4")]
+    pub config: Vec<u8>,
+}
+
+/// Request for `ListConnectors`
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct ListConnectorReq {}
+
+/// Response for `ListConnectors`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListConnectorResp {
+    #[prost(message, repeated, tag = "1")]
+    pub connectors: Vec<Connector>,
+}
+
+/// Request for `CreateConnector`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CreateConnectorReq {
+    #[prost(message, optional, tag = "1")]
+    pub connector: Option<Connector>,
+}
+
+/// Response for `CreateConnector`
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct CreateConnectorResp {
+    #[prost(bool, tag = "1")]
+    pub already_exists: bool,
+}
+
+/// Request for `UpdateConnector`; empty fields are left unchanged
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct UpdateConnectorReq {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub new_type: String,
+    #[prost(string, tag = "3")]
+    pub new_name: String,
+    #[prost(bytes = "vec", tag = "4")]
+    pub new_config: Vec<u8>,
+}
+
+/// Response for `UpdateConnector`
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct UpdateConnectorResp {
+    #[prost(bool, tag = "1")]
+    pub not_found: bool,
+}
+
+/// Request for `DeleteConnector`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DeleteConnectorReq {
+    #[prost(string, tag = "1")]
+    pub id: String,
+}
+
+/// Response for `DeleteConnector`
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct DeleteConnectorResp {
+    #[prost(bool, tag = "1")]
+    pub not_found: bool,
+}
+
+/// Local password entry; `hash` is a bcrypt hash
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Password {
+    #[prost(string, tag = "1")]
+    pub email: String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub hash: Vec<u8>,
+    #[prost(string, tag = "3")]
+    pub username: String,
+    #[prost(string, tag = "4")]
+    pub user_id: String,
+}
+
+/// Request for `CreatePassword`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CreatePasswordReq {
+    #[prost(message, optional, tag = "1")]
+    pub password: Option<Password>,
+}
+
+/// Response for `CreatePassword`
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct CreatePasswordResp {
+    #[prost(bool, tag = "1")]
+    pub already_exists: bool,
+}
+
+/// Request for `UpdatePassword`; empty fields are left unchanged
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct UpdatePasswordReq {
+    #[prost(string, tag = "1")]
+    pub email: String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub new_hash: Vec<u8>,
+    #[prost(string, tag = "3")]
+    pub new_username: String,
+}
+
+/// Response for `UpdatePassword`
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct UpdatePasswordResp {
+    #[prost(bool, tag = "1")]
+    pub not_found: bool,
+}
+
+/// Request for `DeletePassword`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct DeletePasswordReq {
+    #[prost(string, tag = "1")]
+    pub email: String,
+}
+
+/// Response for `DeletePassword`
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct DeletePasswordResp {
+    #[prost(bool, tag = "1")]
+    pub not_found: bool,
+}
+
+/// Request for `ListPasswords`
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct ListPasswordReq {}
+
+/// Response for `ListPasswords`
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ListPasswordResp {
+    #[prost(message, repeated, tag = "1")]
+    pub passwords: Vec<Password>,
+}
+
+/// Subject of a Dex-issued ID token (dex/server/internal/types.proto)
+///
+/// Dex sets the `sub` claim to this message, protobuf-encoded and then
+/// base64url-encoded without padding.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct IdTokenSubject {
+    #[prost(string, tag = "1")]
+    pub user_id: String,
+    #[prost(string, tag = "2")]
+    pub conn_id: String,
+}
+
+/// Build a method path for a unary call
+pub fn method_path(path: &'static str) -> PathAndQuery {
+    PathAndQuery::from_static(path)
+}
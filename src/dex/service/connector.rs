@@ -7,15 +7,31 @@
 
 //! Connector management service
 
+use serde_json::Value;
+
 use crate::Visdata;
 use super::super::error::{Error, Result};
 use super::super::types::{
-    CreateOidcConnectorRequest, CreateLdapConnectorRequest, CreateSamlConnectorRequest,
-    SsoProvider,
+    ConnectorResponse, CreateOidcConnectorRequest, CreateLdapConnectorRequest,
+    CreateSamlConnectorRequest, SsoProvider,
 };
 
+/// Connector config keys holding secrets, redacted in `get_connector_details`
+const SECRET_CONFIG_KEYS: &[&str] = &["bindPW", "clientSecret"];
+
+/// Placeholder for a redacted secret
+const REDACTED: &str = "********";
+
+/// Connector config key set to `true` on connectors disabled by an admin
+///
+/// Dex has no enabled flag on connectors but ignores unknown config keys, so
+/// the flag is stored in the connector config and shared by every instance.
+const DISABLED_CONFIG_KEY: &str = "visdataDisabled";
+
 /// Create an OIDC connector
 pub async fn create_oidc_connector(req: CreateOidcConnectorRequest) -> Result<()> {
+    validate_oidc(&req)?;
+
     let visdata = Visdata::global();
     let mut dex = visdata.dex().write().await;
 
@@ -35,14 +51,18 @@ pub async fn create_oidc_connector(req: CreateOidcConnectorRequest) -> Result<()
         }
     });
 
-    dex.create_connector(&req.id, "oidc", &req.name, &config.to_string())
-        .await?;
+    let config = config.to_string();
+    if !dex.create_connector(&req.id, "oidc", &req.name, &config).await? {
+        return Err(Error::ConnectorExists(req.id));
+    }
 
     Ok(())
 }
 
 /// Create an LDAP connector
 pub async fn create_ldap_connector(req: CreateLdapConnectorRequest) -> Result<()> {
+    validate_ldap(&req)?;
+
     let visdata = Visdata::global();
     let mut dex = visdata.dex().write().await;
 
@@ -74,36 +94,99 @@ pub async fn create_ldap_connector(req: CreateLdapConnectorRequest) -> Result<()
         })
     });
 
-    dex.create_connector(&req.id, "ldap", &req.name, &config.to_string())
-        .await?;
+    let config = config.to_string();
+    if !dex.create_connector(&req.id, "ldap", &req.name, &config).await? {
+        return Err(Error::ConnectorExists(req.id));
+    }
 
     Ok(())
 }
 
 /// Create a SAML connector
 pub async fn create_saml_connector(req: CreateSamlConnectorRequest) -> Result<()> {
+    validate_saml(&req)?;
     let visdata = Visdata::global();
     let mut dex = visdata.dex().write().await;
 
+    // Assertions must be posted to Dex, which federates them into OIDC tokens
+    let redirect_uri = req
+        .redirect_uri
+        .unwrap_or_else(|| format!("{}/callback", visdata.dex_config().issuer_url));
+
     // Build SAML connector config
     let config = serde_json::json!({
         "ssoURL": req.sso_url,
         "entityIssuer": req.entity_issuer,
         "ssoIssuer": req.sso_issuer,
         "ca": req.ca,
-        "redirectURI": req.redirect_uri,
+        "redirectURI": redirect_uri,
         "nameIDPolicyFormat": "emailAddress",
         "usernameAttr": req.name_attr.unwrap_or_else(|| "name".to_string()),
         "emailAttr": req.email_attr.unwrap_or_else(|| "email".to_string()),
         "groupsAttr": req.groups_attr.unwrap_or_else(|| "groups".to_string()),
     });
 
-    dex.create_connector(&req.id, "saml", &req.name, &config.to_string())
-        .await?;
+    let config = config.to_string();
+    if !dex.create_connector(&req.id, "saml", &req.name, &config).await? {
+        return Err(Error::ConnectorExists(req.id));
+    }
 
     Ok(())
 }
 
+/// Reject a blank required field
+fn require(field: &str, value: &str) -> Result<()> {
+    if value.trim().is_empty() {
+        return Err(Error::InvalidConnector(format!("{} is required", field)));
+    }
+    Ok(())
+}
+
+/// Reject a field that is not an absolute http(s) URL
+fn require_url(field: &str, value: &str) -> Result<()> {
+    require(field, value)?;
+    match reqwest::Url::parse(value) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => Ok(()),
+        _ => Err(Error::InvalidConnector(format!(
+            "{} must be an http(s) URL: {}",
+            field, value
+        ))),
+    }
+}
+
+/// Check the fields Dex needs for an OIDC connector
+fn validate_oidc(req: &CreateOidcConnectorRequest) -> Result<()> {
+    require("id", &req.id)?;
+    require_url("issuer", &req.issuer)?;
+    require("client_id", &req.client_id)?;
+    require("client_secret", &req.client_secret)
+}
+
+/// Check the fields Dex needs for an LDAP connector
+///
+/// `host` is a bare host name or address; the port is given separately.
+fn validate_ldap(req: &CreateLdapConnectorRequest) -> Result<()> {
+    require("id", &req.id)?;
+    require("host", &req.host)?;
+    if req.host.contains("://") || req.host.contains(char::is_whitespace) {
+        return Err(Error::InvalidConnector(format!(
+            "host must be a host name without scheme: {}",
+            req.host
+        )));
+    }
+    if req.port == 0 {
+        return Err(Error::InvalidConnector("port is required".to_string()));
+    }
+    require("bind_dn", &req.bind_dn)?;
+    require("user_search_base_dn", &req.user_search_base_dn)
+}
+
+/// Check the fields Dex needs for a SAML connector
+fn validate_saml(req: &CreateSamlConnectorRequest) -> Result<()> {
+    require("id", &req.id)?;
+    require_url("sso_url", &req.sso_url)
+}
+
 /// List all connectors
 pub async fn list_connectors() -> Result<Vec<SsoProvider>> {
     let visdata = Visdata::global();
@@ -114,10 +197,10 @@ pub async fn list_connectors() -> Result<Vec<SsoProvider>> {
     let providers: Vec<SsoProvider> = connectors
         .into_iter()
         .map(|c| SsoProvider {
+            enabled: is_config_enabled(&c.config),
             id: c.id,
             provider_type: c.connector_type,
             name: c.name,
-            enabled: true, // Dex doesn't have enabled flag, assume all are enabled
         })
         .collect();
 
@@ -134,30 +217,180 @@ pub async fn get_connector(id: &str) -> Result<SsoProvider> {
         .ok_or_else(|| Error::ConnectorNotFound(id.to_string()))
 }
 
+/// Get a connector with its full config, for prefilling the edit form
+///
+/// Secret fields (`SECRET_CONFIG_KEYS`) are replaced with a placeholder.
+pub async fn get_connector_details(id: &str) -> Result<ConnectorResponse> {
+    let visdata = Visdata::global();
+    let mut dex = visdata.dex().write().await;
+
+    let connector = dex
+        .list_connectors()
+        .await?
+        .into_iter()
+        .find(|c| c.id == id)
+        .ok_or_else(|| Error::ConnectorNotFound(id.to_string()))?;
+
+    let mut config = connector.config;
+    redact_secrets(&mut config);
+
+    Ok(ConnectorResponse {
+        id: connector.id,
+        connector_type: connector.connector_type,
+        name: connector.name,
+        config,
+    })
+}
+
+/// Replace non-empty secret values anywhere in a connector config
+fn redact_secrets(config: &mut serde_json::Value) {
+    match config {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let is_set = !value.is_null() && value.as_str() != Some("");
+                if SECRET_CONFIG_KEYS.contains(&key.as_str()) && is_set {
+                    *value = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
 /// Delete a connector
 pub async fn delete_connector(id: &str) -> Result<()> {
     let visdata = Visdata::global();
     let mut dex = visdata.dex().write().await;
 
-    dex.delete_connector(id).await
+    dex.delete_connector(id).await?;
+
+    Ok(())
 }
 
-/// Update a connector
+/// Update a connector, merging `patch` into its current config
+///
+/// Only the fields present in `patch` change (see `merge_config`); the name
+/// is kept unless given.
 pub async fn update_connector(
     id: &str,
-    connector_type: &str,
-    name: &str,
-    config_json: &str,
+    name: Option<&str>,
+    patch: Option<Value>,
 ) -> Result<()> {
+    let patch = patch.unwrap_or(Value::Null);
+
     let visdata = Visdata::global();
     let mut dex = visdata.dex().write().await;
 
-    dex.update_connector(id, connector_type, name, config_json)
+    let current = dex
+        .list_connectors()
+        .await?
+        .into_iter()
+        .find(|c| c.id == id)
+        .ok_or_else(|| Error::ConnectorNotFound(id.to_string()))?;
+
+    let name = name.unwrap_or(&current.name);
+    let config = merge_config(current.config, patch).to_string();
+
+    dex.update_connector(id, &current.connector_type, name, &config)
         .await
 }
 
+/// Whether a secret value in an update should leave the stored secret as is
+///
+/// Clients omit secrets, send them empty, or send back the redacted
+/// placeholder from `get_connector_details`.
+fn keeps_stored_secret(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.is_empty() || s == REDACTED,
+        _ => false,
+    }
+}
+
+/// Deep-merge a partial config into a connector config
+///
+/// Objects are merged key by key; other values in `patch` replace the stored
+/// ones, and `null` removes a key. Secrets the client left out keep their
+/// stored value. A `patch` that is not an object changes nothing.
+fn merge_config(base: Value, patch: Value) -> Value {
+    let (mut base, patch) = match (base, patch) {
+        (Value::Object(base), Value::Object(patch)) => (base, patch),
+        (base, _) => return base,
+    };
+
+    for (key, value) in patch {
+        if SECRET_CONFIG_KEYS.contains(&key.as_str()) && keeps_stored_secret(&value) {
+            continue;
+        }
+        match (base.remove(&key), value) {
+            (_, Value::Null) => {}
+            (Some(current @ Value::Object(_)), value @ Value::Object(_)) => {
+                base.insert(key, merge_config(current, value));
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+    Value::Object(base)
+}
+
+/// Enable or disable a connector without deleting it
+///
+/// The flag is written to the connector config in Dex (`DISABLED_CONFIG_KEY`).
+pub async fn set_connector_enabled(id: &str, enabled: bool) -> Result<()> {
+    update_connector(id, None, Some(enabled_patch(enabled))).await?;
+    tracing::info!(
+        "[Auth] Connector {} {}",
+        id,
+        if enabled { "enabled" } else { "disabled" }
+    );
+    Ok(())
+}
+
+/// Config patch setting (or, with `null`, removing) the disabled flag
+fn enabled_patch(enabled: bool) -> Value {
+    let flag = if enabled { Value::Null } else { Value::Bool(true) };
+    let mut patch = serde_json::Map::new();
+    patch.insert(DISABLED_CONFIG_KEY.to_string(), flag);
+    Value::Object(patch)
+}
+
+/// Whether a connector config is not flagged as disabled
+fn is_config_enabled(config: &Value) -> bool {
+    config[DISABLED_CONFIG_KEY] != Value::Bool(true)
+}
+
+/// Check whether a connector is enabled
+///
+/// Connectors Dex does not list (such as built-in ones) count as enabled.
+pub async fn is_connector_enabled(id: &str) -> Result<bool> {
+    let visdata = Visdata::global();
+    let mut dex = visdata.dex().write().await;
+
+    let connectors = dex.list_connectors().await?;
+    Ok(connectors
+        .into_iter()
+        .find(|c| c.id == id)
+        .is_none_or(|c| is_config_enabled(&c.config)))
+}
+
+/// Reject login through a disabled connector
+pub async fn ensure_connector_enabled(id: &str) -> Result<()> {
+    if is_connector_enabled(id).await? {
+        Ok(())
+    } else {
+        Err(Error::ConnectorDisabled(id.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_oidc_config_serialization() {
         let config = serde_json::json!({
@@ -169,4 +402,187 @@ mod tests {
         let serialized = config.to_string();
         assert!(serialized.contains("accounts.google.com"));
     }
+
+    fn oidc_request() -> CreateOidcConnectorRequest {
+        CreateOidcConnectorRequest {
+            id: "okta".to_string(),
+            name: "Okta".to_string(),
+            issuer: "https://example.okta.com".to_string(),
+            client_id: "o2".to_string(),
+            client_secret: "secret".to_string(),
+            scopes: vec![],
+            redirect_uri: None,
+            insecure_skip_verify: false,
+            groups_claim: None,
+            email_claim: None,
+        }
+    }
+
+    fn ldap_request() -> CreateLdapConnectorRequest {
+        CreateLdapConnectorRequest {
+            id: "ldap".to_string(),
+            name: "LDAP".to_string(),
+            host: "ldap.example.com".to_string(),
+            port: 636,
+            use_ssl: true,
+            start_tls: false,
+            insecure_skip_verify: false,
+            bind_dn: "cn=admin,dc=example,dc=com".to_string(),
+            bind_password: "secret".to_string(),
+            user_search_base_dn: "ou=people,dc=example,dc=com".to_string(),
+            user_search_filter: None,
+            user_search_username: None,
+            user_search_id_attr: None,
+            user_search_email_attr: None,
+            user_search_name_attr: None,
+            group_search_base_dn: None,
+            group_search_filter: None,
+        }
+    }
+
+    /// Field named in an `InvalidConnector` error message
+    fn invalid_field(result: Result<()>) -> String {
+        match result {
+            Err(Error::InvalidConnector(msg)) => msg,
+            other => panic!("expected InvalidConnector, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_oidc() {
+        assert!(validate_oidc(&oidc_request()).is_ok());
+
+        let req = CreateOidcConnectorRequest { issuer: "".to_string(), ..oidc_request() };
+        assert_eq!(invalid_field(validate_oidc(&req)), "issuer is required");
+        let req = CreateOidcConnectorRequest {
+            issuer: "example.okta.com".to_string(),
+            ..oidc_request()
+        };
+        assert!(invalid_field(validate_oidc(&req)).starts_with("issuer must be an http(s) URL"));
+        let req = CreateOidcConnectorRequest { client_id: " ".to_string(), ..oidc_request() };
+        assert_eq!(invalid_field(validate_oidc(&req)), "client_id is required");
+        let req = CreateOidcConnectorRequest { client_secret: "".to_string(), ..oidc_request() };
+        assert_eq!(invalid_field(validate_oidc(&req)), "client_secret is required");
+    }
+
+    #[test]
+    fn test_validate_ldap() {
+        assert!(validate_ldap(&ldap_request()).is_ok());
+
+        let req = CreateLdapConnectorRequest { host: "".to_string(), ..ldap_request() };
+        assert_eq!(invalid_field(validate_ldap(&req)), "host is required");
+        let req = CreateLdapConnectorRequest {
+            host: "ldaps://ldap.example.com".to_string(),
+            ..ldap_request()
+        };
+        assert!(invalid_field(validate_ldap(&req)).starts_with("host must be a host name"));
+        let req = CreateLdapConnectorRequest { bind_dn: "".to_string(), ..ldap_request() };
+        assert_eq!(invalid_field(validate_ldap(&req)), "bind_dn is required");
+        let req = CreateLdapConnectorRequest {
+            user_search_base_dn: "".to_string(),
+            ..ldap_request()
+        };
+        assert_eq!(invalid_field(validate_ldap(&req)), "user_search_base_dn is required");
+    }
+
+    #[test]
+    fn test_validate_saml() {
+        let req = CreateSamlConnectorRequest {
+            id: "saml".to_string(),
+            name: "SAML".to_string(),
+            sso_url: "https://idp.example.com/sso".to_string(),
+            entity_issuer: None,
+            sso_issuer: None,
+            ca: None,
+            redirect_uri: None,
+            name_attr: None,
+            email_attr: None,
+            groups_attr: None,
+        };
+        assert!(validate_saml(&req).is_ok());
+
+        let req = CreateSamlConnectorRequest { sso_url: "not a url".to_string(), ..req };
+        assert!(invalid_field(validate_saml(&req)).starts_with("sso_url must be an http(s) URL"));
+    }
+
+    #[test]
+    fn test_redact_secrets() {
+        let mut config = serde_json::json!({
+            "host": "ldap.example.com:636",
+            "bindDN": "cn=admin,dc=example,dc=com",
+            "bindPW": "s3cret",
+            "clientSecret": "",
+            "nested": [{"clientSecret": "other"}],
+        });
+        redact_secrets(&mut config);
+
+        assert_eq!(config["bindPW"], REDACTED);
+        assert_eq!(config["nested"][0]["clientSecret"], REDACTED);
+        assert_eq!(config["clientSecret"], "");
+        assert_eq!(config["bindDN"], "cn=admin,dc=example,dc=com");
+        assert_eq!(config["host"], "ldap.example.com:636");
+    }
+
+    fn stored_oidc_config() -> serde_json::Value {
+        serde_json::json!({
+            "issuer": "https://example.okta.com",
+            "clientID": "o2",
+            "clientSecret": "stored-secret",
+            "redirectURI": "https://o2.example.com/callback",
+            "scopes": ["openid", "email"],
+            "claimMapping": {"groups": "groups", "email": "email"},
+        })
+    }
+
+    #[test]
+    fn test_merge_config_name_only_update() {
+        let stored = stored_oidc_config();
+        assert_eq!(merge_config(stored.clone(), serde_json::Value::Null), stored);
+        assert_eq!(merge_config(stored.clone(), serde_json::json!({})), stored);
+    }
+
+    #[test]
+    fn test_merge_config_partial_update() {
+        let patch = serde_json::json!({
+            "issuer": "https://login.example.com",
+            "clientSecret": "",
+            "claimMapping": {"groups": "roles"},
+            "redirectURI": null,
+        });
+        let merged = merge_config(stored_oidc_config(), patch);
+
+        assert_eq!(merged["issuer"], "https://login.example.com");
+        assert_eq!(merged["clientID"], "o2");
+        assert_eq!(merged["clientSecret"], "stored-secret");
+        let claims = serde_json::json!({"groups": "roles", "email": "email"});
+        assert_eq!(merged["claimMapping"], claims);
+        assert!(merged.get("redirectURI").is_none());
+        assert_eq!(merged["scopes"], serde_json::json!(["openid", "email"]));
+    }
+
+    #[test]
+    fn test_merge_config_keeps_redacted_secret() {
+        let patch = serde_json::json!({"clientSecret": REDACTED});
+        let merged = merge_config(stored_oidc_config(), patch);
+        assert_eq!(merged["clientSecret"], "stored-secret");
+
+        let patch = serde_json::json!({"clientSecret": "new-secret"});
+        let merged = merge_config(stored_oidc_config(), patch);
+        assert_eq!(merged["clientSecret"], "new-secret");
+    }
+
+    #[test]
+    fn test_connector_enable_disable() {
+        let stored = stored_oidc_config();
+        assert!(is_config_enabled(&stored));
+
+        let disabled = merge_config(stored.clone(), enabled_patch(false));
+        assert!(!is_config_enabled(&disabled));
+        assert_eq!(disabled["clientSecret"], "stored-secret");
+
+        // Enabling removes the flag again
+        let enabled = merge_config(disabled, enabled_patch(true));
+        assert!(is_config_enabled(&enabled));
+        assert_eq!(enabled, stored);
+    }
 }
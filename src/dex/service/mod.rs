@@ -9,9 +9,16 @@
 
 pub mod token;
 pub mod connector;
+pub mod password;
 
-pub use token::{verify_token, exchange_code, refresh_token, pre_login, verify_native_login};
+pub use token::{
+    verify_token, verify_token_with_scopes, exchange_code, refresh_token, pre_login,
+    verify_native_login, fetch_userinfo,
+    resolve_user_groups, apply_group_role_mappings, provision_user_on_login, client_credentials,
+    introspect,
+};
 pub use connector::{
     create_oidc_connector, create_ldap_connector, create_saml_connector,
-    list_connectors, get_connector, update_connector, delete_connector,
+    list_connectors, get_connector, get_connector_details, update_connector, delete_connector,
 };
+pub use password::{create_native_user, update_native_user_password, delete_native_user};
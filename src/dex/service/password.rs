@@ -0,0 +1,87 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Native (password) user management service
+
+use crate::Visdata;
+use super::super::error::{Error, Result};
+
+/// Create a native login user in Dex
+///
+/// Hashes `password` with bcrypt and stores it through the Dex password API.
+/// Returns the generated Dex user ID.
+pub async fn create_native_user(email: &str, password: &str, username: &str) -> Result<String> {
+    if email.trim().is_empty() {
+        return Err(Error::InvalidCredentials("Email is required".to_string()));
+    }
+    if password.is_empty() {
+        return Err(Error::InvalidCredentials("Password is required".to_string()));
+    }
+
+    let hash = hash_password(password).await?;
+    let user_id = uuid::Uuid::new_v4().to_string();
+
+    let visdata = Visdata::global();
+    let mut dex = visdata.dex().write().await;
+
+    if !dex.create_password(email, &hash, username, &user_id).await? {
+        return Err(Error::UserExists(email.to_string()));
+    }
+
+    tracing::info!("[Auth] Created native user: {}", email);
+    Ok(user_id)
+}
+
+/// Change a native user's password
+pub async fn update_native_user_password(email: &str, password: &str) -> Result<()> {
+    if password.is_empty() {
+        return Err(Error::InvalidCredentials("Password is required".to_string()));
+    }
+
+    let hash = hash_password(password).await?;
+
+    let visdata = Visdata::global();
+    let mut dex = visdata.dex().write().await;
+    dex.update_password(email, &hash, None).await
+}
+
+/// Delete a native user
+pub async fn delete_native_user(email: &str) -> Result<()> {
+    let visdata = Visdata::global();
+    let mut dex = visdata.dex().write().await;
+    dex.delete_password(email).await
+}
+
+/// Hash a password with bcrypt off the async runtime
+async fn hash_password(password: &str) -> Result<Vec<u8>> {
+    let password = password.to_string();
+    tokio::task::spawn_blocking(move || hash_password_with_cost(&password, bcrypt::DEFAULT_COST))
+        .await
+        .map_err(|e| Error::Internal(format!("Password hashing task failed: {}", e)))?
+}
+
+/// Hash a password with bcrypt at the given cost
+fn hash_password_with_cost(password: &str, cost: u32) -> Result<Vec<u8>> {
+    bcrypt::hash(password, cost)
+        .map(String::into_bytes)
+        .map_err(|e| Error::Internal(format!("Password hashing failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_password_with_cost() {
+        let hash = hash_password_with_cost("s3cret", 4).unwrap();
+        let hash = String::from_utf8(hash).unwrap();
+
+        assert!(hash.starts_with("$2b$04$"));
+        assert!(bcrypt::verify("s3cret", &hash).unwrap());
+        assert!(!bcrypt::verify("wrong", &hash).unwrap());
+    }
+}
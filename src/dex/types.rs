@@ -27,6 +27,15 @@ pub struct SignInResponse {
     pub message: String,
 }
 
+/// Current user's identity and roles in an org
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrentUserResponse {
+    pub email: String,
+    pub name: String,
+    pub roles: Vec<String>,
+    pub is_internal_user: bool,
+}
+
 /// Pre-login response (for OIDC flow)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PreLoginData {
@@ -54,6 +63,28 @@ pub struct TokenValidationResponse {
     pub given_name: String,
     pub is_internal_user: bool,
     pub user_role: Option<String>,
+    /// Token expiry (`exp` claim, Unix seconds)
+    #[serde(default)]
+    pub expires_at: i64,
+    /// Token issue time (`iat` claim, Unix seconds)
+    #[serde(default)]
+    pub issued_at: i64,
+}
+
+/// Token introspection response (RFC 7662)
+///
+/// Only `active` is always present; the other fields are set for active tokens.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntrospectionResponse {
+    #[serde(default)]
+    pub active: bool,
+    pub sub: Option<String>,
+    /// Token expiry (Unix seconds)
+    pub exp: Option<i64>,
+    /// Space-separated scopes
+    pub scope: Option<String>,
+    pub client_id: Option<String>,
+    pub username: Option<String>,
 }
 
 /// Refresh token request
@@ -62,6 +93,18 @@ pub struct RefreshTokenRequest {
     pub refresh_token: String,
 }
 
+/// Client credentials token request (form encoded, RFC 6749 section 4.4)
+///
+/// `client_id`/`client_secret` may instead be sent with HTTP Basic auth.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ClientCredentialsRequest {
+    pub grant_type: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    /// Space-separated scopes
+    pub scope: Option<String>,
+}
+
 // ============================================================================
 // Connector Types
 // ============================================================================
@@ -143,7 +186,7 @@ pub struct CreateSamlConnectorRequest {
     pub sso_issuer: Option<String>,
     /// CA certificate (PEM format)
     pub ca: Option<String>,
-    /// Redirect URI
+    /// Assertion consumer service URL; defaults to Dex's `{issuer}/callback`
     pub redirect_uri: Option<String>,
     /// Attribute mapping for name
     pub name_attr: Option<String>,
@@ -210,6 +253,40 @@ pub struct JwtClaims {
     pub family_name: Option<String>,
     #[serde(default)]
     pub groups: Option<Vec<String>>,
+    /// Space-separated OAuth scopes
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// OAuth scopes as issued by some providers instead of `scope`
+    #[serde(default)]
+    pub scp: Option<StringOrVec>,
+    /// Upstream connector and user ID, present with the `federated:id` scope
+    #[serde(default)]
+    pub federated_claims: Option<FederatedClaims>,
+    /// Authentication methods used (e.g. "pwd")
+    #[serde(default)]
+    pub amr: Option<Vec<String>>,
+}
+
+/// Dex `federated_claims` claim
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FederatedClaims {
+    pub connector_id: String,
+    #[serde(default)]
+    pub user_id: String,
+}
+
+impl JwtClaims {
+    /// Scopes granted to the token, from `scope` and `scp`
+    pub fn scopes(&self) -> Vec<&str> {
+        let mut scopes: Vec<&str> =
+            self.scope.as_deref().unwrap_or_default().split_whitespace().collect();
+        match &self.scp {
+            Some(StringOrVec::Single(s)) => scopes.extend(s.split_whitespace()),
+            Some(StringOrVec::Multiple(v)) => scopes.extend(v.iter().map(String::as_str)),
+            None => {}
+        }
+        scopes
+    }
 }
 
 /// String or Vec<String> for audience claim
@@ -255,4 +332,6 @@ pub struct PkceData {
     pub code_verifier: String,
     pub code_challenge: String,
     pub state: String,
+    /// Org the login was started for; the callback must be for the same org
+    pub org_id: String,
 }
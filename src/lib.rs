@@ -28,6 +28,7 @@
 //! │   ├── config        # OpenFGAConfig
 //! │   ├── client        # OpenFGAClient (HTTP-based)
 //! │   ├── authorizer    # Permission checking API (is_allowed, roles, groups)
+//! │   ├── handler       # HTTP handlers (resource tree)
 //! │   ├── meta          # Resource mappings (OFGA_MODELS)
 //! │   ├── model         # FGA schema, resource definitions
 //! │   ├── service       # Internal service layer
@@ -55,6 +56,7 @@ pub mod common;
 pub mod config;
 pub mod dex;
 pub mod enterprise;
+pub mod log_patterns;
 pub mod openfga;
 
 // ============================================================================
@@ -70,7 +72,7 @@ pub use dex as auth;
 
 pub use config::VisdataConfig;
 
-use std::sync::{Arc, OnceLock};
+use std::sync::{Arc, OnceLock, PoisonError};
 use tokio::sync::RwLock;
 
 /// Error types for visdata
@@ -109,18 +111,43 @@ static VISDATA: OnceLock<Visdata> = OnceLock::new();
 /// Shutdown signal sender for background tasks
 static SHUTDOWN_TX: OnceLock<tokio::sync::watch::Sender<bool>> = OnceLock::new();
 
+/// Readiness of the services VisData depends on
+///
+/// Services of a disabled feature (OpenFGA and the model without RBAC, Dex
+/// without SSO) are not checked and report as ready.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct HealthStatus {
+    /// OpenFGA answered its health endpoint
+    pub openfga: bool,
+    /// Dex answered its health endpoint
+    pub dex: bool,
+    /// An authorization model ID is configured
+    pub model_loaded: bool,
+    /// OpenFGA requests holding a concurrency limiter slot, to spot saturation
+    pub openfga_in_flight_requests: usize,
+}
+
+impl HealthStatus {
+    /// Whether every dependency is ready to serve traffic
+    pub fn is_ready(&self) -> bool {
+        self.openfga && self.dex && self.model_loaded
+    }
+}
+
 /// Main VisData module instance
 pub struct Visdata {
     /// OpenFGA client for authorization
     openfga_client: Arc<openfga::OpenFGAClient>,
     /// Dex client for authentication
     dex_client: Arc<RwLock<dex::DexClient>>,
-    /// Dex configuration
-    dex_cfg: dex::DexConfig,
-    /// OpenFGA configuration
-    openfga_cfg: openfga::OpenFGAConfig,
+    /// Dex configuration, replaced by `reload_config`
+    dex_cfg: std::sync::RwLock<Arc<dex::DexConfig>>,
+    /// OpenFGA configuration, replaced by `reload_config`
+    openfga_cfg: std::sync::RwLock<Arc<openfga::OpenFGAConfig>>,
     /// Main configuration
     config: Arc<RwLock<VisdataConfig>>,
+    /// Serializes `reload_config` calls
+    reload_lock: tokio::sync::Mutex<()>,
 }
 
 impl Visdata {
@@ -130,45 +157,50 @@ impl Visdata {
     /// * `config` - Configuration containing OpenFGA and Dex settings
     ///
     /// # Environment Variables
+    /// Read by `VisdataConfig::from_env` (the API token is always read from the environment):
     /// - `VISDATA_OPENFGA_URL` - OpenFGA HTTP API URL (default: http://localhost:8080)
     /// - `VISDATA_OPENFGA_STORE` - OpenFGA store name (default: openobserve)
+    /// - `VISDATA_OPENFGA_API_TOKEN` - OpenFGA bearer token (default: none)
     /// - `VISDATA_DEX_GRPC_URL` - Dex gRPC URL (default: http://localhost:5557)
     /// - `VISDATA_DEX_ISSUER_URL` - Dex OIDC issuer URL (default: http://localhost:5556)
     /// - `VISDATA_DEX_CLIENT_ID` - OAuth2 client ID (default: openobserve)
     /// - `VISDATA_DEX_CLIENT_SECRET` - OAuth2 client secret
     /// - `VISDATA_DEX_REDIRECT_URI` - OAuth2 redirect URI
     pub async fn init_enterprise(cfg: VisdataConfig) -> Result<()> {
+        cfg.validate().map_err(|errors| Error::Config(errors.join("; ")))?;
+
         // Initialize OpenFGA client
-        let openfga_cfg = openfga::OpenFGAConfig::default()
-            .with_api_url(&cfg.openfga_url)
-            .with_store_name(&cfg.openfga_store_name);
+        openfga::service::validate_role_relation_overrides(&cfg.role_relation_overrides)
+            .map_err(|e| Error::Config(e.to_string()))?;
+
+        let dex_cfg = Self::build_dex_config(&cfg);
+        let openfga_cfg = Self::build_openfga_config(&cfg, &dex_cfg);
 
         let openfga_client = openfga::OpenFGAClient::new(&openfga_cfg)
             .await
             .map_err(|e| Error::OpenFGA(format!("OpenFGA init failed: {}", e)))?;
 
         // Initialize Dex client
-        let dex_cfg = dex::DexConfig::new(&cfg.dex_grpc_url)
-            .with_issuer(&cfg.dex_issuer_url)
-            .with_client(&cfg.dex_client_id, &cfg.dex_client_secret)
-            .with_redirect_uri(&cfg.dex_redirect_uri);
-
         let dex_client = dex::DexClient::new(&dex_cfg)
             .await
             .map_err(|e| Error::Dex(format!("Dex init failed: {}", e)))?;
 
         // Set up shutdown channel
-        let (shutdown_tx, _shutdown_rx) = tokio::sync::watch::channel(false);
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
         SHUTDOWN_TX
             .set(shutdown_tx)
             .map_err(|_| Error::AlreadyInitialized)?;
 
+        // Background tasks
+        tokio::spawn(dex::service::token::run_pkce_sweeper(shutdown_rx));
+
         let instance = Visdata {
             openfga_client: Arc::new(openfga_client),
             dex_client: Arc::new(RwLock::new(dex_client)),
-            dex_cfg,
-            openfga_cfg,
+            dex_cfg: std::sync::RwLock::new(Arc::new(dex_cfg)),
+            openfga_cfg: std::sync::RwLock::new(Arc::new(openfga_cfg)),
             config: Arc::new(RwLock::new(cfg)),
+            reload_lock: tokio::sync::Mutex::new(()),
         };
 
         VISDATA
@@ -179,6 +211,85 @@ impl Visdata {
         Ok(())
     }
 
+    /// Reload the configuration without restarting the process
+    ///
+    /// Validates the new config, rebuilds the Dex client and updates the
+    /// OpenFGA client's config (re-resolving the store if its URL or name
+    /// changed). Nothing is swapped in unless every step succeeds; concurrent
+    /// reloads are serialized.
+    ///
+    /// OpenFGA HTTP settings fixed at startup (auth, timeout, TLS, concurrency
+    /// limit, retries) are not affected.
+    pub async fn reload_config(&self, new: VisdataConfig) -> Result<()> {
+        let _guard = self.reload_lock.lock().await;
+
+        new.validate().map_err(|errors| Error::Config(errors.join("; ")))?;
+        openfga::service::validate_role_relation_overrides(&new.role_relation_overrides)
+            .map_err(|e| Error::Config(e.to_string()))?;
+        let dex_cfg = Self::build_dex_config(&new);
+        dex_cfg.validate().map_err(Error::Config)?;
+
+        // Build everything that can fail before swapping anything in
+        let dex_client = dex::DexClient::new(&dex_cfg)
+            .await
+            .map_err(|e| Error::Dex(format!("Dex reload failed: {}", e)))?;
+
+        let openfga_cfg = Self::build_openfga_config(&new, &dex_cfg);
+        self.openfga_client
+            .update_config(openfga_cfg.clone())
+            .await
+            .map_err(|e| Error::OpenFGA(format!("OpenFGA reload failed: {}", e)))?;
+
+        *self.dex_client.write().await = dex_client;
+        *self.dex_cfg.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(dex_cfg);
+        *self.openfga_cfg.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(openfga_cfg);
+        *self.config.write().await = new;
+
+        tracing::info!("[VISDATA] Configuration reloaded");
+        Ok(())
+    }
+
+    /// Build the OpenFGA client config from the main configuration
+    ///
+    /// New stores are bootstrapped for the Dex default organization, and the
+    /// bootstrap root user is always a configured root user.
+    fn build_openfga_config(
+        cfg: &VisdataConfig,
+        dex_cfg: &dex::DexConfig,
+    ) -> openfga::OpenFGAConfig {
+        openfga::OpenFGAConfig::default()
+            .with_api_url(&cfg.openfga_url)
+            .with_store_name(&cfg.openfga_store_name)
+            .with_auth(cfg.openfga_auth_method, cfg.openfga_api_token.clone())
+            .with_role_relation_overrides(cfg.role_relation_overrides.clone())
+            .with_bootstrap(&cfg.root_email, &dex_cfg.default_org, cfg.openfga_include_meta_org)
+            .with_auto_upgrade_model(cfg.openfga_auto_upgrade_model)
+            .with_cloud(cfg.is_cloud)
+            .with_root_emails(
+                cfg.root_emails.iter().chain([&cfg.root_email]).cloned().collect(),
+            )
+            .with_trust_root_role_string(cfg.trust_root_role_string)
+            .with_max_concurrent_requests(cfg.openfga_max_concurrent_requests)
+            .with_init_write_concurrency(cfg.openfga_init_write_concurrency)
+            .with_retry(cfg.openfga_max_retries, cfg.openfga_retry_base_delay_ms)
+            .with_tls(cfg.openfga_tls_ca_cert_path.clone(), cfg.openfga_tls_skip_verify)
+            .with_cache(cfg.cache.clone())
+    }
+
+    /// Build the Dex client config from the main configuration
+    fn build_dex_config(cfg: &VisdataConfig) -> dex::DexConfig {
+        dex::DexConfig::new(&cfg.dex_grpc_url)
+            .with_issuer(&cfg.dex_issuer_url)
+            .with_client(&cfg.dex_client_id, &cfg.dex_client_secret)
+            .with_additional_audiences(cfg.dex_additional_audiences.clone())
+            .with_redirect_uri(&cfg.dex_redirect_uri)
+            .with_post_logout_redirect_uri(cfg.dex_post_logout_redirect_uri.clone())
+            .with_jwks_cache_ttl(cfg.dex_jwks_cache_ttl_seconds)
+            .with_group_role_mappings(cfg.dex_group_role_mappings.clone())
+            .with_internal_connector_ids(cfg.dex_internal_connector_ids.clone())
+            .with_tls(cfg.dex_tls_ca_cert_path.clone(), cfg.dex_tls_skip_verify)
+    }
+
     /// Shutdown the VisData module and stop background tasks
     pub fn shutdown() {
         if let Some(tx) = SHUTDOWN_TX.get() {
@@ -212,20 +323,46 @@ impl Visdata {
         &self.dex_client
     }
 
-    /// Get the Dex configuration
-    pub fn dex_config(&self) -> &dex::DexConfig {
-        &self.dex_cfg
+    /// Get the current Dex configuration
+    pub fn dex_config(&self) -> Arc<dex::DexConfig> {
+        self.dex_cfg.read().unwrap_or_else(PoisonError::into_inner).clone()
     }
 
-    /// Get the OpenFGA configuration
-    pub fn openfga_config(&self) -> &openfga::OpenFGAConfig {
-        &self.openfga_cfg
+    /// Get the current OpenFGA configuration
+    pub fn openfga_config(&self) -> Arc<openfga::OpenFGAConfig> {
+        self.openfga_cfg.read().unwrap_or_else(PoisonError::into_inner).clone()
     }
 
     /// Get the configuration
     pub async fn config(&self) -> VisdataConfig {
         self.config.read().await.clone()
     }
+
+    /// Check OpenFGA and Dex concurrently (for readiness probes)
+    ///
+    /// Only the services of enabled features are contacted: OpenFGA and the
+    /// model with RBAC enabled, Dex with SSO enabled. The OpenFGA in-flight
+    /// request count is reported either way and does not affect readiness.
+    pub async fn health(&self) -> HealthStatus {
+        let (rbac_enabled, sso_enabled) = {
+            let config = self.config.read().await;
+            (config.rbac_enabled, config.sso_enabled)
+        };
+
+        let openfga = async { !rbac_enabled || self.openfga_client.is_healthy().await };
+        let dex = async { !sso_enabled || self.dex_client.read().await.is_healthy().await };
+        let model_loaded = async {
+            !rbac_enabled || self.openfga_client.model_id().await.is_some()
+        };
+
+        let (openfga, dex, model_loaded) = tokio::join!(openfga, dex, model_loaded);
+        HealthStatus {
+            openfga,
+            dex,
+            model_loaded,
+            openfga_in_flight_requests: self.openfga_client.in_flight_requests(),
+        }
+    }
 }
 
 /// Check if VisData module is initialized
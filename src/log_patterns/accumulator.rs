@@ -0,0 +1,254 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Pattern accumulation over a stream of log lines
+
+use super::config::PatternExtractionConfig;
+use super::extractor::DrainTree;
+use super::extractor::LogCluster;
+use super::types::{Pattern, PatternResult, Statistics, Token};
+
+/// Feeds log lines into a Drain tree and reports the resulting patterns
+///
+/// At most `max_logs` lines are analyzed. Once `max_clusters` clusters exist,
+/// lines that match none of them are counted but not clustered. Clusters
+/// smaller than `min_cluster_size` are left out of the output.
+#[derive(Debug)]
+pub struct PatternAccumulator {
+    config: PatternExtractionConfig,
+    tree: DrainTree,
+    /// First line of each cluster, indexed by cluster ID
+    samples: Vec<String>,
+    total_logs: usize,
+}
+
+impl PatternAccumulator {
+    /// Create an empty accumulator
+    pub fn new(config: PatternExtractionConfig) -> Self {
+        Self {
+            tree: DrainTree::new(config.clone()),
+            config,
+            samples: Vec::new(),
+            total_logs: 0,
+        }
+    }
+
+    /// Add a log line; returns false, ignoring the line, once `max_logs` is reached
+    pub fn add(&mut self, line: &str) -> bool {
+        if self.is_full() {
+            return false;
+        }
+        self.total_logs += 1;
+
+        if self.tree.len() >= self.config.max_clusters {
+            self.tree.add_to_existing(line);
+            return true;
+        }
+        let id = self.tree.add_log_message(line);
+        if id.0 == self.samples.len() {
+            self.samples.push(line.to_string());
+        }
+        true
+    }
+
+    /// Add log lines until the input ends or `max_logs` is reached
+    ///
+    /// No line is pulled from `lines` once `max_logs` is reached.
+    pub fn add_all<I, S>(&mut self, lines: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut lines = lines.into_iter();
+        while !self.is_full() {
+            let Some(line) = lines.next() else {
+                break;
+            };
+            self.add(line.as_ref());
+        }
+    }
+
+    /// Whether `max_logs` lines have been analyzed
+    pub fn is_full(&self) -> bool {
+        self.total_logs >= self.config.max_logs
+    }
+
+    /// Patterns with at least `min_cluster_size` lines, most frequent first
+    pub fn patterns(&self) -> Vec<Pattern> {
+        let mut patterns: Vec<Pattern> = self
+            .reported_clusters()
+            .map(|cluster| pattern(cluster, self.samples[cluster.id.0].clone()))
+            .collect();
+        sort_patterns(&mut patterns);
+        patterns
+    }
+
+    /// Summarize the run
+    pub fn finalize(self) -> Statistics {
+        self.statistics()
+    }
+
+    /// Patterns and statistics, moving samples out instead of cloning them
+    pub fn into_result(mut self) -> PatternResult {
+        let statistics = self.statistics();
+        let mut samples = std::mem::take(&mut self.samples);
+        let mut patterns: Vec<Pattern> = self
+            .reported_clusters()
+            .map(|cluster| pattern(cluster, std::mem::take(&mut samples[cluster.id.0])))
+            .collect();
+        sort_patterns(&mut patterns);
+        PatternResult {
+            patterns,
+            statistics,
+        }
+    }
+
+    /// Clusters large enough to be reported
+    fn reported_clusters(&self) -> impl Iterator<Item = &LogCluster> {
+        self.tree
+            .clusters()
+            .iter()
+            .filter(|cluster| cluster.size >= self.config.min_cluster_size)
+    }
+
+    fn statistics(&self) -> Statistics {
+        let (total_patterns, covered) = self
+            .reported_clusters()
+            .fold((0, 0), |(patterns, logs), cluster| (patterns + 1, logs + cluster.size));
+
+        let coverage_percentage = if self.total_logs == 0 {
+            0.0
+        } else {
+            covered as f64 * 100.0 / self.total_logs as f64
+        };
+        Statistics {
+            total_logs: self.total_logs,
+            total_patterns,
+            coverage_percentage,
+        }
+    }
+}
+
+/// Build the output pattern for a cluster
+fn pattern(cluster: &LogCluster, sample: String) -> Pattern {
+    Pattern {
+        template: cluster.template(),
+        count: cluster.size,
+        sample,
+        tokens: cluster
+            .template_tokens
+            .iter()
+            .map(|t| Token::from_template_token(t))
+            .collect(),
+    }
+}
+
+/// Most frequent first; the stable sort keeps creation order among equal counts
+fn sort_patterns(patterns: &mut [Pattern]) {
+    patterns.sort_by(|a, b| b.count.cmp(&a.count));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> PatternExtractionConfig {
+        PatternExtractionConfig::default()
+    }
+
+    #[test]
+    fn test_patterns_and_statistics() {
+        let mut acc = PatternAccumulator::new(config());
+        acc.add_all([
+            "session opened for alice",
+            "disk full on node-1",
+            "session opened for bob",
+            "session opened for carol",
+            "disk full on node-2",
+            "unexpected shutdown",
+        ]);
+
+        let patterns = acc.patterns();
+        assert_eq!(patterns.len(), 2);
+        assert_eq!(patterns[0].template, "session opened for <*>");
+        assert_eq!(patterns[0].count, 3);
+        assert_eq!(patterns[0].sample, "session opened for alice");
+        assert_eq!(
+            patterns[0].tokens,
+            vec![
+                Token::Literal("session".to_string()),
+                Token::Literal("opened".to_string()),
+                Token::Literal("for".to_string()),
+                Token::Wildcard,
+            ]
+        );
+        assert_eq!(patterns[1].template, "disk full on <*>");
+        assert_eq!(patterns[1].count, 2);
+
+        // The single "unexpected shutdown" line is below min_cluster_size
+        let stats = acc.finalize();
+        assert_eq!(stats.total_logs, 6);
+        assert_eq!(stats.total_patterns, 2);
+        assert!((stats.coverage_percentage - 500.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_max_logs() {
+        let mut acc = PatternAccumulator::new(PatternExtractionConfig {
+            max_logs: 3,
+            ..config()
+        });
+        let mut lines = (0..10).map(|i| format!("request {} done", i));
+        acc.add_all(lines.by_ref());
+
+        // Lines past max_logs are left in the input
+        assert_eq!(lines.next().as_deref(), Some("request 3 done"));
+
+        assert!(acc.is_full());
+        assert!(!acc.add("request 11 done"));
+        assert_eq!(acc.patterns()[0].count, 3);
+        assert_eq!(acc.finalize().total_logs, 3);
+    }
+
+    #[test]
+    fn test_max_clusters() {
+        let mut acc = PatternAccumulator::new(PatternExtractionConfig {
+            max_clusters: 1,
+            min_cluster_size: 1,
+            ..config()
+        });
+        acc.add_all([
+            "cache lookup hit",
+            "cache lookup miss",
+            "connection reset",
+            "cache lookup hit",
+        ]);
+
+        // The unmatched line is analyzed but not clustered
+        let patterns = acc.patterns();
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].template, "cache lookup <*>");
+        assert_eq!(patterns[0].count, 3);
+        let stats = acc.finalize();
+        assert_eq!(stats.total_logs, 4);
+        assert!((stats.coverage_percentage - 75.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let acc = PatternAccumulator::new(config());
+        assert!(acc.patterns().is_empty());
+        assert_eq!(
+            acc.finalize(),
+            Statistics {
+                total_logs: 0,
+                total_patterns: 0,
+                coverage_percentage: 0.0,
+            }
+        );
+    }
+}
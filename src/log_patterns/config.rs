@@ -0,0 +1,99 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Log pattern extraction configuration
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::VisdataConfig;
+
+/// Pattern extraction configuration
+///
+/// Defaults match the `log_patterns_*` defaults of `VisdataConfig`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PatternExtractionConfig {
+    /// Maximum number of logs to analyze
+    pub max_logs: usize,
+
+    /// Minimum cluster size for a pattern to be reported
+    pub min_cluster_size: usize,
+
+    /// Similarity threshold for grouping logs (0.0-1.0)
+    pub similarity_threshold: f64,
+
+    /// Drain tree depth, counting the root and leaf levels
+    pub drain_depth: usize,
+
+    /// Maximum child nodes per tree node
+    pub drain_max_child: usize,
+
+    /// Maximum number of clusters/patterns to extract
+    pub max_clusters: usize,
+}
+
+impl Default for PatternExtractionConfig {
+    fn default() -> Self {
+        Self {
+            max_logs: 10000,
+            min_cluster_size: 2,
+            similarity_threshold: 0.6,
+            drain_depth: 4,
+            drain_max_child: 100,
+            max_clusters: 1000,
+        }
+    }
+}
+
+impl From<&VisdataConfig> for PatternExtractionConfig {
+    fn from(cfg: &VisdataConfig) -> Self {
+        Self {
+            max_logs: cfg.log_patterns_max_logs,
+            min_cluster_size: cfg.log_patterns_min_cluster_size,
+            similarity_threshold: cfg.log_patterns_similarity_threshold,
+            drain_depth: cfg.log_patterns_drain_depth,
+            drain_max_child: cfg.log_patterns_drain_max_child,
+            max_clusters: cfg.log_patterns_max_clusters,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matches_visdata_config() {
+        assert_eq!(
+            PatternExtractionConfig::from(&VisdataConfig::default()),
+            PatternExtractionConfig::default()
+        );
+    }
+
+    #[test]
+    fn test_from_visdata_config() {
+        let cfg = VisdataConfig {
+            log_patterns_max_logs: 500,
+            log_patterns_min_cluster_size: 5,
+            log_patterns_similarity_threshold: 0.8,
+            log_patterns_drain_depth: 6,
+            log_patterns_drain_max_child: 20,
+            log_patterns_max_clusters: 50,
+            ..Default::default()
+        };
+        assert_eq!(
+            PatternExtractionConfig::from(&cfg),
+            PatternExtractionConfig {
+                max_logs: 500,
+                min_cluster_size: 5,
+                similarity_threshold: 0.8,
+                drain_depth: 6,
+                drain_max_child: 20,
+                max_clusters: 50,
+            }
+        );
+    }
+}
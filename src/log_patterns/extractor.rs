@@ -0,0 +1,350 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Drain algorithm implementation
+//!
+//! Drain (He et al., ICWS 2017) clusters log lines with a fixed-depth prefix
+//! tree: the first level splits by token count, the next `drain_depth - 2`
+//! levels by leading tokens, and each leaf holds candidate clusters that are
+//! compared token by token. A line joins the most similar cluster when the
+//! similarity reaches `similarity_threshold`; positions that differ become
+//! the `<*>` wildcard in the cluster template.
+//!
+//! Lines are normalized with `sdr::normalize` first, so known variable tokens
+//! (numbers, IPs, UUIDs, ...) appear as typed placeholders like `<NUM>`.
+
+use std::collections::HashMap;
+
+use super::config::PatternExtractionConfig;
+use super::sdr;
+
+/// Wildcard token used in templates and for grouping tree nodes
+pub const WILDCARD: &str = "<*>";
+
+/// Identifier of a cluster within a `DrainTree`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ClusterId(pub usize);
+
+/// A group of log lines sharing a template
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogCluster {
+    /// Cluster ID
+    pub id: ClusterId,
+    /// Template tokens; variable positions are `<*>` or an SDR placeholder
+    pub template_tokens: Vec<String>,
+    /// Number of log lines in the cluster
+    pub size: usize,
+}
+
+impl LogCluster {
+    /// Template as a single space-separated string
+    pub fn template(&self) -> String {
+        self.template_tokens.join(" ")
+    }
+}
+
+/// Prefix tree node
+#[derive(Debug, Default)]
+struct Node {
+    children: HashMap<String, Node>,
+    /// Clusters stored at a leaf
+    cluster_ids: Vec<ClusterId>,
+}
+
+/// Drain prefix tree and its clusters
+#[derive(Debug)]
+pub struct DrainTree {
+    config: PatternExtractionConfig,
+    /// First level, keyed by token count
+    root: HashMap<usize, Node>,
+    clusters: Vec<LogCluster>,
+    /// Reused normalization buffer
+    scratch: String,
+}
+
+impl DrainTree {
+    /// Create an empty tree
+    pub fn new(config: PatternExtractionConfig) -> Self {
+        Self {
+            config,
+            root: HashMap::new(),
+            clusters: Vec::new(),
+            scratch: String::new(),
+        }
+    }
+
+    /// Add a log line, returning the cluster it was assigned to
+    pub fn add_log_message(&mut self, message: &str) -> ClusterId {
+        let normalized = self.normalize(message);
+        let tokens: Vec<&str> = normalized.split_whitespace().collect();
+        let id = match self.merge_into_match(&tokens) {
+            Some(id) => id,
+            None => {
+                let id = ClusterId(self.clusters.len());
+                self.clusters.push(LogCluster {
+                    id,
+                    template_tokens: tokens.iter().map(|t| t.to_string()).collect(),
+                    size: 1,
+                });
+                self.insert_cluster(id, &tokens);
+                id
+            }
+        };
+        self.scratch = normalized;
+        id
+    }
+
+    /// Add a log line only if it matches an existing cluster
+    ///
+    /// Returns `None`, leaving the tree unchanged, when the line would need a
+    /// new cluster.
+    pub fn add_to_existing(&mut self, message: &str) -> Option<ClusterId> {
+        let normalized = self.normalize(message);
+        let tokens: Vec<&str> = normalized.split_whitespace().collect();
+        let id = self.merge_into_match(&tokens);
+        self.scratch = normalized;
+        id
+    }
+
+    /// Get a cluster by ID
+    pub fn cluster(&self, id: ClusterId) -> Option<&LogCluster> {
+        self.clusters.get(id.0)
+    }
+
+    /// All clusters, in creation order
+    pub fn clusters(&self) -> &[LogCluster] {
+        &self.clusters
+    }
+
+    /// Number of clusters
+    pub fn len(&self) -> usize {
+        self.clusters.len()
+    }
+
+    /// Whether no log line has been added yet
+    pub fn is_empty(&self) -> bool {
+        self.clusters.is_empty()
+    }
+
+    /// Normalize a line into the scratch buffer, which the caller puts back
+    fn normalize(&mut self, message: &str) -> String {
+        let mut normalized = std::mem::take(&mut self.scratch);
+        sdr::normalize_into(message, &mut normalized);
+        normalized
+    }
+
+    /// Number of prefix token levels between the length level and the leaves
+    fn prefix_levels(&self, token_count: usize) -> usize {
+        self.config.drain_depth.saturating_sub(2).min(token_count)
+    }
+
+    /// Merge `tokens` into the most similar cluster, if one is similar enough
+    fn merge_into_match(&mut self, tokens: &[&str]) -> Option<ClusterId> {
+        let id = self.match_cluster(tokens)?;
+        let cluster = &mut self.clusters[id.0];
+        merge_template(&mut cluster.template_tokens, tokens);
+        cluster.size += 1;
+        Some(id)
+    }
+
+    /// Find the most similar cluster at the leaf `tokens` route to
+    fn match_cluster(&self, tokens: &[&str]) -> Option<ClusterId> {
+        let mut node = self.root.get(&tokens.len())?;
+        for token in &tokens[..self.prefix_levels(tokens.len())] {
+            node = node
+                .children
+                .get(*token)
+                .or_else(|| node.children.get(WILDCARD))?;
+        }
+
+        // Highest similarity wins; ties go to the more general template
+        let mut best: Option<(ClusterId, f64, usize)> = None;
+        for &id in &node.cluster_ids {
+            let (similarity, wildcards) = similarity(&self.clusters[id.0].template_tokens, tokens);
+            let better = match best {
+                None => true,
+                Some((_, best_sim, best_wildcards)) => {
+                    similarity > best_sim || (similarity == best_sim && wildcards > best_wildcards)
+                }
+            };
+            if better {
+                best = Some((id, similarity, wildcards));
+            }
+        }
+        best.filter(|(_, similarity, _)| *similarity >= self.config.similarity_threshold)
+            .map(|(id, _, _)| id)
+    }
+
+    /// Add a new cluster to the tree, creating the path to its leaf
+    fn insert_cluster(&mut self, id: ClusterId, tokens: &[&str]) {
+        let levels = self.prefix_levels(tokens.len());
+        // One slot is kept free for the wildcard child
+        let max_named = self.config.drain_max_child.saturating_sub(1);
+
+        let mut node = self.root.entry(tokens.len()).or_default();
+        for token in &tokens[..levels] {
+            let key = if node.children.contains_key(*token) {
+                *token
+            } else if has_digit(token) || node.children.len() >= max_named {
+                WILDCARD
+            } else {
+                *token
+            };
+            node = node.children.entry(key.to_string()).or_default();
+        }
+        node.cluster_ids.push(id);
+    }
+}
+
+/// Fraction of positions where the template matches (wildcards match anything),
+/// and the number of wildcards in the template
+fn similarity(template: &[String], tokens: &[&str]) -> (f64, usize) {
+    if tokens.is_empty() {
+        return (1.0, 0);
+    }
+    let mut same = 0;
+    let mut wildcards = 0;
+    for (template_token, token) in template.iter().zip(tokens) {
+        if template_token == WILDCARD {
+            wildcards += 1;
+            same += 1;
+        } else if template_token == token {
+            same += 1;
+        }
+    }
+    (same as f64 / tokens.len() as f64, wildcards)
+}
+
+/// Replace template positions that differ from `tokens` with the wildcard
+fn merge_template(template: &mut [String], tokens: &[&str]) {
+    for (template_token, token) in template.iter_mut().zip(tokens) {
+        if template_token != token {
+            *template_token = WILDCARD.to_string();
+        }
+    }
+}
+
+/// Tokens with digits are likely variables, so they share the wildcard node
+fn has_digit(token: &str) -> bool {
+    token.bytes().any(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> DrainTree {
+        DrainTree::new(PatternExtractionConfig::default())
+    }
+
+    #[test]
+    fn test_similar_lines_share_a_template() {
+        let mut tree = tree();
+        let a = tree.add_log_message("connected to db01 in 5 ms");
+        let b = tree.add_log_message("connected to db02 in 17 ms");
+        let c = tree.add_log_message("connected  to db03 in 9 ms");
+
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+        let cluster = tree.cluster(a).unwrap();
+        assert_eq!(cluster.template(), "connected to <*> in <NUM> ms");
+        assert_eq!(cluster.size, 3);
+        assert_eq!(tree.len(), 1);
+    }
+
+    #[test]
+    fn test_dissimilar_lines_get_separate_clusters() {
+        let mut tree = tree();
+        let login = tree.add_log_message("user alice logged in");
+        let logout = tree.add_log_message("user alice logged out");
+        let disk = tree.add_log_message("disk quota exceeded for volume");
+        let short = tree.add_log_message("user alice");
+
+        // 3 of 4 tokens match, above the 0.6 threshold
+        assert_eq!(login, logout);
+        assert_eq!(tree.cluster(login).unwrap().template(), "user alice logged <*>");
+        // Different leading tokens or token counts never meet
+        assert_ne!(login, disk);
+        assert_ne!(login, short);
+        assert_eq!(tree.len(), 3);
+
+        let mut strict = DrainTree::new(PatternExtractionConfig {
+            similarity_threshold: 0.9,
+            ..Default::default()
+        });
+        let a = strict.add_log_message("user alice logged in");
+        let b = strict.add_log_message("user alice logged out");
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_leading_variables_route_through_wildcard_node() {
+        let mut tree = tree();
+        let a = tree.add_log_message("worker-1 GET /index.html 200");
+        let b = tree.add_log_message("worker-2 GET /index.html 200");
+        assert_eq!(a, b);
+        assert_eq!(tree.cluster(a).unwrap().template(), "<*> GET /index.html <NUM>");
+    }
+
+    #[test]
+    fn test_variables_are_normalized_before_clustering() {
+        let mut tree = tree();
+        let a = tree.add_log_message("10.0.0.1 GET /index.html 200");
+        let b = tree.add_log_message("10.0.0.2 GET /index.html 200");
+        let c = tree.add_log_message("user 123");
+        assert_eq!(tree.add_log_message("user 456"), c);
+        assert_eq!(tree.cluster(c).unwrap().template(), "user <NUM>");
+        assert_eq!(a, b);
+        assert_eq!(tree.cluster(a).unwrap().template(), "<IP> GET /index.html <NUM>");
+    }
+
+    #[test]
+    fn test_max_child_overflows_into_wildcard() {
+        let mut tree = DrainTree::new(PatternExtractionConfig {
+            drain_max_child: 3,
+            ..Default::default()
+        });
+        for service in ["auth", "billing", "search", "ingest"] {
+            tree.add_log_message(&format!("{} started worker pool", service));
+        }
+
+        // Two named children plus the wildcard; "search" and "ingest" share it
+        let first_level = &tree.root[&4];
+        assert_eq!(first_level.children.len(), 3);
+        assert!(first_level.children.contains_key(WILDCARD));
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.clusters()[2].template(), "<*> started worker pool");
+        assert_eq!(tree.clusters()[2].size, 2);
+    }
+
+    #[test]
+    fn test_add_to_existing() {
+        let mut tree = tree();
+        assert_eq!(tree.add_to_existing("job 1 finished"), None);
+        assert!(tree.is_empty());
+
+        let id = tree.add_log_message("job 1 finished");
+        assert_eq!(tree.add_to_existing("job 2 finished"), Some(id));
+        assert_eq!(tree.add_to_existing("cache miss for key"), None);
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.cluster(id).unwrap().size, 2);
+    }
+
+    #[test]
+    fn test_empty_and_short_lines() {
+        let mut tree = tree();
+        let empty = tree.add_log_message("");
+        assert_eq!(tree.add_log_message("   "), empty);
+        assert_eq!(tree.cluster(empty).unwrap().template(), "");
+
+        // Fewer tokens than the tree depth still cluster
+        let a = tree.add_log_message("ok");
+        assert_eq!(tree.add_log_message("ok"), a);
+        assert_eq!(tree.cluster(a).unwrap().size, 2);
+        assert!(!tree.is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Log pattern extraction (compatible with o2_enterprise::log_patterns)
+//!
+//! Groups similar log lines into templates with the Drain algorithm.
+//! `extract_patterns` is the one-call entry point; the submodules expose the
+//! individual stages for incremental use.
+
+pub mod accumulator;
+pub mod config;
+pub mod extractor;
+pub mod sdr;
+pub mod types;
+
+pub use accumulator::PatternAccumulator;
+pub use config::PatternExtractionConfig;
+pub use extractor::{ClusterId, DrainTree, LogCluster};
+pub use sdr::TokenType;
+pub use types::{Pattern, PatternResult, Statistics, Token};
+
+/// Extract patterns from log lines
+///
+/// Lines are SDR-normalized and clustered with Drain; at most `max_logs` lines
+/// are consumed from `logs`. Patterns are returned most frequent first.
+pub fn extract_patterns(
+    logs: impl IntoIterator<Item = String>,
+    config: &PatternExtractionConfig,
+) -> PatternResult {
+    let mut accumulator = PatternAccumulator::new(config.clone());
+    accumulator.add_all(logs);
+    accumulator.into_result()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_patterns() {
+        let logs = (0..30)
+            .map(|i| format!("GET /api/items/{} returned 200 in {} ms", i, i * 3))
+            .chain((0..10).map(|i| format!("user{}@example.com failed login from 10.0.0.{}", i, i)))
+            .chain(std::iter::once("panic: out of memory".to_string()));
+
+        let result = extract_patterns(logs, &PatternExtractionConfig::default());
+
+        let templates: Vec<(&str, usize)> = result
+            .patterns
+            .iter()
+            .map(|p| (p.template.as_str(), p.count))
+            .collect();
+        assert_eq!(
+            templates,
+            vec![
+                ("GET <*> returned <NUM> in <NUM> ms", 30),
+                ("<EMAIL> failed login from <IP>", 10),
+            ]
+        );
+        assert_eq!(result.patterns[1].sample, "user0@example.com failed login from 10.0.0.0");
+        assert_eq!(result.statistics.total_logs, 41);
+        assert_eq!(result.statistics.total_patterns, 2);
+        assert!((result.statistics.coverage_percentage - 4000.0 / 41.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_extract_patterns_stops_at_max_logs() {
+        let config = PatternExtractionConfig {
+            max_logs: 5,
+            ..Default::default()
+        };
+        let result = extract_patterns((0..100).map(|i| format!("tick {}", i)), &config);
+        assert_eq!(result.statistics.total_logs, 5);
+        assert_eq!(result.patterns[0].count, 5);
+    }
+}
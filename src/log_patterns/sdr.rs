@@ -0,0 +1,219 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! SDR type recognition (IP, NUM, UUID, etc.)
+//!
+//! Masks variable tokens with typed placeholders before clustering, so lines
+//! that differ only in ids, addresses or numbers share one template.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static UUID_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$")
+        .unwrap()
+});
+
+static EMAIL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}$").unwrap()
+});
+
+/// ISO 8601 date, time or date-time (e.g. `2025-01-02T03:04:05.123Z`)
+static TIMESTAMP_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"^(\d{4}-\d{2}-\d{2}([T ]\d{2}:\d{2}:\d{2}(\.\d+)?)?",
+        r"|\d{2}:\d{2}:\d{2}(\.\d+)?)",
+        r"(Z|[+-]\d{2}:?\d{2})?$"
+    ))
+    .unwrap()
+});
+
+static INTEGER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[-+]?\d+$").unwrap());
+
+static FLOAT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[-+]?\d+\.\d+([eE][-+]?\d+)?$").unwrap());
+
+/// `0x`-prefixed hex, or 8+ hex digits mixing digits and letters (hashes, ids)
+static HEX_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(0[xX][0-9a-fA-F]+|[0-9a-fA-F]{8,})$").unwrap());
+
+/// Characters stripped from the start of a token before classification
+const LEADING_PUNCTUATION: &[char] = &['(', '[', '{', '"', '\''];
+
+/// Characters stripped from the end of a token; a trailing period or colon
+/// ends a sentence or label rather than the value
+const TRAILING_PUNCTUATION: &[char] = &[')', ']', '}', ',', ';', '"', '\'', '.', ':'];
+
+/// Recognized token types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenType {
+    Ipv4,
+    Ipv6,
+    Integer,
+    Float,
+    Uuid,
+    Timestamp,
+    Hex,
+    Email,
+    /// Anything else; kept as is
+    Text,
+}
+
+impl TokenType {
+    /// Placeholder that replaces tokens of this type, or `None` for text
+    pub fn placeholder(&self) -> Option<&'static str> {
+        match self {
+            TokenType::Ipv4 | TokenType::Ipv6 => Some("<IP>"),
+            TokenType::Integer | TokenType::Float => Some("<NUM>"),
+            TokenType::Uuid => Some("<UUID>"),
+            TokenType::Timestamp => Some("<TS>"),
+            TokenType::Hex => Some("<HEX>"),
+            TokenType::Email => Some("<EMAIL>"),
+            TokenType::Text => None,
+        }
+    }
+}
+
+/// All placeholders produced by `normalize`
+pub const PLACEHOLDERS: &[&str] = &["<IP>", "<NUM>", "<UUID>", "<TS>", "<HEX>", "<EMAIL>"];
+
+/// Whether a template token is one of the `normalize` placeholders
+pub fn is_placeholder(token: &str) -> bool {
+    PLACEHOLDERS.contains(&token)
+}
+
+/// Classify a single token
+pub fn classify_token(token: &str) -> TokenType {
+    if token.is_empty() {
+        TokenType::Text
+    } else if UUID_RE.is_match(token) {
+        TokenType::Uuid
+    } else if token.parse::<Ipv4Addr>().is_ok() {
+        TokenType::Ipv4
+    } else if token.contains(':') && token.parse::<Ipv6Addr>().is_ok() {
+        TokenType::Ipv6
+    } else if EMAIL_RE.is_match(token) {
+        TokenType::Email
+    } else if TIMESTAMP_RE.is_match(token) {
+        TokenType::Timestamp
+    } else if INTEGER_RE.is_match(token) {
+        TokenType::Integer
+    } else if FLOAT_RE.is_match(token) {
+        TokenType::Float
+    } else if HEX_RE.is_match(token) && is_hex_id(token) {
+        TokenType::Hex
+    } else {
+        TokenType::Text
+    }
+}
+
+/// Replace variable tokens in a log line with placeholders
+///
+/// Tokens are split on whitespace and rejoined with single spaces. Surrounding
+/// brackets, quotes and separators are kept, and in `key=value` tokens only
+/// the value is masked (`user=42,` becomes `user=<NUM>,`).
+pub fn normalize(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    normalize_into(line, &mut out);
+    out
+}
+
+/// `normalize` into a reusable buffer, which is cleared first
+pub fn normalize_into(line: &str, out: &mut String) {
+    out.clear();
+    for token in line.split_whitespace() {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        normalize_token(token, out);
+    }
+}
+
+/// Append `token` to `out`, masking its core if it is a variable
+fn normalize_token(token: &str, out: &mut String) {
+    let value_start = token.rfind('=').map_or(0, |i| i + 1);
+    let (key, value) = token.split_at(value_start);
+
+    let core = value.trim_start_matches(LEADING_PUNCTUATION);
+    let prefix_len = value.len() - core.len();
+    let core = core.trim_end_matches(TRAILING_PUNCTUATION);
+
+    match classify_token(core).placeholder() {
+        Some(placeholder) => {
+            out.push_str(key);
+            out.push_str(&value[..prefix_len]);
+            out.push_str(placeholder);
+            out.push_str(&value[prefix_len + core.len()..]);
+        }
+        None => out.push_str(token),
+    }
+}
+
+/// Unprefixed hex must mix digits and letters, so plain words are not masked
+fn is_hex_id(token: &str) -> bool {
+    token.starts_with("0x")
+        || token.starts_with("0X")
+        || (token.bytes().any(|b| b.is_ascii_digit())
+            && token.bytes().any(|b| b.is_ascii_alphabetic()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_token() {
+        let cases = [
+            ("10.0.0.1", TokenType::Ipv4),
+            ("255.255.255.255", TokenType::Ipv4),
+            ("fe80::1", TokenType::Ipv6),
+            ("2001:db8:85a3::8a2e:370:7334", TokenType::Ipv6),
+            ("42", TokenType::Integer),
+            ("-7", TokenType::Integer),
+            ("3.14", TokenType::Float),
+            ("-2.5e10", TokenType::Float),
+            ("550e8400-e29b-41d4-a716-446655440000", TokenType::Uuid),
+            ("2025-01-02", TokenType::Timestamp),
+            ("2025-01-02T03:04:05Z", TokenType::Timestamp),
+            ("2025-01-02T03:04:05.123+00:00", TokenType::Timestamp),
+            ("03:04:05", TokenType::Timestamp),
+            ("0x1f", TokenType::Hex),
+            ("deadbeef42", TokenType::Hex),
+            ("a94a8fe5ccb19ba61c4c0873d391e987982fbbd3", TokenType::Hex),
+            ("alice@example.com", TokenType::Email),
+            ("deadbeef", TokenType::Text),
+            ("1.2.3", TokenType::Text),
+            ("v1.2", TokenType::Text),
+            ("GET", TokenType::Text),
+            ("node-1", TokenType::Text),
+            ("", TokenType::Text),
+        ];
+        for (token, expected) in cases {
+            assert_eq!(classify_token(token), expected, "token {:?}", token);
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        let cases = [
+            ("user 123 logged in", "user <NUM> logged in"),
+            ("user 456 logged in", "user <NUM> logged in"),
+            ("from 10.0.0.1:8080", "from 10.0.0.1:8080"),
+            ("client=10.0.0.1, status=200", "client=<IP>, status=<NUM>"),
+            ("request (id 550e8400-e29b-41d4-a716-446655440000)", "request (id <UUID>)"),
+            ("sent to \"bob@example.org\".", "sent to \"<EMAIL>\"."),
+            ("took 1.5 ms at 2025-01-02T03:04:05Z", "took <NUM> ms at <TS>"),
+            ("  extra   spaces  ", "extra spaces"),
+            ("", ""),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(normalize(line), expected, "line {:?}", line);
+        }
+    }
+}
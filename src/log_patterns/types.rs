@@ -0,0 +1,71 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Log pattern types
+
+use serde::{Deserialize, Serialize};
+
+use super::extractor::WILDCARD;
+use super::sdr;
+
+/// A template token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum Token {
+    /// Text that is identical in every matching log line
+    Literal(String),
+    /// A position whose value varies between log lines
+    Wildcard,
+    /// A variable of a recognized type, such as `<NUM>` or `<IP>`
+    Placeholder(String),
+}
+
+impl Token {
+    /// Convert a template token string
+    pub fn from_template_token(token: &str) -> Self {
+        if token == WILDCARD {
+            Token::Wildcard
+        } else if sdr::is_placeholder(token) {
+            Token::Placeholder(token.to_string())
+        } else {
+            Token::Literal(token.to_string())
+        }
+    }
+}
+
+/// An extracted log pattern
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pattern {
+    /// Template with `<*>` or typed placeholders at variable positions
+    pub template: String,
+    /// Number of log lines matching the pattern
+    pub count: usize,
+    /// First log line that matched the pattern
+    pub sample: String,
+    /// Template tokens
+    pub tokens: Vec<Token>,
+}
+
+/// Summary of a pattern extraction run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Statistics {
+    /// Number of log lines analyzed
+    pub total_logs: usize,
+    /// Number of patterns reported
+    pub total_patterns: usize,
+    /// Percentage of analyzed log lines covered by the reported patterns
+    pub coverage_percentage: f64,
+}
+
+/// Result of a pattern extraction run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PatternResult {
+    /// Patterns, most frequent first
+    pub patterns: Vec<Pattern>,
+    /// Run summary
+    pub statistics: Statistics,
+}
@@ -10,14 +10,25 @@
 //! This module provides the core authorization checking functions that are
 //! compatible with the o2_openfga::authorizer::authz API.
 
+use std::collections::{HashMap, HashSet};
+
 use crate::Visdata;
-use crate::openfga::error::Result;
-use crate::openfga::model::schema;
+use crate::openfga::error::{Error, Result};
+use crate::openfga::model::{resources, schema};
 use crate::openfga::service::{checker, tuples};
-use crate::openfga::types::TupleKey;
+use crate::openfga::types::{Permission, TupleChange, TupleKey, UsersetEdge};
+
+/// Maximum number of userset expansions performed by `explain_permission`
+const MAX_EXPLAIN_EXPANSIONS: usize = 50;
+
+/// Maximum tuple changes per write issued by `add_users_to_org`
+const MAX_TUPLES_PER_WRITE: usize = 50;
+
+/// Pending org membership changes for one user: (email, writes, deletes)
+type UserChanges = (String, Vec<TupleKey>, Vec<TupleKey>);
 
 // Re-export core functions from service layer
-pub use checker::{is_allowed, check_permissions, list_objects_for_user};
+pub use checker::{is_allowed, is_allowed_with_consistency, is_allowed_explained, check_permissions, authorize, list_objects_for_user, list_permitted_objects, missing_permissions, check_menu_access};
 pub use tuples::{
     update_tuples,
     get_add_user_to_org_tuples,
@@ -25,9 +36,11 @@ pub use tuples::{
     get_org_creation_tuples,
     get_ownership_tuple,
     get_resource_parent_tuple,
+    get_folder_parent_tuple,
     get_org_resource_permission_tuple,
     get_group_member_tuple,
     get_group_role_tuple,
+    get_public_access_tuple,
     get_service_account_creation_tuple,
     get_new_user_creation_tuple,
     get_delete_user_from_org_tuples,
@@ -56,14 +69,145 @@ pub async fn init_open_fga() -> Result<()> {
 }
 
 /// Add a user to an organization (compatible with o2_openfga::authorizer::authz::add_user_to_org)
+///
+/// Idempotent: existing tuples are read first so that re-adding a user with the
+/// same role is a no-op, and re-adding with a different role replaces the role tuple.
 pub async fn add_user_to_org(
     org_id: &str,
     user_email: &str,
     role: &str,
 ) -> Result<()> {
-    let mut writes = Vec::new();
-    get_add_user_to_org_tuples(org_id, user_email, role, &mut writes);
-    update_tuples(writes, vec![]).await
+    let visdata = Visdata::global();
+
+    let mut desired = Vec::new();
+    get_add_user_to_org_tuples(org_id, user_email, role, &mut desired);
+
+    let filter = crate::openfga::types::TupleKeyFilter {
+        user: Some(schema::user_type(user_email)),
+        relation: None,
+        object: Some(schema::org_type(org_id)),
+    };
+    let existing: Vec<TupleKey> = visdata
+        .openfga()
+        .read(Some(filter))
+        .await?
+        .into_iter()
+        .map(|t| t.key)
+        .collect();
+
+    let role_relations =
+        tuples::system_role_relations(&visdata.openfga_config().role_relation_overrides);
+    let (writes, deletes) = tuples::diff_add_user_to_org_tuples(&existing, desired, &role_relations);
+    update_tuples(writes, deletes).await
+}
+
+/// Add many users to an organization with as few writes as possible
+///
+/// Reads the org's tuples once, diffs each `(email, role)` like
+/// `add_user_to_org` and writes the changes in chunks of up to
+/// `MAX_TUPLES_PER_WRITE` tuples, never splitting one user across chunks.
+/// If a chunk is rejected its users are retried one by one, so a bad entry
+/// only fails itself. Returns one result per input entry, in input order.
+pub async fn add_users_to_org(
+    org_id: &str,
+    users: Vec<(String, String)>,
+) -> Result<Vec<(String, Result<()>)>> {
+    let visdata = Visdata::global();
+
+    let filter = crate::openfga::types::TupleKeyFilter {
+        user: None,
+        relation: None,
+        object: Some(schema::org_type(org_id)),
+    };
+    let mut existing: HashMap<String, Vec<TupleKey>> = HashMap::new();
+    for tuple in visdata.openfga().read(Some(filter)).await? {
+        existing.entry(tuple.key.user.clone()).or_default().push(tuple.key);
+    }
+
+    let role_relations =
+        tuples::system_role_relations(&visdata.openfga_config().role_relation_overrides);
+
+    let mut results: Vec<(String, Option<Result<()>>)> = Vec::with_capacity(users.len());
+    let mut changes: Vec<UserChanges> = Vec::new();
+    let mut seen = HashSet::new();
+    for (email, role) in users {
+        if email.trim().is_empty() {
+            results.push((email, Some(Err(Error::Validation("User email is empty".to_string())))));
+            continue;
+        }
+        if !seen.insert(email.clone()) {
+            let err = Error::DuplicateEntry(format!("User {} appears more than once", email));
+            results.push((email, Some(Err(err))));
+            continue;
+        }
+
+        let mut desired = Vec::new();
+        get_add_user_to_org_tuples(org_id, &email, &role, &mut desired);
+        let current = existing
+            .get(&schema::user_type(&email))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let (writes, deletes) = tuples::diff_add_user_to_org_tuples(current, desired, &role_relations);
+        if !writes.is_empty() || !deletes.is_empty() {
+            changes.push((email.clone(), writes, deletes));
+        }
+        results.push((email, None));
+    }
+
+    let mut outcomes: HashMap<String, Result<()>> = HashMap::new();
+    for chunk in chunk_user_changes(changes, MAX_TUPLES_PER_WRITE) {
+        let (writes, deletes): (Vec<_>, Vec<_>) = chunk
+            .iter()
+            .map(|(_, writes, deletes)| (writes.clone(), deletes.clone()))
+            .unzip();
+        match update_tuples(writes.concat(), deletes.concat()).await {
+            Ok(()) => {
+                for (email, _, _) in chunk {
+                    outcomes.insert(email, Ok(()));
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "[RBAC] Batch add of {} users to org {} failed, retrying individually: {}",
+                    chunk.len(), org_id, e
+                );
+                for (email, writes, deletes) in chunk {
+                    outcomes.insert(email, update_tuples(writes, deletes).await);
+                }
+            }
+        }
+    }
+
+    Ok(results
+        .into_iter()
+        .map(|(email, result)| {
+            let result = result.or_else(|| outcomes.remove(&email)).unwrap_or(Ok(()));
+            (email, result)
+        })
+        .collect())
+}
+
+/// Group per-user changes into chunks of at most `max_tuples` tuple changes
+///
+/// A user's changes always stay in one chunk; a user needing more than
+/// `max_tuples` changes gets a chunk of their own.
+fn chunk_user_changes(changes: Vec<UserChanges>, max_tuples: usize) -> Vec<Vec<UserChanges>> {
+    let mut chunks: Vec<Vec<UserChanges>> = Vec::new();
+    let mut current: Vec<UserChanges> = Vec::new();
+    let mut current_len = 0;
+    for change in changes {
+        let len = change.1.len() + change.2.len();
+        if !current.is_empty() && current_len + len > max_tuples {
+            chunks.push(std::mem::take(&mut current));
+            current_len = 0;
+        }
+        current_len += len;
+        current.push(change);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
 }
 
 /// Delete a user from an organization (compatible with o2_openfga::authorizer::authz::delete_user_from_org)
@@ -90,6 +234,209 @@ pub async fn delete_user_from_org_with_role(
     update_tuples(vec![], deletes).await
 }
 
+/// Remove every tuple referencing a user, returning how many were deleted
+///
+/// For global deprovisioning (e.g. GDPR deletion): covers all org
+/// memberships, role and group assignments, `owner` and other direct grants
+/// on resources, and tuples on the user object itself (such as its
+/// `owningOrg`). Tuples whose user is `user:{email}` or a userset of it, or
+/// whose object is `user:{email}`, are deleted in batches of
+/// `MAX_TUPLES_PER_WRITE`. The whole store is read because OpenFGA cannot
+/// filter reads by user alone. A failed batch
+/// stops the purge, leaving earlier batches deleted; re-running it is safe.
+pub async fn purge_user(user_email: &str) -> Result<usize> {
+    let user = schema::user_type(user_email);
+    let tuples = Visdata::global().openfga().read(None).await?;
+    let deletes: Vec<TupleKey> = tuples
+        .into_iter()
+        .map(|tuple| tuple.key)
+        .filter(|key| is_user_tuple(&user, key))
+        .collect();
+
+    let mut deleted = 0;
+    for batch in deletes.chunks(MAX_TUPLES_PER_WRITE) {
+        update_tuples(vec![], batch.to_vec()).await?;
+        deleted += batch.len();
+    }
+    if deleted > 0 {
+        tracing::info!("[RBAC] Purged {} tuples for {}", deleted, user_email);
+    }
+    Ok(deleted)
+}
+
+/// Whether a tuple's user is `user` itself or one of its usersets, or its
+/// object is `user`
+fn is_user_tuple(user: &str, key: &TupleKey) -> bool {
+    key.object == user
+        || key
+            .user
+            .strip_prefix(user)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('#'))
+}
+
+/// Move ownership of a resource from one user to another
+///
+/// The old `owner` tuple is deleted and the new one written in a single
+/// write, so the resource never has both or neither owner. If `to_email`
+/// already owns it, only the old tuple is removed. Fails with `NotFound` if
+/// the resource does not belong to the org (see `transfer_all_ownership`)
+/// or `from_email` does not own it.
+pub async fn transfer_ownership(
+    org_id: &str,
+    resource_type: &str,
+    entity_id: &str,
+    from_email: &str,
+    to_email: &str,
+) -> Result<()> {
+    if from_email == to_email {
+        return Ok(());
+    }
+    let old = get_ownership_tuple(org_id, resource_type, entity_id, from_email);
+    if !resource_in_org(org_id, &old.object).await? {
+        return Err(Error::NotFound(format!("{} in org {}", old.object, org_id)));
+    }
+    if !tuples::tuple_exists(&old).await? {
+        return Err(Error::NotFound(format!(
+            "{} does not own {}",
+            from_email, old.object
+        )));
+    }
+    let new = get_ownership_tuple(org_id, resource_type, entity_id, to_email);
+    let writes = if tuples::tuple_exists(&new).await? { vec![] } else { vec![new] };
+    update_tuples(writes, vec![old]).await
+}
+
+/// Whether `object` is linked to an org through `parent` tuples, directly or
+/// via its folders
+async fn resource_in_org(org_id: &str, object: &str) -> Result<bool> {
+    let client = Visdata::global().openfga();
+    let org = schema::org_type(org_id);
+    let mut seen = HashSet::new();
+    let mut pending = vec![object.to_string()];
+    while let Some(current) = pending.pop() {
+        if !seen.insert(current.clone()) {
+            continue;
+        }
+        let filter = crate::openfga::types::TupleKeyFilter {
+            user: None,
+            relation: Some("parent".to_string()),
+            object: Some(current),
+        };
+        for tuple in client.read(Some(filter)).await? {
+            if tuple.key.user == org {
+                return Ok(true);
+            }
+            pending.push(tuple.key.user);
+        }
+    }
+    Ok(false)
+}
+
+/// Move ownership of every resource `from_email` owns in an org to `to_email`
+///
+/// A resource belongs to the org if it is linked to the org through
+/// `parent` tuples, directly or via its folders. Each resource's delete and
+/// write go in the same request; resources are batched up to
+/// `MAX_TUPLES_PER_WRITE` tuple changes per request. A failed batch stops
+/// the transfer, leaving earlier batches applied; re-running it is safe.
+/// Returns the number of resources transferred.
+pub async fn transfer_all_ownership(
+    org_id: &str,
+    from_email: &str,
+    to_email: &str,
+) -> Result<usize> {
+    if from_email == to_email {
+        return Ok(0);
+    }
+    let tuples: Vec<TupleKey> = Visdata::global()
+        .openfga()
+        .read(None)
+        .await?
+        .into_iter()
+        .map(|tuple| tuple.key)
+        .collect();
+    let changes = plan_ownership_transfer(org_id, from_email, to_email, &tuples);
+
+    let mut transferred = 0;
+    for chunk in chunk_user_changes(changes, MAX_TUPLES_PER_WRITE) {
+        let (writes, deletes): (Vec<_>, Vec<_>) = chunk
+            .iter()
+            .map(|(_, writes, deletes)| (writes.clone(), deletes.clone()))
+            .unzip();
+        update_tuples(writes.concat(), deletes.concat()).await?;
+        transferred += chunk.len();
+    }
+    tracing::info!(
+        "[RBAC] Transferred {} resources in org {} from {} to {}",
+        transferred, org_id, from_email, to_email
+    );
+    Ok(transferred)
+}
+
+/// Per-resource ownership changes for `transfer_all_ownership`
+///
+/// Returns `(object, writes, deletes)` for each resource in the org owned by
+/// `from_email`. The new owner tuple is not written again if it exists.
+fn plan_ownership_transfer(
+    org_id: &str,
+    from_email: &str,
+    to_email: &str,
+    tuples: &[TupleKey],
+) -> Vec<UserChanges> {
+    let in_org = org_resources(org_id, tuples);
+    let from = schema::user_type(from_email);
+    let to = schema::user_type(to_email);
+    tuples
+        .iter()
+        .filter(|t| t.relation == "owner" && t.user == from && in_org.contains(t.object.as_str()))
+        .map(|old| {
+            let new = TupleKey::new(&to, "owner", &old.object);
+            let writes = if tuples.contains(&new) { vec![] } else { vec![new] };
+            (old.object.clone(), writes, vec![old.clone()])
+        })
+        .collect()
+}
+
+/// Resources reachable from an org through `parent` tuples, directly or via
+/// their folders
+fn org_resources<'a>(org_id: &str, tuples: &'a [TupleKey]) -> HashSet<&'a str> {
+    let mut in_org: HashSet<&str> = HashSet::new();
+    let mut pending = vec![schema::org_type(org_id)];
+    while let Some(parent) = pending.pop() {
+        for tuple in tuples.iter().filter(|t| t.relation == "parent" && t.user == parent) {
+            if in_org.insert(&tuple.object) {
+                pending.push(tuple.object.clone());
+            }
+        }
+    }
+    in_org
+}
+
+/// List the organizations a user belongs to, sorted and deduplicated
+///
+/// Membership is read from the user's `org_context` tuples, which
+/// `get_add_user_to_org_tuples` writes for every org member.
+pub async fn get_user_orgs(user_email: &str) -> Result<Vec<String>> {
+    let filter = crate::openfga::types::TupleKeyFilter {
+        user: Some(schema::user_type(user_email)),
+        relation: Some("org_context".to_string()),
+        object: None,
+    };
+    let tuples = Visdata::global().openfga().read_by_object_type("org", filter).await?;
+    Ok(org_ids(tuples.iter().map(|t| &t.key)))
+}
+
+/// Org IDs of the `org_context` tuples among `tuples`, sorted and deduplicated
+fn org_ids<'a>(tuples: impl IntoIterator<Item = &'a TupleKey>) -> Vec<String> {
+    let ids: std::collections::BTreeSet<&str> = tuples
+        .into_iter()
+        .filter(|t| t.relation == "org_context")
+        .filter_map(|t| t.object.strip_prefix("org:"))
+        .filter(|id| !id.is_empty())
+        .collect();
+    ids.into_iter().map(String::from).collect()
+}
+
 /// Save organization tuples (compatible with o2_openfga::authorizer::authz::save_org_tuples)
 pub async fn save_org_tuples(org_id: &str) -> Result<()> {
     let mut writes = Vec::new();
@@ -97,6 +444,78 @@ pub async fn save_org_tuples(org_id: &str) -> Result<()> {
     update_tuples(writes, vec![]).await
 }
 
+/// Grant a permission on a resource to every authenticated user
+///
+/// Writes the `user:*` wildcard tuple (e.g. `user:* ALLOW_GET dashboard:d1`),
+/// which OpenFGA matches for any user in `is_allowed` and `list_objects`.
+/// Only read permissions (`AllowGet`, `AllowList`) can be made public; the
+/// model accepts the wildcard on no other relation. Granting an existing
+/// public permission is a no-op.
+pub async fn grant_public_access(
+    org_id: &str,
+    resource_type: &str,
+    entity_id: &str,
+    permission: Permission,
+) -> Result<()> {
+    check_public_permission(permission)?;
+    let tuple = public_access_tuple(org_id, resource_type, entity_id, permission)?;
+    if tuples::tuple_exists(&tuple).await? {
+        return Ok(());
+    }
+    update_tuples(vec![tuple], vec![]).await
+}
+
+/// Revoke a permission granted with `grant_public_access`
+///
+/// Revoking a permission that is not public is a no-op.
+pub async fn revoke_public_access(
+    org_id: &str,
+    resource_type: &str,
+    entity_id: &str,
+    permission: Permission,
+) -> Result<()> {
+    let tuple = public_access_tuple(org_id, resource_type, entity_id, permission)?;
+    if !tuples::tuple_exists(&tuple).await? {
+        return Ok(());
+    }
+    update_tuples(vec![], vec![tuple]).await
+}
+
+/// Reject permissions other than the read permissions `user:*` may be granted
+fn check_public_permission(permission: Permission) -> Result<()> {
+    match permission {
+        Permission::AllowGet | Permission::AllowList => Ok(()),
+        _ => Err(Error::Validation(format!(
+            "{} cannot be granted publicly, only AllowGet and AllowList",
+            permission
+        ))),
+    }
+}
+
+/// Public access tuple for a resource, rejecting unknown resource types
+///
+/// Legacy resource keys (e.g. "serviceaccounts") are resolved first.
+fn public_access_tuple(
+    org_id: &str,
+    resource_type: &str,
+    entity_id: &str,
+    permission: Permission,
+) -> Result<TupleKey> {
+    let Some(resource_type) = resources::resolve_resource_key(resource_type) else {
+        return Err(Error::InvalidResourceType(resource_type.to_string()));
+    };
+    Ok(get_public_access_tuple(org_id, resource_type, entity_id, permission))
+}
+
+/// Whether an organization was created with `save_org_tuples`
+///
+/// Looks up the self-referential `selfParent` tuple written by
+/// `get_org_creation_tuples` with a single exact read.
+pub async fn org_exists(org_id: &str) -> Result<bool> {
+    let org = schema::org_type(org_id);
+    tuples::tuple_exists(&TupleKey::new(&org, "selfParent", &org)).await
+}
+
 /// Delete organization tuples (compatible with o2_openfga::authorizer::authz::delete_org_tuples)
 pub async fn delete_org_tuples(org_id: &str) -> Result<()> {
     let visdata = Visdata::global();
@@ -134,6 +553,311 @@ pub async fn list_objects(
     list_objects_for_user(org_id, user_id, permission, object_type, role).await
 }
 
+/// List the entity IDs of all resources of a type in an organization
+///
+/// A resource is in the org if a tuple links it to the org (e.g. its
+/// `owningOrg` tuple) or to one of the org's folders of the parent type, so
+/// this finds resources that have authorization tuples. Each read is
+/// filtered by object type server-side, one per folder for foldered types.
+/// The `_all_{org}` type-level object is excluded. IDs are sorted. Legacy
+/// resource keys are resolved first.
+pub async fn list_org_resources(org_id: &str, resource_type: &str) -> Result<Vec<String>> {
+    let Some(resource_type) = resources::resolve_resource_key(resource_type) else {
+        return Err(Error::InvalidResourceType(resource_type.to_string()));
+    };
+
+    let mut parents = vec![schema::org_type(org_id)];
+    if let Some(parent_type) = resources::get_parent_type(resource_type) {
+        let folders = Box::pin(list_org_resources(org_id, parent_type)).await?;
+        parents.extend(
+            folders
+                .iter()
+                .map(|folder| schema::resource_object(org_id, parent_type, folder)),
+        );
+    }
+
+    let client = Visdata::global().openfga();
+    let mut linked = Vec::new();
+    for parent in parents {
+        let filter = crate::openfga::types::TupleKeyFilter {
+            user: Some(parent),
+            relation: None,
+            object: None,
+        };
+        linked.extend(client.read_by_object_type(resource_type, filter).await?);
+    }
+    Ok(org_entity_ids(org_id, linked.iter().map(|t| &t.key)))
+}
+
+/// Distinct entity IDs of the objects in `tuples`, without `_all` objects
+fn org_entity_ids<'a>(org_id: &str, tuples: impl IntoIterator<Item = &'a TupleKey>) -> Vec<String> {
+    let ids: std::collections::BTreeSet<&str> = tuples
+        .into_iter()
+        .filter_map(|t| resources::parse_object(&t.object))
+        .map(|(_, entity_id)| entity_id)
+        .filter(|entity_id| !resources::is_all_org_entity(entity_id, org_id))
+        .collect();
+    ids.into_iter().map(String::from).collect()
+}
+
+/// Get one page of recent tuple changes that belong to an organization
+///
+/// Pass the returned token back as `since_token` to continue from where the
+/// previous call stopped. Changes are attributed to the org through the org
+/// object, the roles and groups it owns (`owningOrg`) and "_all_{org}"
+/// objects on either side of the tuple; direct grants on individual
+/// resources (e.g. ownership) carry no org and are not included.
+pub async fn get_recent_changes(
+    org_id: &str,
+    since_token: Option<String>,
+) -> Result<(Vec<TupleChange>, Option<String>)> {
+    let client = Visdata::global().openfga();
+    let (changes, token) = client.read_changes(None, since_token).await?;
+
+    let mut owned = HashSet::new();
+    for object_type in ["role", "group"] {
+        let filter = crate::openfga::types::TupleKeyFilter {
+            user: Some(schema::org_type(org_id)),
+            relation: Some("owningOrg".to_string()),
+            object: None,
+        };
+        let tuples = client.read_by_object_type(object_type, filter).await?;
+        owned.extend(org_owned_objects(org_id, tuples.iter().map(|t| &t.key)));
+    }
+    // Roles and groups created (or deleted) within this page of changes
+    owned.extend(org_owned_objects(org_id, changes.iter().map(|c| &c.tuple_key)));
+
+    let changes = changes
+        .into_iter()
+        .filter(|change| is_org_tuple(org_id, &owned, &change.tuple_key))
+        .collect();
+    Ok((changes, token))
+}
+
+/// Export every tuple referencing an organization, for backup or migration
+///
+/// Reads the whole store and keeps tuples whose user or object is the org,
+/// one of the roles or groups it owns (per their `owningOrg` tuple), one of
+/// its `_all_{org}` resource objects, or one of its resources (see
+/// `org_resources`). The latter covers folder links, ownership and public
+/// grants. OpenFGA reads are eventually consistent, so tuples written just
+/// before the export may be missing.
+pub async fn export_org_tuples(org_id: &str) -> Result<Vec<TupleKey>> {
+    let tuples = Visdata::global().openfga().read(None).await?;
+    let keys: Vec<TupleKey> = tuples.into_iter().map(|tuple| tuple.key).collect();
+    Ok(select_org_tuples(org_id, keys))
+}
+
+/// Keep the tuples of `keys` that `export_org_tuples` attributes to an org
+fn select_org_tuples(org_id: &str, keys: Vec<TupleKey>) -> Vec<TupleKey> {
+    let mut owned = org_owned_objects(org_id, &keys);
+    owned.extend(org_resources(org_id, &keys).into_iter().map(String::from));
+    keys.into_iter()
+        .filter(|key| is_org_tuple(org_id, &owned, key))
+        .collect()
+}
+
+/// Restore tuples produced by `export_org_tuples`, returning how many were written
+///
+/// Every tuple must reference the org; roles and groups count as the org's
+/// when the store or the import itself holds their `owningOrg` tuple, and
+/// resources when `parent` tuples in either link them to the org. Claiming a
+/// role or group owned by another org, or re-parenting a resource linked to
+/// another org, is rejected. Tuples that
+/// already exist (per a fresh read, which is eventually consistent) and
+/// duplicates are skipped; the rest are written in batches of
+/// `MAX_TUPLES_PER_WRITE`. A failed batch stops the import, leaving earlier
+/// batches written; re-running it is safe.
+pub async fn import_org_tuples(org_id: &str, tuples: Vec<TupleKey>) -> Result<usize> {
+    let store: Vec<TupleKey> = Visdata::global()
+        .openfga()
+        .read(None)
+        .await?
+        .into_iter()
+        .map(|tuple| tuple.key)
+        .collect();
+    let batches = plan_import(org_id, tuples, &store, MAX_TUPLES_PER_WRITE)?;
+
+    let mut written = 0;
+    for batch in batches {
+        let len = batch.len();
+        update_tuples(batch, vec![]).await?;
+        written += len;
+    }
+    Ok(written)
+}
+
+/// Validate import tuples and split the ones missing from `store` into write batches
+fn plan_import(
+    org_id: &str,
+    tuples: Vec<TupleKey>,
+    store: &[TupleKey],
+    batch_size: usize,
+) -> Result<Vec<Vec<TupleKey>>> {
+    let org = schema::org_type(org_id);
+    let foreign_owned: HashSet<&str> = store
+        .iter()
+        .filter(|t| t.relation == "owningOrg" && t.user != org)
+        .map(|t| t.object.as_str())
+        .collect();
+    if let Some(claimed) = tuples
+        .iter()
+        .filter(|t| t.relation == "owningOrg" && t.user == org)
+        .find(|t| foreign_owned.contains(t.object.as_str()))
+    {
+        return Err(Error::Validation(format!(
+            "{} is owned by another org than {}",
+            claimed.object, org_id
+        )));
+    }
+
+    let store_resources = org_resources(org_id, store);
+    if let Some(claimed) = tuples.iter().filter(|t| t.relation == "parent").find(|t| {
+        !store_resources.contains(t.object.as_str())
+            && store.iter().any(|s| s.relation == "parent" && s.object == t.object)
+    }) {
+        return Err(Error::Validation(format!(
+            "{} is linked to another org than {}",
+            claimed.object, org_id
+        )));
+    }
+
+    let mut owned = org_owned_objects(org_id, store);
+    owned.extend(org_owned_objects(org_id, &tuples));
+    let linked: Vec<TupleKey> = store.iter().chain(&tuples).cloned().collect();
+    owned.extend(org_resources(org_id, &linked).into_iter().map(String::from));
+    if let Some(foreign) = tuples.iter().find(|t| !is_org_tuple(org_id, &owned, t)) {
+        return Err(Error::Validation(format!(
+            "Tuple {} {} {} does not belong to org {}",
+            foreign.user, foreign.relation, foreign.object, org_id
+        )));
+    }
+
+    let existing: HashSet<&TupleKey> = store.iter().collect();
+    let mut seen = HashSet::new();
+    let new: Vec<TupleKey> = tuples
+        .into_iter()
+        .filter(|t| !existing.contains(t) && seen.insert(t.clone()))
+        .collect();
+    Ok(new.chunks(batch_size.max(1)).map(<[TupleKey]>::to_vec).collect())
+}
+
+/// Role and group objects owned by an organization, per `owningOrg` tuples
+///
+/// Role and group IDs ("role:{org}_{name}") cannot be attributed by prefix:
+/// "role:acme_corp_dev" may be acme's "corp_dev" role or acme_corp's "dev".
+fn org_owned_objects<'a>(
+    org_id: &str,
+    tuples: impl IntoIterator<Item = &'a TupleKey>,
+) -> HashSet<String> {
+    let org = schema::org_type(org_id);
+    tuples
+        .into_iter()
+        .filter(|t| t.relation == "owningOrg" && t.user == org)
+        .filter(|t| t.object.starts_with("role:") || t.object.starts_with("group:"))
+        .map(|t| t.object.clone())
+        .collect()
+}
+
+/// Check whether a tuple references an organization's org, owned role or
+/// group (see `org_owned_objects`), or "_all" objects
+fn is_org_tuple(org_id: &str, owned: &HashSet<String>, tuple: &TupleKey) -> bool {
+    let org = schema::org_type(org_id);
+    let all_suffix = format!(":_all_{}", org_id);
+
+    [tuple.user.as_str(), tuple.object.as_str()].into_iter().any(|side| {
+        let side = side.split('#').next().unwrap_or(side);
+        side == org || owned.contains(side) || side.ends_with(&all_suffix)
+    })
+}
+
+/// Explain how a user gets a relation on an object
+///
+/// Expands the relation on the object (following referenced usersets such as
+/// roles, groups and parent objects) and returns the edges on paths from the
+/// user to the object, e.g. "user:alice@example.com -> group:default_devs#member".
+/// Exclusions (intersection/difference) are listed, not evaluated; use
+/// `is_allowed` for the actual decision. `object` uses the
+/// "resource_type:entity_id" format.
+pub async fn explain_permission(
+    org_id: &str,
+    user_id: &str,
+    relation: &str,
+    object: &str,
+) -> Result<Vec<String>> {
+    let fga_object = match resources::parse_object(object) {
+        Some((resource_type, entity_id)) if resources::is_all_org_entity(entity_id, org_id) => {
+            schema::resource_object_all(org_id, resource_type)
+        }
+        Some((resource_type, entity_id)) => schema::resource_object(org_id, resource_type, entity_id),
+        None => {
+            return Err(crate::openfga::error::Error::Validation(format!(
+                "Invalid object format: {}",
+                object
+            )));
+        }
+    };
+
+    let visdata = Visdata::global();
+    let mut pending = vec![format!("{}#{}", fga_object, relation)];
+    let mut expanded = std::collections::HashSet::new();
+    let mut edges = Vec::new();
+
+    while let Some(userset) = pending.pop() {
+        if expanded.len() >= MAX_EXPLAIN_EXPANSIONS {
+            tracing::warn!("[RBAC] Explain for {} truncated after {} expansions", object, MAX_EXPLAIN_EXPANSIONS);
+            break;
+        }
+        let Some((userset_object, userset_relation)) = userset.rsplit_once('#') else {
+            continue;
+        };
+        if !expanded.insert(userset.clone()) {
+            continue;
+        }
+
+        let response = visdata.openfga().expand(userset_relation, userset_object).await?;
+        let Some(root) = response.tree.root else {
+            continue;
+        };
+
+        let start = edges.len();
+        root.edges(&mut edges);
+        pending.extend(
+            edges[start..]
+                .iter()
+                .filter(|e| e.from.contains('#') && !expanded.contains(&e.from))
+                .map(|e| e.from.clone()),
+        );
+    }
+
+    Ok(contributing_edges(&edges, &schema::user_type(user_id)))
+}
+
+/// Keep the edges that lie on a path starting from `user` (or the `user:*` wildcard)
+fn contributing_edges(edges: &[UsersetEdge], user: &str) -> Vec<String> {
+    let mut reachable: std::collections::HashSet<&str> = [user, "user:*"].into_iter().collect();
+    loop {
+        let before = reachable.len();
+        for edge in edges {
+            if reachable.contains(edge.from.as_str()) {
+                reachable.insert(edge.to.as_str());
+            }
+        }
+        if reachable.len() == before {
+            break;
+        }
+    }
+
+    let mut result: Vec<String> = Vec::new();
+    for edge in edges.iter().filter(|e| reachable.contains(e.from.as_str())) {
+        let line = edge.to_string();
+        if !result.contains(&line) {
+            result.push(line);
+        }
+    }
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,11 +869,284 @@ mod tests {
         let _ = is_allowed;
         let _ = init_open_fga;
         let _ = add_user_to_org;
+        let _ = add_users_to_org;
         let _ = delete_user_from_org;
         let _ = save_org_tuples;
         let _ = delete_org_tuples;
+        let _ = org_exists;
+        let _ = grant_public_access;
+        let _ = revoke_public_access;
         let _ = update_tuples;
         let _ = list_objects;
+        let _ = explain_permission;
+        let _ = get_recent_changes;
+        let _ = get_user_orgs;
+        let _ = purge_user;
+        let _ = transfer_ownership;
+        let _ = transfer_all_ownership;
+        let _ = list_org_resources;
+    }
+
+    #[test]
+    fn test_plan_ownership_transfer() {
+        let owner = |email: &str, object: &str| {
+            TupleKey::new(schema::user_type(email), "owner", object)
+        };
+        let tuples = vec![
+            TupleKey::new("org:default", "parent", "dfolder:f1"),
+            TupleKey::new("dfolder:f1", "parent", "dashboard:d1"),
+            TupleKey::new("org:default", "parent", "alert:a1"),
+            TupleKey::new("org:other", "parent", "alert:a2"),
+            owner("old@b.com", "dashboard:d1"),
+            owner("old@b.com", "alert:a1"),
+            owner("new@b.com", "alert:a1"),
+            // Owned in another org
+            owner("old@b.com", "alert:a2"),
+            owner("someone@b.com", "dfolder:f1"),
+        ];
+
+        let changes = plan_ownership_transfer("default", "old@b.com", "new@b.com", &tuples);
+        assert_eq!(
+            changes,
+            vec![
+                (
+                    "dashboard:d1".to_string(),
+                    vec![owner("new@b.com", "dashboard:d1")],
+                    vec![owner("old@b.com", "dashboard:d1")],
+                ),
+                // Already owned by the new user: only the old tuple goes
+                ("alert:a1".to_string(), vec![], vec![owner("old@b.com", "alert:a1")]),
+            ]
+        );
+        assert!(plan_ownership_transfer("other", "new@b.com", "old@b.com", &tuples).is_empty());
+    }
+
+    #[test]
+    fn test_is_user_tuple() {
+        let user = schema::user_type("a@b.com");
+        let owned = TupleKey::new("user:a@b.com", "owner", "dashboard:d1");
+        assert!(is_user_tuple(&user, &owned));
+        let userset = TupleKey::new("user:a@b.com#member", "viewer", "folder:f1");
+        assert!(is_user_tuple(&user, &userset));
+
+        // Tuples on the user object are purged too
+        let object = TupleKey::new("org:default", "owningOrg", "user:a@b.com");
+        assert!(is_user_tuple(&user, &object));
+
+        // Other users sharing the prefix
+        let other = TupleKey::new("user:a@b.com.au", "owner", "dashboard:d1");
+        assert!(!is_user_tuple(&user, &other));
+        let other_object = TupleKey::new("org:default", "owningOrg", "user:a@b.com.au");
+        assert!(!is_user_tuple(&user, &other_object));
+    }
+
+    #[test]
+    fn test_org_entity_ids() {
+        let tuples = vec![
+            TupleKey::new("org:default", "owningOrg", "dashboard:_all_default"),
+            TupleKey::new("org:default", "owningOrg", "dashboard:d2"),
+            TupleKey::new("dfolder:f1", "parent", "dashboard:d1"),
+            TupleKey::new("org:default", "owningOrg", "dashboard:d2"),
+        ];
+        assert_eq!(org_entity_ids("default", &tuples), vec!["d1", "d2"]);
+    }
+
+    #[test]
+    fn test_org_ids() {
+        let tuples = vec![
+            TupleKey::new("user:a@b.com", "org_context", "org:prod"),
+            TupleKey::new("user:a@b.com", "admin", "org:prod"),
+            TupleKey::new("user:a@b.com", "org_context", "org:default"),
+            TupleKey::new("user:a@b.com", "org_context", "org:prod"),
+            TupleKey::new("user:a@b.com", "assigned", "role:prod_auditor"),
+        ];
+        assert_eq!(org_ids(&tuples), vec!["default", "prod"]);
+        assert!(org_ids(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_chunk_user_changes() {
+        let change = |email: &str, writes: usize, deletes: usize| -> UserChanges {
+            let tuple = TupleKey::new(format!("user:{}", email), "viewer", "org:default");
+            (email.to_string(), vec![tuple.clone(); writes], vec![tuple; deletes])
+        };
+        let changes = vec![
+            change("a", 2, 0),
+            change("b", 2, 1),
+            change("c", 2, 0),
+            change("d", 6, 0),
+            change("e", 1, 0),
+        ];
+
+        let chunks = chunk_user_changes(changes, 5);
+        let emails: Vec<Vec<&str>> = chunks
+            .iter()
+            .map(|chunk| chunk.iter().map(|(email, _, _)| email.as_str()).collect())
+            .collect();
+        assert_eq!(emails, vec![vec!["a", "b"], vec!["c"], vec!["d"], vec!["e"]]);
+        assert!(chunk_user_changes(vec![], 5).is_empty());
+    }
+
+    #[test]
+    fn test_is_org_tuple() {
+        let owned: HashSet<String> =
+            ["role:default_dev", "group:default_ops"].into_iter().map(String::from).collect();
+        let is_default = |user: &str, relation: &str, object: &str| {
+            is_org_tuple("default", &owned, &TupleKey::new(user, relation, object))
+        };
+        assert!(is_default("user:a@b.com", "admin", "org:default"));
+        assert!(is_default("user:a@b.com", "assigned", "role:default_dev"));
+        assert!(is_default("group:default_ops#member", "assigned", "role:other_x"));
+        assert!(is_default("role:default_dev#has", "ALLOW_GET", "logs:_all_default"));
+        assert!(is_default("org:default", "parent", "dashboard:d1"));
+
+        assert!(!is_default("user:a@b.com", "admin", "org:default2"));
+        assert!(!is_default("user:a@b.com", "assigned", "role:prod_dev"));
+        assert!(!is_default("user:a@b.com", "assigned", "role:default_unowned"));
+        assert!(!is_default("user:a@b.com", "owner", "dashboard:d1"));
+    }
+
+    #[test]
+    fn test_org_tuples_with_prefix_sharing_orgs() {
+        let store = vec![
+            TupleKey::new("org:acme", "owningOrg", "role:acme_dev"),
+            TupleKey::new("org:acme_corp", "owningOrg", "role:acme_corp_dev"),
+            TupleKey::new("org:acme_corp", "owningOrg", "group:acme_corp_ops"),
+            TupleKey::new("user:a@b.com", "assigned", "role:acme_dev"),
+            TupleKey::new("user:b@b.com", "assigned", "role:acme_corp_dev"),
+            TupleKey::new("user:b@b.com", "member", "group:acme_corp_ops"),
+            TupleKey::new("role:acme_corp_dev#has", "ALLOW_GET", "logs:_all_acme_corp"),
+        ];
+
+        // acme's export holds none of acme_corp's tuples
+        let owned = org_owned_objects("acme", &store);
+        let exported: Vec<&TupleKey> =
+            store.iter().filter(|t| is_org_tuple("acme", &owned, t)).collect();
+        assert_eq!(exported, vec![&store[0], &store[3]]);
+
+        // Importing acme_corp's tuples into acme is rejected
+        let err = plan_import("acme", vec![store[4].clone()], &store, 50).unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+        let claim = TupleKey::new("org:acme", "owningOrg", "role:acme_corp_dev");
+        let err = plan_import("acme", vec![claim, store[4].clone()], &store, 50).unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+    }
+
+    #[test]
+    fn test_check_public_permission() {
+        assert!(check_public_permission(Permission::AllowGet).is_ok());
+        assert!(check_public_permission(Permission::AllowList).is_ok());
+        for permission in [
+            Permission::AllowAll,
+            Permission::AllowPost,
+            Permission::AllowPut,
+            Permission::AllowDelete,
+        ] {
+            let err = check_public_permission(permission).unwrap_err();
+            assert!(matches!(err, Error::Validation(_)));
+        }
+    }
+
+    #[test]
+    fn test_export_import_round_trip_keeps_resources() {
+        let store = vec![
+            TupleKey::new("user:a@b.com", "admin", "org:default"),
+            TupleKey::new("org:default", "parent", "dfolder:f1"),
+            TupleKey::new("dfolder:f1", "parent", "dashboard:d1"),
+            TupleKey::new("user:a@b.com", "owner", "dashboard:d1"),
+            TupleKey::new("user:*", "ALLOW_GET", "dashboard:d1"),
+            TupleKey::new("org:other", "parent", "dfolder:f2"),
+            TupleKey::new("dfolder:f2", "parent", "dashboard:d2"),
+            TupleKey::new("user:b@b.com", "owner", "dashboard:d2"),
+            TupleKey::new("user:*", "ALLOW_GET", "dashboard:d2"),
+        ];
+
+        let exported = select_org_tuples("default", store.clone());
+        assert_eq!(exported, store[..5].to_vec());
+
+        // Restoring into an empty store writes everything back
+        let batches = plan_import("default", exported.clone(), &[], 50).unwrap();
+        assert_eq!(batches, vec![exported]);
+
+        // Another org's resource cannot be pulled in through a folder link
+        let claim = TupleKey::new("dfolder:f1", "parent", "dashboard:d2");
+        let err = plan_import("default", vec![claim], &store, 50).unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+        let grant = TupleKey::new("user:*", "ALLOW_GET", "dashboard:d2");
+        let err = plan_import("default", vec![grant], &store, 50).unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+    }
+
+    #[test]
+    fn test_plan_import() {
+        let admin = TupleKey::new("user:a@b.com", "admin", "org:default");
+        let context = TupleKey::new("user:a@b.com", "org_context", "org:default");
+        let owning = TupleKey::new("org:default", "owningOrg", "role:default_dev");
+        let role = TupleKey::new("user:a@b.com", "assigned", "role:default_dev");
+        let store = vec![admin.clone()];
+
+        // Existing tuples and duplicates are skipped
+        let tuples =
+            vec![admin.clone(), context.clone(), role.clone(), context.clone(), owning.clone()];
+        let batches = plan_import("default", tuples, &store, 1).unwrap();
+        assert_eq!(batches, vec![vec![context.clone()], vec![role.clone()], vec![owning.clone()]]);
+
+        let batches = plan_import("default", vec![context, role.clone(), owning], &[], 50).unwrap();
+        assert_eq!(batches.len(), 1);
+        assert!(plan_import("default", vec![admin], &store, 50).unwrap().is_empty());
+
+        // A role is only the org's with an owningOrg tuple
+        let err = plan_import("default", vec![role], &store, 50).unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+
+        let foreign = TupleKey::new("user:a@b.com", "admin", "org:other");
+        let err = plan_import("default", vec![foreign], &[], 50).unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+    }
+
+    #[test]
+    fn test_contributing_edges_follows_paths_from_user() {
+        let tree: crate::openfga::types::UsersetTree = serde_json::from_value(serde_json::json!({
+            "root": {
+                "name": "dashboard:d1#can_read",
+                "union": {"nodes": [
+                    {"name": "dashboard:d1#can_read", "leaf": {"users": {"users": ["user:bob@example.com"]}}},
+                    {"name": "dashboard:d1#can_read", "leaf": {"computed": {"userset": "dashboard:d1#editor"}}},
+                    {"name": "dashboard:d1#can_read", "leaf": {"tupleToUserset": {
+                        "tupleset": "dashboard:d1#parent",
+                        "computed": [{"userset": "folder:f1#can_read"}]
+                    }}}
+                ]}
+            }
+        }))
+        .unwrap();
+        let mut edges = Vec::new();
+        tree.root.unwrap().edges(&mut edges);
+
+        // Expansion of the parent folder grants via a group
+        edges.push(UsersetEdge {
+            from: "group:default_devs#member".to_string(),
+            to: "folder:f1#can_read".to_string(),
+            via: None,
+        });
+        edges.push(UsersetEdge {
+            from: "user:alice@example.com".to_string(),
+            to: "group:default_devs#member".to_string(),
+            via: None,
+        });
+
+        let explained = contributing_edges(&edges, "user:alice@example.com");
+        assert_eq!(
+            explained,
+            vec![
+                "folder:f1#can_read -> dashboard:d1#can_read (via dashboard:d1#parent)",
+                "group:default_devs#member -> folder:f1#can_read",
+                "user:alice@example.com -> group:default_devs#member",
+            ]
+        );
+
+        let explained = contributing_edges(&edges, "user:carol@example.com");
+        assert!(explained.is_empty());
     }
 }
 
@@ -14,7 +14,7 @@ use std::collections::HashSet;
 
 use crate::openfga::error::Result;
 use crate::openfga::service::groups as group_service;
-use crate::openfga::types::GroupResponse;
+use crate::openfga::types::{GroupListOptions, GroupResponse};
 
 /// Create a new group (compatible with o2_openfga::authorizer::groups::create_group)
 ///
@@ -49,6 +49,18 @@ pub async fn create_group_with_users(
     Ok(id)
 }
 
+/// Update a group's display name and description
+///
+/// `None` leaves a field unchanged, `Some("")` clears it.
+pub async fn update_group_metadata(
+    org_id: &str,
+    group_name: &str,
+    display_name: Option<&str>,
+    description: Option<&str>,
+) -> Result<()> {
+    group_service::update_group_metadata(org_id, group_name, display_name, description).await
+}
+
 /// Delete a group
 pub async fn delete_group(org_id: &str, group_name: &str) -> Result<()> {
     group_service::delete_group(org_id, group_name).await
@@ -58,7 +70,8 @@ pub async fn delete_group(org_id: &str, group_name: &str) -> Result<()> {
 ///
 /// If `permitted` is Some, only return groups that are in the permitted list.
 pub async fn get_all_groups(org_id: &str, permitted: Option<Vec<String>>) -> Result<Vec<String>> {
-    let all_groups = group_service::list_groups(org_id).await?;
+    let options = GroupListOptions::default();
+    let all_groups = group_service::list_groups_with_options(org_id, &options).await?;
 
     // Filter by permitted if specified
     match permitted {
@@ -125,6 +138,21 @@ pub async fn update_group(
     Ok(())
 }
 
+/// Nest a group inside another group so its members inherit the parent's roles
+pub async fn add_subgroup(org_id: &str, parent_group: &str, child_group: &str) -> Result<()> {
+    group_service::add_subgroup(org_id, parent_group, child_group).await
+}
+
+/// Remove a nested group from its parent
+pub async fn remove_subgroup(org_id: &str, parent_group: &str, child_group: &str) -> Result<()> {
+    group_service::remove_subgroup(org_id, parent_group, child_group).await
+}
+
+/// Check whether a user is a direct member of a group
+pub async fn is_group_member(org_id: &str, group_name: &str, user_email: &str) -> Result<bool> {
+    group_service::is_group_member(org_id, group_name, user_email).await
+}
+
 /// Get users in a group
 pub async fn get_group_users(org_id: &str, group_name: &str) -> Result<Vec<String>> {
     let details = get_group_details(org_id, group_name).await?;
@@ -12,10 +12,14 @@
 
 use std::collections::HashSet;
 
-use crate::openfga::error::Result;
+use crate::Visdata;
+use crate::openfga::error::{Error, Result};
+use crate::openfga::model::{resources, schema};
 use crate::openfga::service::roles as role_service;
 use crate::openfga::service::tuples;
-use crate::openfga::types::{PermissionEntry, RoleResponse, UserRoleOption};
+use crate::openfga::types::{
+    Permission, PermissionEntry, RoleFullResponse, RoleResponse, RoleSummary, UserRoleOption,
+};
 
 // Re-export from tuples for compatibility with o2_openfga::authorizer::roles
 pub use tuples::{get_role_key, get_user_crole_removal_tuples};
@@ -25,6 +29,16 @@ pub async fn create_role(org_id: &str, role_name: &str) -> Result<()> {
     role_service::create_role(org_id, role_name).await
 }
 
+/// Clone a role's permissions (and optionally its users) into a new role
+pub async fn clone_role(
+    org_id: &str,
+    source_role: &str,
+    new_role: &str,
+    copy_users: bool,
+) -> Result<()> {
+    role_service::clone_role(org_id, source_role, new_role, copy_users).await
+}
+
 /// Delete a role (compatible with o2_openfga::authorizer::roles::delete_role)
 pub async fn delete_role(org_id: &str, role_name: &str) -> Result<()> {
     role_service::delete_role(org_id, role_name).await
@@ -50,6 +64,13 @@ pub async fn get_all_roles(org_id: &str, permitted: Option<Vec<String>>) -> Resu
     }
 }
 
+/// Get all custom roles with user counts and granted resource types
+///
+/// Computed from a single bulk read instead of per-role permission queries.
+pub async fn list_roles_with_summary(org_id: &str) -> Result<Vec<RoleSummary>> {
+    role_service::list_roles_with_summary(org_id).await
+}
+
 /// Get all roles including system roles for dropdown options
 pub async fn get_all_role_options(org_id: &str) -> Result<Vec<UserRoleOption>> {
     let mut options = role_service::list_system_roles(org_id).await;
@@ -61,6 +82,8 @@ pub async fn get_all_role_options(org_id: &str) -> Result<Vec<UserRoleOption>> {
 /// Update a role (compatible with o2_openfga::authorizer::roles::update_role)
 ///
 /// This function handles adding/removing permissions and users to/from a role.
+/// All changes are validated first and written together, so a failure leaves
+/// the role unchanged.
 pub async fn update_role(
     org_id: &str,
     role_name: &str,
@@ -69,43 +92,16 @@ pub async fn update_role(
     add_users: Option<&HashSet<String>>,
     remove_users: Option<&HashSet<String>>,
 ) -> Result<()> {
-    println!(
-        "[RBAC] authorizer::roles::update_role called: org_id={}, role_name={}, add_perms={:?}, remove_perms={:?}",
-        org_id, role_name, add_permissions.map(|p| p.len()), remove_permissions.map(|p| p.len())
-    );
-
-    // Add permissions
-    if let Some(perms) = add_permissions {
-        println!("[RBAC] add_permissions: {} perms, is_empty={}", perms.len(), perms.is_empty());
-        if !perms.is_empty() {
-            println!("[RBAC] Calling add_role_permissions...");
-            role_service::add_role_permissions(org_id, role_name, perms).await?;
-            println!("[RBAC] add_role_permissions completed");
-        }
-    }
-
-    // Remove permissions
-    if let Some(perms) = remove_permissions {
-        if !perms.is_empty() {
-            role_service::remove_role_permissions(org_id, role_name, perms).await?;
-        }
-    }
-
-    // Add users
-    if let Some(users) = add_users {
-        if !users.is_empty() {
-            role_service::add_role_users(org_id, role_name, users).await?;
-        }
-    }
-
-    // Remove users
-    if let Some(users) = remove_users {
-        if !users.is_empty() {
-            role_service::remove_role_users(org_id, role_name, users).await?;
-        }
-    }
-
-    Ok(())
+    let empty = HashSet::new();
+    role_service::update_role(
+        org_id,
+        role_name,
+        add_permissions.unwrap_or_default(),
+        remove_permissions.unwrap_or_default(),
+        add_users.unwrap_or(&empty),
+        remove_users.unwrap_or(&empty),
+    )
+    .await
 }
 
 /// Get role permissions (compatible with o2_openfga::authorizer::roles::get_role_permissions)
@@ -122,6 +118,11 @@ pub async fn get_users_with_role(org_id: &str, role_name: &str) -> Result<Vec<St
     role_service::get_role_users(org_id, role_name).await
 }
 
+/// Check whether a user is directly assigned to a role
+pub async fn is_role_assigned(org_id: &str, role_name: &str, user_email: &str) -> Result<bool> {
+    role_service::is_role_assigned(org_id, role_name, user_email).await
+}
+
 /// Get roles for a user in an organization (compatible with o2_openfga::authorizer::roles::get_roles_for_org_user)
 pub async fn get_roles_for_org_user(org_id: &str, user_email: &str) -> Result<Vec<String>> {
     crate::openfga::service::groups::get_user_roles(org_id, user_email).await
@@ -141,6 +142,46 @@ pub async fn get_role(org_id: &str, role_name: &str) -> Result<RoleResponse> {
     })
 }
 
+/// Get a role with users and permissions grouped by resource type in one call
+pub async fn get_role_full(org_id: &str, role_name: &str) -> Result<RoleFullResponse> {
+    role_service::get_role_full(org_id, role_name).await
+}
+
+/// Get users that have a permission on a resource ("who can access this")
+///
+/// `permission` is a permission string such as "AllowGet". Returns user
+/// emails; "*" means every user has the permission.
+pub async fn get_object_users(
+    org_id: &str,
+    resource_type: &str,
+    entity_id: &str,
+    permission: &str,
+) -> Result<Vec<String>> {
+    let permission: Permission = permission
+        .parse()
+        .map_err(|_| Error::Validation(format!("Unknown permission: {}", permission)))?;
+    let Some(resource_type) = resources::resolve_resource_key(resource_type) else {
+        return Err(Error::Validation(format!("Unknown resource type: {}", resource_type)));
+    };
+
+    let object = if resources::is_all_org_entity(entity_id, org_id) {
+        schema::resource_object_all(org_id, resource_type)
+    } else {
+        schema::resource_object(org_id, resource_type, entity_id)
+    };
+
+    let users = Visdata::global()
+        .openfga()
+        .list_users(&object, permission.to_internal_relation(), vec!["user".to_string()])
+        .await?;
+
+    Ok(users
+        .iter()
+        .filter_map(|u| u.strip_prefix("user:"))
+        .map(str::to_string)
+        .collect())
+}
+
 /// Capitalize first letter
 fn capitalize(s: &str) -> String {
     let mut chars = s.chars();
@@ -7,32 +7,231 @@
 
 //! OpenFGA HTTP client implementation
 
-use reqwest::Client;
-use serde::Deserialize;
+use futures::StreamExt;
+use reqwest::{Client, RequestBuilder, Response};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, Semaphore};
+use tracing::Span;
+use tracing::field::Empty;
 
-use super::config::OpenFGAConfig;
+use crate::common::telemetry::{self, CallTimer};
+use crate::common::tls;
+use super::config::{OpenFGAAuthMethod, OpenFGAConfig};
 use super::error::{Error, Result};
+use super::metrics;
 use super::types::*;
 
+/// Maximum number of times a read is restarted after its continuation token expires
+const MAX_READ_RESTARTS: usize = 3;
+
+/// How many times `init_store` looks up or creates the store before giving up
+const MAX_STORE_INIT_ATTEMPTS: u32 = 3;
+
+/// Check whether an OpenFGA error response is an invalid/expired continuation token
+fn is_invalid_continuation_token(status: u16, body: &str) -> bool {
+    status == 400 && body.to_lowercase().contains("invalid_continuation_token")
+}
+
+/// Restrict a read filter to objects of `object_type`
+///
+/// A filter without an object gets the type-only object "{object_type}:";
+/// an object of another type is rejected.
+fn type_qualified_filter(object_type: &str, mut filter: TupleKeyFilter) -> Result<TupleKeyFilter> {
+    let type_prefix = format!("{}:", object_type);
+    match &filter.object {
+        None => filter.object = Some(type_prefix),
+        Some(object) if object.starts_with(&type_prefix) => {}
+        Some(object) => {
+            return Err(Error::Validation(format!(
+                "Object {} is not of type {}",
+                object, object_type
+            )));
+        }
+    }
+    Ok(filter)
+}
+
+/// Whether a tuple matches a read filter, treating a type-only object
+/// ("role:") as matching every object of that type
+fn filter_matches(filter: &TupleKeyFilter, key: &TupleKey) -> bool {
+    let object_matches = |object: &str| match object.strip_suffix(':') {
+        Some(object_type) => key.object.split(':').next() == Some(object_type),
+        None => key.object == object,
+    };
+    filter.user.as_ref().is_none_or(|u| key.user == *u)
+        && filter.relation.as_ref().is_none_or(|r| key.relation == *r)
+        && filter.object.as_deref().is_none_or(object_matches)
+}
+
+/// The store every node should use among those named `name`
+///
+/// OpenFGA does not enforce unique store names, so racing nodes can create
+/// duplicates. All of them pick the oldest one, falling back to the lowest id
+/// (store ids are ULIDs and sort by creation time). Stores without a creation
+/// time sort last.
+fn canonical_store<'a>(stores: &'a [Store], name: &str) -> Option<&'a Store> {
+    let key = |s: &'a Store| (s.created_at.is_none(), &s.created_at, &s.id);
+    stores
+        .iter()
+        .filter(|s| s.name == name)
+        .min_by(|a, b| key(a).cmp(&key(b)))
+}
+
+/// Turn a non-2xx OpenFGA response into a structured error
+async fn response_error(context: &'static str, resp: Response) -> Error {
+    metrics::record_openfga_error(context);
+    let status = resp.status().as_u16();
+    let body = resp.text().await.unwrap_or_default();
+    Error::from_openfga_response(context, status, &body)
+}
+
+/// Upper bound on a single retry delay, including `Retry-After`
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Whether a response status is worth retrying (rate limited or server error)
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Whether a write response status is worth retrying
+///
+/// Only statuses where OpenFGA did not apply the write (rate limited or
+/// unavailable); other server errors may come after a commit.
+fn is_retryable_write_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::SERVICE_UNAVAILABLE
+}
+
+/// Parse a `Retry-After` header given in seconds
+fn retry_after(resp: &Response) -> Option<Duration> {
+    resp.headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Delay before retry number `attempt` (0-based)
+///
+/// Uses `Retry-After` when the server sent one, otherwise exponential backoff
+/// from `base` plus up to `base` of random jitter. Capped at `MAX_RETRY_DELAY`.
+fn retry_delay(attempt: u32, base: Duration, retry_after: Option<Duration>) -> Duration {
+    let delay = retry_after.unwrap_or_else(|| {
+        let jitter = base.mul_f64(rand::random::<f64>());
+        base.saturating_mul(2u32.saturating_pow(attempt)) + jitter
+    });
+    delay.min(MAX_RETRY_DELAY)
+}
+
+/// Build the Authorization header for the configured auth method
+///
+/// The header is marked sensitive so it is redacted from debug output.
+fn auth_header(config: &OpenFGAConfig) -> Result<Option<reqwest::header::HeaderValue>> {
+    use base64::Engine;
+
+    let value = match (config.auth_method, config.api_token.as_deref()) {
+        (OpenFGAAuthMethod::None, _) => return Ok(None),
+        (_, None | Some("")) => {
+            return Err(Error::Config(format!(
+                "OpenFGA auth method {:?} requires an api_token",
+                config.auth_method
+            )));
+        }
+        (OpenFGAAuthMethod::Bearer, Some(token)) => format!("Bearer {}", token),
+        (OpenFGAAuthMethod::Basic, Some(credentials)) => format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD.encode(credentials)
+        ),
+    };
+
+    let mut header = reqwest::header::HeaderValue::from_str(&value)
+        .map_err(|_| Error::Config("OpenFGA api_token contains invalid characters".to_string()))?;
+    header.set_sensitive(true);
+    Ok(Some(header))
+}
+
+/// Maximum number of checks sent in one batch-check request (OpenFGA's default limit)
+const MAX_BATCH_CHECK_SIZE: usize = 50;
+
+/// Map a batch-check response back to the order of the submitted checks
+///
+/// Correlation IDs are the indices of the checks within the chunk, offset by
+/// `offset`. A missing result or a per-check error fails the whole batch.
+fn batch_check_results(
+    offset: usize,
+    count: usize,
+    mut response: BatchCheckResponse,
+) -> Result<Vec<bool>> {
+    (offset..offset + count)
+        .map(|i| match response.result.remove(&i.to_string()) {
+            Some(BatchCheckSingleResult { error: Some(e), .. }) => Err(Error::OpenFGA(format!(
+                "Batch check item {} failed: {}",
+                i, e
+            ))),
+            Some(result) => Ok(result.allowed),
+            None => Err(Error::OpenFGA(format!(
+                "Batch check response missing result for item {}",
+                i
+            ))),
+        })
+        .collect()
+}
+
+/// OpenFGA client health snapshot
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenFGAHealth {
+    pub store_id: String,
+    pub model_id: Option<String>,
+    /// Requests currently holding a concurrency slot
+    pub in_flight_requests: usize,
+    pub max_concurrent_requests: usize,
+}
+
 /// OpenFGA HTTP client
 pub struct OpenFGAClient {
     http: Client,
     config: Arc<RwLock<OpenFGAConfig>>,
+    /// Shared limiter for outbound requests
+    limiter: Arc<Semaphore>,
+    max_concurrent_requests: usize,
+    timeout: Duration,
+    max_retries: u32,
+    retry_base_delay: Duration,
 }
 
 impl OpenFGAClient {
     /// Create a new OpenFGA client
     pub async fn new(config: &OpenFGAConfig) -> Result<Self> {
-        let http = Client::builder()
-            .timeout(Duration::from_secs(config.timeout_seconds))
-            .build()?;
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some(value) = auth_header(config)? {
+            headers.insert(reqwest::header::AUTHORIZATION, value);
+        }
 
+        let builder = Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .default_headers(headers);
+        let http = tls::configure(
+            builder,
+            config.tls_ca_cert_path.as_deref(),
+            config.tls_skip_verify,
+            "OpenFGA",
+        )?
+        .build()?;
+
+        let max_concurrent_requests = config.max_concurrent_requests.max(1);
         let client = Self {
             http,
             config: Arc::new(RwLock::new(config.clone())),
+            limiter: Arc::new(Semaphore::new(max_concurrent_requests)),
+            max_concurrent_requests,
+            timeout: Duration::from_secs(config.timeout_seconds),
+            max_retries: config.max_retries,
+            retry_base_delay: Duration::from_millis(config.retry_base_delay_ms),
         };
 
         // Initialize store if not configured
@@ -58,53 +257,178 @@ impl OpenFGAClient {
         self.config.read().await.model_id.clone()
     }
 
-    /// Initialize store (create if not exists, write model and initial tuples)
-    async fn init_store(&self) -> Result<()> {
-        use super::model::schema::{get_authorization_model_json, get_initial_tuples};
+    /// Apply a new configuration at runtime
+    ///
+    /// Keeps the resolved store and model when the API URL and store name are
+    /// unchanged; otherwise the store is resolved via `init_store` on a
+    /// candidate client first, and the live config is only replaced once that
+    /// succeeds. Settings fixed when the client was built (auth header,
+    /// timeout, concurrency limit, retries) are not affected.
+    pub async fn update_config(&self, mut new: OpenFGAConfig) -> Result<()> {
+        {
+            let config = self.config.read().await;
+            let same_store =
+                config.api_url == new.api_url && config.store_name == new.store_name;
+            if same_store && new.store_id.is_empty() {
+                new.store_id = config.store_id.clone();
+                new.model_id = config.model_id.clone();
+            }
+        }
+
+        if new.store_id.is_empty() {
+            let candidate = self.with_config(new);
+            candidate.init_store().await?;
+            new = candidate.config().await;
+        }
+        *self.config.write().await = new;
+        Ok(())
+    }
+
+    /// Client sharing this one's HTTP client and limiter with another config
+    fn with_config(&self, config: OpenFGAConfig) -> Self {
+        Self {
+            http: self.http.clone(),
+            config: Arc::new(RwLock::new(config)),
+            limiter: self.limiter.clone(),
+            max_concurrent_requests: self.max_concurrent_requests,
+            timeout: self.timeout,
+            max_retries: self.max_retries,
+            retry_base_delay: self.retry_base_delay,
+        }
+    }
+
+    /// Number of requests currently in flight
+    pub fn in_flight_requests(&self) -> usize {
+        self.max_concurrent_requests - self.limiter.available_permits()
+    }
 
+    /// Get a health snapshot of the client
+    pub async fn health(&self) -> OpenFGAHealth {
         let config = self.config.read().await;
-        let store_name = config.store_name.clone();
-        let api_url = config.api_url.clone();
-        drop(config);
+        OpenFGAHealth {
+            store_id: config.store_id.clone(),
+            model_id: config.model_id.clone(),
+            in_flight_requests: self.in_flight_requests(),
+            max_concurrent_requests: self.max_concurrent_requests,
+        }
+    }
 
-        // Try to find existing store
-        let stores = self.list_stores().await?;
-        let is_new_store;
+    /// Check if the OpenFGA server is reachable and serving
+    pub async fn is_healthy(&self) -> bool {
+        let url = format!("{}/healthz", self.config.read().await.api_url);
 
-        if let Some(store) = stores.iter().find(|s| s.name == store_name) {
-            let mut config = self.config.write().await;
-            config.store_id = store.id.clone();
-            tracing::info!("[OpenFGA] Using existing store: {}", store.id);
-            is_new_store = false;
-        } else {
-            // Create new store
-            let url = format!("{}/stores", api_url);
-            let req = CreateStoreRequest { name: store_name.clone() };
+        match self.send(self.http.get(&url)).await {
+            Ok(resp) => resp.status().is_success(),
+            Err(_) => false,
+        }
+    }
 
-            let resp = self.http.post(&url).json(&req).send().await?;
+    /// Send a request once a concurrency slot is available
+    ///
+    /// Waiting for a slot is bounded by the request timeout so a saturated
+    /// limiter fails instead of hanging.
+    async fn send(&self, req: RequestBuilder) -> Result<Response> {
+        let _permit = tokio::time::timeout(self.timeout, self.limiter.acquire())
+            .await
+            .map_err(|_| {
+                Error::OpenFGA(format!(
+                    "Timed out waiting for a request slot ({} in flight)",
+                    self.in_flight_requests()
+                ))
+            })?
+            .map_err(|e| Error::Internal(format!("Request limiter closed: {}", e)))?;
+
+        let resp = req.send().await.inspect_err(|_| {
+            metrics::record_openfga_error("Request failed");
+        })?;
+        telemetry::record_status(resp.status().as_u16());
+        Ok(resp)
+    }
 
-            if !resp.status().is_success() {
-                let status = resp.status();
-                let body = resp.text().await.unwrap_or_default();
-                return Err(Error::OpenFGA(format!(
-                    "Failed to create store: {} - {}",
-                    status, body
-                )));
+    /// Send a request, retrying rate-limited, server and network errors
+    ///
+    /// Other 4xx responses are returned immediately. After the last attempt
+    /// the final response (or error) is returned to the caller as-is.
+    async fn send_with_retry(&self, req: RequestBuilder) -> Result<Response> {
+        self.send_retrying(req, true).await
+    }
+
+    /// Send a write, retrying only failures where it cannot have been applied
+    ///
+    /// A write whose response was lost (timeout, server error) may have been
+    /// committed, and re-sending it would fail as a duplicate tuple write or a
+    /// delete of a missing tuple. Only connect errors and 429/503 responses
+    /// are retried (see `is_retryable_write_status`).
+    async fn send_write_with_retry(&self, req: RequestBuilder) -> Result<Response> {
+        self.send_retrying(req, false).await
+    }
+
+    /// Retry loop behind `send_with_retry` and `send_write_with_retry`
+    ///
+    /// `idempotent` requests are retried on any network error and server error.
+    async fn send_retrying(&self, req: RequestBuilder, idempotent: bool) -> Result<Response> {
+        let is_retryable = if idempotent { is_retryable_status } else { is_retryable_write_status };
+        let mut attempt = 0;
+        loop {
+            let Some(this_req) = req.try_clone() else {
+                return self.send(req).await;
+            };
+
+            let (result, after) = match self.send(this_req).await {
+                Ok(resp) if is_retryable(resp.status()) => {
+                    let after = retry_after(&resp);
+                    (Ok(resp), after)
+                }
+                Ok(resp) => return Ok(resp),
+                Err(Error::Http(e))
+                    if e.is_connect() || (idempotent && (e.is_timeout() || e.is_request())) =>
+                {
+                    (Err(Error::Http(e)), None)
+                }
+                Err(e) => return Err(e),
+            };
+
+            if attempt >= self.max_retries {
+                return result;
             }
 
-            let store: CreateStoreResponse = resp.json().await?;
-            let mut config = self.config.write().await;
-            config.store_id = store.id.clone();
-            tracing::info!("[OpenFGA] Created new store: {}", store.id);
-            is_new_store = true;
+            let delay = retry_delay(attempt, self.retry_base_delay, after);
+            match &result {
+                Ok(resp) => tracing::warn!(
+                    "[OpenFGA] Request returned {}, retrying in {:?} ({}/{})",
+                    resp.status(), delay, attempt + 1, self.max_retries
+                ),
+                Err(e) => tracing::warn!(
+                    "[OpenFGA] Request failed: {}, retrying in {:?} ({}/{})",
+                    e, delay, attempt + 1, self.max_retries
+                ),
+            }
+            tokio::time::sleep(delay).await;
+            attempt += 1;
         }
+    }
+
+    /// Initialize store (create if not exists, write model and initial tuples)
+    async fn init_store(&self) -> Result<()> {
+        use super::model::schema::{get_authorization_model_json, get_initial_tuples};
+
+        let config = self.config.read().await;
+        let store_name = config.store_name.clone();
+        let initial_tuples =
+            get_initial_tuples(&config.root_email, &config.default_org, config.include_meta_org);
+        drop(config);
+
+        let is_new_store = self.resolve_store(&store_name).await?;
 
         // Check if model exists, write if not
         let model_id = self.get_latest_model_id().await?;
+        let auto_upgrade = self.config.read().await.auto_upgrade_model;
         if model_id.is_none() {
             tracing::info!("[OpenFGA] No authorization model found, writing default model...");
             let model_json = get_authorization_model_json();
             self.write_authorization_model(model_json).await?;
+        } else if auto_upgrade {
+            self.ensure_latest_model().await?;
         } else {
             // Update config with existing model ID
             let mut config = self.config.write().await;
@@ -115,82 +439,442 @@ impl OpenFGAClient {
         // Write initial tuples only for new store
         if is_new_store {
             tracing::info!("[OpenFGA] Writing initial tuples...");
-            let initial_tuples = get_initial_tuples();
 
-            // Write tuples in batches (OpenFGA has a limit per request)
+            // Write tuples in batches (OpenFGA has a limit per request),
+            // several batches at a time
             const BATCH_SIZE: usize = 50;
-            for chunk in initial_tuples.chunks(BATCH_SIZE) {
-                if let Err(e) = self.write(chunk.to_vec(), vec![]).await {
-                    tracing::warn!("[OpenFGA] Failed to write some initial tuples: {}", e);
+            let concurrency = self.config.read().await.init_write_concurrency.max(1);
+            let failed = futures::stream::iter(initial_tuples.chunks(BATCH_SIZE))
+                .map(|chunk| self.write(chunk.to_vec(), vec![]))
+                .buffer_unordered(concurrency)
+                .filter_map(|result| async move { result.err() })
+                .fold(0usize, |failed, e| async move {
                     // Continue with other batches, some tuples might already exist
+                    tracing::warn!("[OpenFGA] Failed to write some initial tuples: {}", e);
+                    failed + 1
+                })
+                .await;
+            if failed == 0 {
+                tracing::info!("[OpenFGA] Initial tuples written successfully");
+            } else {
+                tracing::info!("[OpenFGA] Initial tuples written ({} batches failed)", failed);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find or create the store named `store_name` and make it the current store
+    ///
+    /// Returns whether this call created the store. Nodes booting at the same
+    /// time may race to create it: a conflicting create is retried after
+    /// listing again, and when duplicates exist every node adopts the same
+    /// one (see `canonical_store`), removing the duplicate it created itself.
+    async fn resolve_store(&self, store_name: &str) -> Result<bool> {
+        let url = format!("{}/stores", self.config.read().await.api_url);
+        let mut attempt = 0;
+
+        loop {
+            // Try to find existing store
+            let stores = self.list_stores().await?;
+            if let Some(store) = canonical_store(&stores, store_name) {
+                self.config.write().await.store_id = store.id.clone();
+                tracing::info!("[OpenFGA] Using existing store: {}", store.id);
+                return Ok(false);
+            }
+
+            // Create new store
+            let req = CreateStoreRequest { name: store_name.to_string() };
+            let resp = self.send(self.http.post(&url).json(&req)).await?;
+
+            if !resp.status().is_success() {
+                match response_error("Failed to create store", resp).await {
+                    Error::Conflict(msg) if attempt + 1 < MAX_STORE_INIT_ATTEMPTS => {
+                        attempt += 1;
+                        tracing::warn!(
+                            "[OpenFGA] Store creation conflicted, looking up again ({}/{}): {}",
+                            attempt, MAX_STORE_INIT_ATTEMPTS, msg
+                        );
+                        tokio::time::sleep(retry_delay(attempt, self.retry_base_delay, None)).await;
+                        continue;
+                    }
+                    e => return Err(e),
                 }
             }
-            tracing::info!("[OpenFGA] Initial tuples written successfully");
+
+            let created: CreateStoreResponse = resp.json().await?;
+
+            // Another node may have created the same store meanwhile
+            let stores = self.list_stores().await?;
+            let store_id = match canonical_store(&stores, store_name) {
+                Some(store) if store.id != created.id => {
+                    tracing::warn!(
+                        "[OpenFGA] Store {} was created concurrently, adopting it",
+                        store.id
+                    );
+                    let store_id = store.id.clone();
+                    if let Err(e) = self.delete_store(&created.id).await {
+                        tracing::warn!(
+                            "[OpenFGA] Failed to delete duplicate store {}: {}",
+                            created.id, e
+                        );
+                    }
+                    self.config.write().await.store_id = store_id;
+                    return Ok(false);
+                }
+                _ => created.id,
+            };
+
+            tracing::info!("[OpenFGA] Created new store: {}", store_id);
+            self.config.write().await.store_id = store_id;
+            return Ok(true);
         }
+    }
+
+    /// Delete a store
+    ///
+    /// Clears `store_id` and `model_id` in config when deleting the current store.
+    pub async fn delete_store(&self, store_id: &str) -> Result<()> {
+        let config = self.config.read().await;
+        let url = format!("{}/stores/{}", config.api_url, store_id);
+        drop(config);
+
+        let resp = self.send(self.http.delete(&url)).await?;
 
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(Error::StoreNotFound);
+        }
+        if !resp.status().is_success() {
+            return Err(response_error("Failed to delete store", resp).await);
+        }
+
+        let mut config = self.config.write().await;
+        if config.store_id == store_id {
+            config.store_id.clear();
+            config.model_id = None;
+        }
+        tracing::info!("[OpenFGA] Deleted store: {}", store_id);
         Ok(())
     }
 
-    /// List all stores
+    /// Delete the current store and recreate it with the model and initial tuples
+    ///
+    /// Destroys all tuples. `confirm` must be true, as a guard against
+    /// accidental use outside tests and tenant offboarding.
+    pub async fn reset_store(&self, confirm: bool) -> Result<()> {
+        if !confirm {
+            return Err(Error::Validation(
+                "reset_store deletes all authorization data and requires confirm=true".to_string(),
+            ));
+        }
+
+        let store_id = self.store_id().await;
+        if store_id.is_empty() {
+            return Err(Error::StoreNotFound);
+        }
+
+        tracing::warn!("[OpenFGA] Resetting store: {}", store_id);
+        self.delete_store(&store_id).await?;
+        self.init_store().await
+    }
+
+    /// List all stores (handles pagination automatically)
     pub async fn list_stores(&self) -> Result<Vec<Store>> {
         let config = self.config.read().await;
         let url = format!("{}/stores", config.api_url);
         drop(config);
 
-        let resp = self.http.get(&url).send().await?;
+        let mut stores = Vec::new();
+        let mut continuation_token: Option<String> = None;
 
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            return Err(Error::OpenFGA(format!(
-                "Failed to list stores: {} - {}",
-                status, body
-            )));
+        loop {
+            let mut query = Vec::new();
+            if let Some(token) = &continuation_token {
+                query.push(("continuation_token", token.as_str()));
+            }
+
+            let resp = self.send(self.http.get(&url).query(&query)).await?;
+
+            if !resp.status().is_success() {
+                return Err(response_error("Failed to list stores", resp).await);
+            }
+
+            let response: ListStoresResponse = resp.json().await?;
+            stores.extend(response.stores);
+
+            // Check if there are more pages
+            match response.continuation_token.filter(|t| !t.is_empty()) {
+                Some(token) => continuation_token = Some(token),
+                None => break,
+            }
         }
 
-        let response: ListStoresResponse = resp.json().await?;
-        Ok(response.stores)
+        Ok(stores)
     }
 
     /// Check if a user has permission on an object
     pub async fn check(&self, tuple_key: &TupleKey) -> Result<bool> {
+        self.check_with_consistency(tuple_key, ConsistencyPreference::MinimizeLatency)
+            .await
+    }
+
+    /// Check if a user has permission on an object with a consistency preference
+    pub async fn check_with_consistency(
+        &self,
+        tuple_key: &TupleKey,
+        consistency: ConsistencyPreference,
+    ) -> Result<bool> {
+        self.check_request(tuple_key, consistency, None).await
+    }
+
+    /// Check if a user has permission on an object, assuming `contextual_tuples` exist
+    pub async fn check_with_contextual_tuples(
+        &self,
+        tuple_key: &TupleKey,
+        contextual_tuples: Vec<TupleKey>,
+    ) -> Result<bool> {
+        let contextual_tuples = (!contextual_tuples.is_empty()).then_some(TupleKeys {
+            tuple_keys: contextual_tuples,
+        });
+        self.check_request(tuple_key, ConsistencyPreference::MinimizeLatency, contextual_tuples)
+            .await
+    }
+
+    #[tracing::instrument(
+        name = "openfga.check",
+        skip_all,
+        fields(
+            operation = "check",
+            relation = %tuple_key.relation,
+            object = %tuple_key.object,
+            store_id = Empty,
+            status = Empty,
+            elapsed_ms = Empty,
+        )
+    )]
+    async fn check_request(
+        &self,
+        tuple_key: &TupleKey,
+        consistency: ConsistencyPreference,
+        contextual_tuples: Option<TupleKeys>,
+    ) -> Result<bool> {
+        let _timer = CallTimer::start();
         let config = self.config.read().await;
         if config.store_id.is_empty() {
             return Err(Error::StoreNotFound);
         }
+        Span::current().record("store_id", config.store_id.as_str());
 
         let url = format!("{}/stores/{}/check", config.api_url, config.store_id);
         let req = CheckRequest {
             tuple_key: tuple_key.clone(),
             authorization_model_id: config.model_id.clone(),
+            consistency,
+            contextual_tuples,
         };
         drop(config);
 
-        let resp = self.http.post(&url).json(&req).send().await?;
+        let resp = self.send_with_retry(self.http.post(&url).json(&req)).await?;
 
         if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            return Err(Error::OpenFGA(format!(
-                "Check failed: {} - {}",
-                status, body
-            )));
+            return Err(response_error("Check failed", resp).await);
         }
 
         let response: CheckResponse = resp.json().await?;
         Ok(response.allowed)
     }
 
+    /// Check many tuples at once
+    ///
+    /// Returns results in the same order as `tuples`. Falls back to parallel
+    /// `check` calls when the server has no batch-check endpoint (404).
+    pub async fn batch_check(&self, tuples: Vec<TupleKey>) -> Result<Vec<bool>> {
+        if tuples.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let config = self.config.read().await;
+        if config.store_id.is_empty() {
+            return Err(Error::StoreNotFound);
+        }
+
+        let url = format!("{}/stores/{}/batch-check", config.api_url, config.store_id);
+        let model_id = config.model_id.clone();
+        drop(config);
+
+        let mut results = Vec::with_capacity(tuples.len());
+        for (chunk_idx, chunk) in tuples.chunks(MAX_BATCH_CHECK_SIZE).enumerate() {
+            let offset = chunk_idx * MAX_BATCH_CHECK_SIZE;
+            let req = BatchCheckRequest {
+                checks: chunk
+                    .iter()
+                    .enumerate()
+                    .map(|(i, tuple_key)| BatchCheckItem {
+                        tuple_key: tuple_key.clone(),
+                        correlation_id: (offset + i).to_string(),
+                    })
+                    .collect(),
+                authorization_model_id: model_id.clone(),
+            };
+
+            let resp = self.send(self.http.post(&url).json(&req)).await?;
+
+            if resp.status() == reqwest::StatusCode::NOT_FOUND {
+                tracing::debug!("[OpenFGA] Batch check not supported, falling back to parallel checks");
+                return futures::future::join_all(tuples.iter().map(|t| self.check(t)))
+                    .await
+                    .into_iter()
+                    .collect();
+            }
+
+            if !resp.status().is_success() {
+                return Err(response_error("Batch check failed", resp).await);
+            }
+
+            let response: BatchCheckResponse = resp.json().await?;
+            results.extend(batch_check_results(offset, chunk.len(), response)?);
+        }
+
+        Ok(results)
+    }
+
+    /// List users that have a relation on an object (reverse of `list_objects`)
+    ///
+    /// `object` is "type:id"; `user_filters` are user types such as "user" or
+    /// "group#member". Returns users as "user:alice", "group:x#member" or "user:*".
+    pub async fn list_users(
+        &self,
+        object: &str,
+        relation: &str,
+        user_filters: Vec<String>,
+    ) -> Result<Vec<String>> {
+        let (object_type, object_id) = object.split_once(':').ok_or_else(|| {
+            Error::Validation(format!("Invalid object '{}': expected 'type:id'", object))
+        })?;
+
+        let config = self.config.read().await;
+        if config.store_id.is_empty() {
+            return Err(Error::StoreNotFound);
+        }
+
+        let url = format!("{}/stores/{}/list-users", config.api_url, config.store_id);
+        let req = ListUsersRequest {
+            object: FgaObject {
+                type_: object_type.to_string(),
+                id: object_id.to_string(),
+            },
+            relation: relation.to_string(),
+            user_filters: user_filters.iter().map(|f| UserTypeFilter::parse(f)).collect(),
+            authorization_model_id: config.model_id.clone(),
+        };
+        drop(config);
+
+        let resp = self.send(self.http.post(&url).json(&req)).await?;
+
+        let status = resp.status();
+        if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::NOT_IMPLEMENTED {
+            return Err(Error::OpenFGA(format!(
+                "List users is not supported by this OpenFGA server ({}); upgrade OpenFGA to use it",
+                status
+            )));
+        }
+        if !status.is_success() {
+            return Err(response_error("List users failed", resp).await);
+        }
+
+        let response: ListUsersResponse = resp.json().await.map_err(|e| {
+            Error::OpenFGA(format!("Unexpected list users response: {}", e))
+        })?;
+        Ok(response.users.iter().filter_map(ListUser::to_fga_user).collect())
+    }
+
+    /// Read tuple changes, oldest first
+    ///
+    /// Returns one page of changes (optionally limited to `object_type`) and the
+    /// token to pass back for the next page. The token is returned even when
+    /// no changes remain, so it can be stored and polled later.
+    pub async fn read_changes(
+        &self,
+        object_type: Option<&str>,
+        continuation_token: Option<String>,
+    ) -> Result<(Vec<TupleChange>, Option<String>)> {
+        let config = self.config.read().await;
+        if config.store_id.is_empty() {
+            return Err(Error::StoreNotFound);
+        }
+
+        let url = format!("{}/stores/{}/changes", config.api_url, config.store_id);
+        drop(config);
+
+        let mut query = Vec::new();
+        if let Some(object_type) = object_type {
+            query.push(("type", object_type.to_string()));
+        }
+        if let Some(token) = continuation_token {
+            query.push(("continuation_token", token));
+        }
+
+        let resp = self.send_with_retry(self.http.get(&url).query(&query)).await?;
+
+        if !resp.status().is_success() {
+            return Err(response_error("Read changes failed", resp).await);
+        }
+
+        let response: ReadChangesResponse = resp.json().await?;
+        let token = response.continuation_token.filter(|t| !t.is_empty());
+        Ok((response.changes, token))
+    }
+
+    /// Expand a relation on an object into its userset resolution tree
+    pub async fn expand(&self, relation: &str, object: &str) -> Result<ExpandResponse> {
+        let config = self.config.read().await;
+        if config.store_id.is_empty() {
+            return Err(Error::StoreNotFound);
+        }
+
+        let url = format!("{}/stores/{}/expand", config.api_url, config.store_id);
+        let req = ExpandRequest {
+            tuple_key: ExpandTupleKey {
+                relation: relation.to_string(),
+                object: object.to_string(),
+            },
+            authorization_model_id: config.model_id.clone(),
+        };
+        drop(config);
+
+        let resp = self.send(self.http.post(&url).json(&req)).await?;
+
+        if !resp.status().is_success() {
+            return Err(response_error("Expand failed", resp).await);
+        }
+
+        Ok(resp.json().await?)
+    }
+
     /// Write tuples (add and/or delete)
+    #[tracing::instrument(
+        name = "openfga.write",
+        skip_all,
+        fields(
+            operation = "write",
+            writes = writes.len(),
+            deletes = deletes.len(),
+            store_id = Empty,
+            status = Empty,
+            elapsed_ms = Empty,
+        )
+    )]
     pub async fn write(
         &self,
         writes: Vec<TupleKey>,
         deletes: Vec<TupleKey>,
     ) -> Result<()> {
+        let _timer = CallTimer::start();
         let config = self.config.read().await;
         if config.store_id.is_empty() {
             return Err(Error::StoreNotFound);
         }
+        Span::current().record("store_id", config.store_id.as_str());
 
         let url = format!("{}/stores/{}/write", config.api_url, config.store_id);
 
@@ -209,15 +893,10 @@ impl OpenFGAClient {
         };
         drop(config);
 
-        let resp = self.http.post(&url).json(&req).send().await?;
+        let resp = self.send_write_with_retry(self.http.post(&url).json(&req)).await?;
 
         if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            return Err(Error::OpenFGA(format!(
-                "Write failed: {} - {}",
-                status, body
-            )));
+            return Err(response_error("Write failed", resp).await);
         }
 
         Ok(())
@@ -226,11 +905,24 @@ impl OpenFGAClient {
     /// Read tuples with optional filter (handles pagination automatically)
     /// Note: OpenFGA requires object type in filter. If filter is incomplete,
     /// we read all tuples and filter in memory.
+    #[tracing::instrument(
+        name = "openfga.read",
+        skip_all,
+        fields(
+            operation = "read",
+            store_id = Empty,
+            status = Empty,
+            tuples = Empty,
+            elapsed_ms = Empty,
+        )
+    )]
     pub async fn read(&self, filter: Option<TupleKeyFilter>) -> Result<Vec<Tuple>> {
+        let _timer = CallTimer::start();
         let config = self.config.read().await;
         if config.store_id.is_empty() {
             return Err(Error::StoreNotFound);
         }
+        Span::current().record("store_id", config.store_id.as_str());
 
         let url = format!("{}/stores/{}/read", config.api_url, config.store_id);
         drop(config);
@@ -245,6 +937,43 @@ impl OpenFGAClient {
             _ => (filter, None),
         };
 
+        // Restart the scan from the beginning if the continuation token expires
+        // mid-pagination (e.g., long scans on a busy store)
+        let mut restarts = 0;
+        let mut all_tuples = loop {
+            match self.read_pages(&url, &api_filter).await {
+                Err(Error::PaginationExpired(msg)) if restarts < MAX_READ_RESTARTS => {
+                    restarts += 1;
+                    tracing::warn!(
+                        "[OpenFGA] Continuation token expired, restarting read ({}/{}): {}",
+                        restarts, MAX_READ_RESTARTS, msg
+                    );
+                }
+                result => break result?,
+            }
+        };
+
+        // Apply memory filter if needed
+        if let Some(f) = memory_filter {
+            all_tuples.retain(|t| {
+                let user_match = f.user.as_ref().map_or(true, |u| t.key.user == *u);
+                let relation_match = f.relation.as_ref().map_or(true, |r| t.key.relation == *r);
+                let object_match = f.object.as_ref().map_or(true, |o| t.key.object == *o);
+                user_match && relation_match && object_match
+            });
+        }
+
+        Span::current().record("tuples", all_tuples.len());
+        Ok(all_tuples)
+    }
+
+    /// Read all pages for a filter, returning `Error::PaginationExpired` if
+    /// OpenFGA rejects a continuation token
+    async fn read_pages(
+        &self,
+        url: &str,
+        api_filter: &Option<TupleKeyFilter>,
+    ) -> Result<Vec<Tuple>> {
         let mut all_tuples = Vec::new();
         let mut continuation_token: Option<String> = None;
 
@@ -253,17 +982,20 @@ impl OpenFGAClient {
                 tuple_key: api_filter.clone(),
                 page_size: Some(100), // OpenFGA max is 100
                 continuation_token: continuation_token.clone(),
+                consistency: ConsistencyPreference::default(),
             };
 
-            let resp = self.http.post(&url).json(&req).send().await?;
+            let resp = self.send_with_retry(self.http.post(url).json(&req)).await?;
 
             if !resp.status().is_success() {
                 let status = resp.status();
                 let body = resp.text().await.unwrap_or_default();
-                return Err(Error::OpenFGA(format!(
-                    "Read failed: {} - {}",
-                    status, body
-                )));
+                if continuation_token.is_some()
+                    && is_invalid_continuation_token(status.as_u16(), &body)
+                {
+                    return Err(Error::PaginationExpired(body));
+                }
+                return Err(Error::from_openfga_response("Read failed", status.as_u16(), &body));
             }
 
             let response: ReadResponse = resp.json().await?;
@@ -276,17 +1008,29 @@ impl OpenFGAClient {
             continuation_token = response.continuation_token;
         }
 
-        // Apply memory filter if needed
-        if let Some(f) = memory_filter {
-            all_tuples.retain(|t| {
-                let user_match = f.user.as_ref().map_or(true, |u| t.key.user == *u);
-                let relation_match = f.relation.as_ref().map_or(true, |r| t.key.relation == *r);
-                let object_match = f.object.as_ref().map_or(true, |o| t.key.object == *o);
-                user_match && relation_match && object_match
-            });
+        Ok(all_tuples)
+    }
+
+    /// Read tuples on objects of one type, filtered server-side
+    ///
+    /// Fills in the type-only object ("role:") when `filter` has no object,
+    /// so OpenFGA only returns tuples of that type instead of the whole store.
+    /// OpenFGA accepts a type-only object only together with a user; without
+    /// one this falls back to reading everything and filtering in memory.
+    pub async fn read_by_object_type(
+        &self,
+        object_type: &str,
+        filter: TupleKeyFilter,
+    ) -> Result<Vec<Tuple>> {
+        let filter = type_qualified_filter(object_type, filter)?;
+        let type_only = filter.object.as_deref().is_some_and(|o| o.ends_with(':'));
+        if filter.user.is_some() || !type_only {
+            return self.read(Some(filter)).await;
         }
 
-        Ok(all_tuples)
+        let mut tuples = self.read(None).await?;
+        tuples.retain(|t| filter_matches(&filter, &t.key));
+        Ok(tuples)
     }
 
     /// List objects that a user can access with a specific relation
@@ -296,10 +1040,41 @@ impl OpenFGAClient {
         relation: &str,
         object_type: &str,
     ) -> Result<Vec<String>> {
+        self.list_objects_with_consistency(
+            user,
+            relation,
+            object_type,
+            ConsistencyPreference::MinimizeLatency,
+        )
+        .await
+    }
+
+    /// List objects that a user can access with a consistency preference
+    #[tracing::instrument(
+        name = "openfga.list_objects",
+        skip_all,
+        fields(
+            operation = "list_objects",
+            relation = %relation,
+            object_type = %object_type,
+            store_id = Empty,
+            status = Empty,
+            elapsed_ms = Empty,
+        )
+    )]
+    pub async fn list_objects_with_consistency(
+        &self,
+        user: &str,
+        relation: &str,
+        object_type: &str,
+        consistency: ConsistencyPreference,
+    ) -> Result<Vec<String>> {
+        let _timer = CallTimer::start();
         let config = self.config.read().await;
         if config.store_id.is_empty() {
             return Err(Error::StoreNotFound);
         }
+        Span::current().record("store_id", config.store_id.as_str());
 
         let url = format!("{}/stores/{}/list-objects", config.api_url, config.store_id);
         let req = ListObjectsRequest {
@@ -307,18 +1082,14 @@ impl OpenFGAClient {
             relation: relation.to_string(),
             type_: object_type.to_string(),
             authorization_model_id: config.model_id.clone(),
+            consistency,
         };
         drop(config);
 
-        let resp = self.http.post(&url).json(&req).send().await?;
+        let resp = self.send_with_retry(self.http.post(&url).json(&req)).await?;
 
         if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            return Err(Error::OpenFGA(format!(
-                "List objects failed: {} - {}",
-                status, body
-            )));
+            return Err(response_error("List objects failed", resp).await);
         }
 
         let response: ListObjectsResponse = resp.json().await?;
@@ -341,15 +1112,10 @@ impl OpenFGAClient {
         // Parse model JSON
         let model: serde_json::Value = serde_json::from_str(model_json)?;
 
-        let resp = self.http.post(&url).json(&model).send().await?;
+        let resp = self.send(self.http.post(&url).json(&model)).await?;
 
         if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            return Err(Error::OpenFGA(format!(
-                "Write model failed: {} - {}",
-                status, body
-            )));
+            return Err(response_error("Write model failed", resp).await);
         }
 
         #[derive(Deserialize)]
@@ -381,15 +1147,10 @@ impl OpenFGAClient {
         );
         drop(config);
 
-        let resp = self.http.get(&url).send().await?;
+        let resp = self.send(self.http.get(&url)).await?;
 
         if !resp.status().is_success() {
-            let status = resp.status();
-            let body = resp.text().await.unwrap_or_default();
-            return Err(Error::OpenFGA(format!(
-                "Get models failed: {} - {}",
-                status, body
-            )));
+            return Err(response_error("Get models failed", resp).await);
         }
 
         #[derive(Deserialize)]
@@ -406,6 +1167,134 @@ impl OpenFGAClient {
 
         Ok(response.authorization_models.first().map(|m| m.id.clone()))
     }
+
+    /// Read an authorization model by ID
+    pub async fn read_authorization_model(&self, model_id: &str) -> Result<serde_json::Value> {
+        let config = self.config.read().await;
+        if config.store_id.is_empty() {
+            return Err(Error::StoreNotFound);
+        }
+
+        let url = format!(
+            "{}/stores/{}/authorization-models/{}",
+            config.api_url, config.store_id, model_id
+        );
+        drop(config);
+
+        let resp = self.send(self.http.get(&url)).await?;
+
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(Error::ModelNotFound);
+        }
+        if !resp.status().is_success() {
+            return Err(response_error("Read model failed", resp).await);
+        }
+
+        let mut response: serde_json::Value = resp.json().await?;
+        Ok(response["authorization_model"].take())
+    }
+
+    /// Write the bundled authorization model if the store's latest model differs
+    ///
+    /// Type definitions are compared semantically; a new model version is
+    /// written only when a type was added, removed or changed. Returns whether
+    /// a new version was written.
+    pub async fn ensure_latest_model(&self) -> Result<bool> {
+        use super::model::schema::{diff_type_definitions, get_authorization_model_json};
+
+        let model_json = get_authorization_model_json();
+        let Some(model_id) = self.get_latest_model_id().await? else {
+            tracing::info!("[OpenFGA] No authorization model found, writing default model...");
+            self.write_authorization_model(model_json).await?;
+            return Ok(true);
+        };
+
+        let current = self.read_authorization_model(&model_id).await?;
+        let desired: serde_json::Value = serde_json::from_str(model_json)?;
+        let diff = diff_type_definitions(&current, &desired);
+        if diff.is_empty() {
+            self.config.write().await.model_id = Some(model_id);
+            tracing::info!("[OpenFGA] Authorization model is up to date");
+            return Ok(false);
+        }
+
+        tracing::info!(
+            "[OpenFGA] Upgrading authorization model {} \
+             (added: {:?}, removed: {:?}, changed: {:?})",
+            model_id,
+            diff.added,
+            diff.removed,
+            diff.changed
+        );
+        self.write_authorization_model(model_json).await?;
+        Ok(true)
+    }
+
+    /// Store assertions for the current authorization model, replacing existing ones
+    pub async fn write_assertions(&self, assertions: Vec<Assertion>) -> Result<()> {
+        let url = self.assertions_url().await?;
+        let req = WriteAssertionsRequest { assertions };
+
+        let resp = self.send(self.http.put(&url).json(&req)).await?;
+
+        if !resp.status().is_success() {
+            return Err(response_error("Write assertions failed", resp).await);
+        }
+
+        Ok(())
+    }
+
+    /// Read the assertions stored for the current authorization model
+    pub async fn read_assertions(&self) -> Result<Vec<Assertion>> {
+        let url = self.assertions_url().await?;
+
+        let resp = self.send(self.http.get(&url)).await?;
+
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(Error::ModelNotFound);
+        }
+        if !resp.status().is_success() {
+            return Err(response_error("Read assertions failed", resp).await);
+        }
+
+        let response: ReadAssertionsResponse = resp.json().await?;
+        Ok(response.assertions)
+    }
+
+    /// Store `assertions`, read them back and check each against the store
+    ///
+    /// Returns the assertions whose check result differs from their
+    /// expectation; an empty list means the model behaves as asserted.
+    pub async fn run_assertions(&self, assertions: Vec<Assertion>) -> Result<Vec<Assertion>> {
+        self.write_assertions(assertions).await?;
+
+        let mut failures = Vec::new();
+        for assertion in self.read_assertions().await? {
+            let allowed = self
+                .check_with_contextual_tuples(
+                    &assertion.tuple_key,
+                    assertion.contextual_tuples.clone(),
+                )
+                .await?;
+            if allowed != assertion.expectation {
+                failures.push(assertion);
+            }
+        }
+        Ok(failures)
+    }
+
+    /// Assertions endpoint for the current store and model
+    async fn assertions_url(&self) -> Result<String> {
+        let config = self.config.read().await;
+        if config.store_id.is_empty() {
+            return Err(Error::StoreNotFound);
+        }
+        let model_id = config.model_id.as_deref().ok_or(Error::ModelNotFound)?;
+        Ok(format!(
+            "{}/stores/{}/assertions/{}",
+            config.api_url, config.store_id, model_id
+        ))
+    }
 }
 
 #[cfg(test)]
@@ -419,4 +1308,314 @@ mod tests {
         assert_eq!(key.relation, "viewer");
         assert_eq!(key.object, "document:doc1");
     }
+
+    #[test]
+    fn test_is_invalid_continuation_token() {
+        let body = r#"{"code":"invalid_continuation_token","message":"invalid continuation token"}"#;
+        assert!(is_invalid_continuation_token(400, body));
+        assert!(!is_invalid_continuation_token(500, body));
+        assert!(!is_invalid_continuation_token(
+            400,
+            r#"{"code":"validation_error","message":"invalid tuple"}"#
+        ));
+    }
+
+    #[test]
+    fn test_type_qualified_filter() {
+        let filter = TupleKeyFilter {
+            user: Some("org:default".to_string()),
+            relation: Some("owningOrg".to_string()),
+            object: None,
+        };
+        let qualified = type_qualified_filter("role", filter.clone()).unwrap();
+        assert_eq!(qualified.object.as_deref(), Some("role:"));
+        assert_eq!(qualified.user, filter.user);
+
+        // Objects of the right type are kept, others rejected
+        let exact = TupleKeyFilter {
+            object: Some("role:default_dev".to_string()),
+            ..filter.clone()
+        };
+        let qualified = type_qualified_filter("role", exact).unwrap();
+        assert_eq!(qualified.object.as_deref(), Some("role:default_dev"));
+        let other = TupleKeyFilter {
+            object: Some("group:default_dev".to_string()),
+            ..filter
+        };
+        assert!(matches!(type_qualified_filter("role", other), Err(Error::Validation(_))));
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let key = TupleKey::new("user:a@b.com", "member", "group:default_dev");
+        let filter = |relation: Option<&str>, object: &str| TupleKeyFilter {
+            user: None,
+            relation: relation.map(str::to_string),
+            object: Some(object.to_string()),
+        };
+        assert!(filter_matches(&filter(None, "group:"), &key));
+        assert!(filter_matches(&filter(Some("member"), "group:default_dev"), &key));
+        assert!(!filter_matches(&filter(Some("owningOrg"), "group:"), &key));
+        assert!(!filter_matches(&filter(None, "role:"), &key));
+        // A type-only filter does not match types sharing the prefix
+        assert!(!filter_matches(&filter(None, "grou:"), &key));
+        assert!(filter_matches(&TupleKeyFilter::default(), &key));
+    }
+
+    #[tokio::test]
+    async fn test_send_times_out_when_limiter_saturated() {
+        let client = OpenFGAClient {
+            http: Client::new(),
+            config: Arc::new(RwLock::new(OpenFGAConfig::default())),
+            limiter: Arc::new(Semaphore::new(1)),
+            max_concurrent_requests: 1,
+            timeout: Duration::from_millis(20),
+            max_retries: 0,
+            retry_base_delay: Duration::ZERO,
+        };
+
+        let _held = client.limiter.acquire().await.unwrap();
+        assert_eq!(client.in_flight_requests(), 1);
+
+        let err = client
+            .send(client.http.get("http://127.0.0.1:9/unreachable"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Timed out waiting for a request slot"));
+
+        let health = client.health().await;
+        assert_eq!(health.in_flight_requests, 1);
+        assert_eq!(health.max_concurrent_requests, 1);
+    }
+
+    #[test]
+    fn test_batch_check_results_ordered_by_correlation_id() {
+        let response: BatchCheckResponse = serde_json::from_value(serde_json::json!({
+            "result": {
+                "51": {"allowed": true},
+                "50": {"allowed": false},
+                "52": {"allowed": true}
+            }
+        }))
+        .unwrap();
+
+        let results = batch_check_results(50, 3, response).unwrap();
+        assert_eq!(results, vec![false, true, true]);
+    }
+
+    #[test]
+    fn test_batch_check_results_missing_or_failed_item() {
+        let response: BatchCheckResponse = serde_json::from_value(serde_json::json!({
+            "result": {"0": {"allowed": true}}
+        }))
+        .unwrap();
+        assert!(batch_check_results(0, 2, response).is_err());
+
+        let response: BatchCheckResponse = serde_json::from_value(serde_json::json!({
+            "result": {"0": {"error": {"message": "type not found"}}}
+        }))
+        .unwrap();
+        let err = batch_check_results(0, 1, response).unwrap_err();
+        assert!(err.to_string().contains("type not found"));
+    }
+
+    #[test]
+    fn test_is_retryable_status() {
+        use reqwest::StatusCode;
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(is_retryable_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!is_retryable_status(StatusCode::BAD_REQUEST));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+        assert!(!is_retryable_status(StatusCode::OK));
+
+        // Writes are only retried where OpenFGA cannot have committed them
+        assert!(is_retryable_write_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_write_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_retryable_write_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!is_retryable_write_status(StatusCode::GATEWAY_TIMEOUT));
+        assert!(!is_retryable_write_status(StatusCode::BAD_REQUEST));
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let base = Duration::from_millis(100);
+
+        let first = retry_delay(0, base, None);
+        assert!(first >= base && first <= base * 2);
+        let third = retry_delay(2, base, None);
+        assert!(third >= base * 4 && third <= base * 5);
+
+        // Retry-After wins, but is capped
+        assert_eq!(retry_delay(0, base, Some(Duration::from_secs(2))), Duration::from_secs(2));
+        assert_eq!(retry_delay(0, base, Some(Duration::from_secs(3600))), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(40, base, None), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn test_auth_header() {
+        let config = OpenFGAConfig::default();
+        assert!(auth_header(&config).unwrap().is_none());
+
+        let config = OpenFGAConfig::default()
+            .with_auth(OpenFGAAuthMethod::Bearer, Some("tok123".to_string()));
+        let header = auth_header(&config).unwrap().unwrap();
+        assert_eq!(header.to_str().unwrap(), "Bearer tok123");
+        assert!(header.is_sensitive());
+        assert!(!format!("{:?}", header).contains("tok123"));
+
+        let config = OpenFGAConfig::default()
+            .with_auth(OpenFGAAuthMethod::Basic, Some("fga:pass".to_string()));
+        let header = auth_header(&config).unwrap().unwrap();
+        assert_eq!(header.to_str().unwrap(), "Basic ZmdhOnBhc3M=");
+
+        let config = OpenFGAConfig::default().with_auth(OpenFGAAuthMethod::Bearer, None);
+        assert!(auth_header(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_reset_store_requires_confirm() {
+        let client = OpenFGAClient {
+            http: Client::new(),
+            config: Arc::new(RwLock::new(OpenFGAConfig::default().with_store_id("s1"))),
+            limiter: Arc::new(Semaphore::new(1)),
+            max_concurrent_requests: 1,
+            timeout: Duration::from_millis(20),
+            max_retries: 0,
+            retry_base_delay: Duration::ZERO,
+        };
+
+        let err = client.reset_store(false).await.unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+        assert_eq!(client.store_id().await, "s1");
+    }
+
+    /// Serve HTTP requests with `respond`, one connection per request
+    ///
+    /// `respond` gets the request line (e.g. "GET /stores HTTP/1.1") and
+    /// returns the status code and JSON body. Returns the server URL.
+    async fn mock_server<F>(respond: F) -> String
+    where
+        F: Fn(&str) -> (u16, &'static str) + Send + 'static,
+    {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match socket.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request);
+                let (status, body) = respond(request.lines().next().unwrap_or_default());
+                let response = format!(
+                    "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    /// Client for a mock server, with a store name of "visdata"
+    fn mock_client(api_url: &str) -> OpenFGAClient {
+        let config = OpenFGAConfig::default().with_api_url(api_url).with_store_name("visdata");
+        OpenFGAClient {
+            http: Client::new(),
+            config: Arc::new(RwLock::new(config)),
+            limiter: Arc::new(Semaphore::new(1)),
+            max_concurrent_requests: 1,
+            timeout: Duration::from_secs(5),
+            max_retries: 0,
+            retry_base_delay: Duration::ZERO,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_list_stores_follows_continuation_token() {
+        let api_url = mock_server(|request| {
+            if request.contains("continuation_token=page2") {
+                (200, r#"{"stores":[{"id":"s2","name":"visdata"}],"continuation_token":""}"#)
+            } else {
+                (200, r#"{"stores":[{"id":"s1","name":"other"}],"continuation_token":"page2"}"#)
+            }
+        })
+        .await;
+        let client = mock_client(&api_url);
+
+        let stores = client.list_stores().await.unwrap();
+        let ids: Vec<&str> = stores.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["s1", "s2"]);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_store_adopts_store_after_create_conflict() {
+        // Another node creates the store between our list and our create
+        let created = std::sync::atomic::AtomicBool::new(false);
+        let api_url = mock_server(move |request| {
+            use std::sync::atomic::Ordering;
+            if request.starts_with("POST /stores") {
+                created.store(true, Ordering::SeqCst);
+                (409, r#"{"code":"aborted","message":"store already exists"}"#)
+            } else if created.load(Ordering::SeqCst) {
+                (200, r#"{"stores":[{"id":"01HOTHER","name":"visdata"}]}"#)
+            } else {
+                (200, r#"{"stores":[]}"#)
+            }
+        })
+        .await;
+        let client = mock_client(&api_url);
+
+        let is_new_store = client.resolve_store("visdata").await.unwrap();
+        assert!(!is_new_store);
+        assert_eq!(client.store_id().await, "01HOTHER");
+    }
+
+    #[tokio::test]
+    async fn test_update_config_keeps_live_config_when_init_fails() {
+        let api_url = mock_server(|_| (500, r#"{"code":"internal_error"}"#)).await;
+        let client = mock_client(&api_url);
+        client.config.write().await.store_id = "s1".to_string();
+
+        // A new store name needs the store resolved again, which fails
+        let new = OpenFGAConfig::default().with_api_url(&api_url).with_store_name("renamed");
+        assert!(client.update_config(new).await.is_err());
+
+        let config = client.config().await;
+        assert_eq!(config.store_name, "visdata");
+        assert_eq!(config.store_id, "s1");
+    }
+
+    #[test]
+    fn test_canonical_store_picks_oldest_duplicate() {
+        let store = |id: &str, name: &str, created_at: &str| Store {
+            id: id.to_string(),
+            name: name.to_string(),
+            created_at: Some(created_at.to_string()),
+            updated_at: None,
+        };
+        let stores = vec![
+            store("01B", "visdata", "2025-01-02T00:00:00Z"),
+            store("01A", "other", "2025-01-01T00:00:00Z"),
+            store("01C", "visdata", "2025-01-01T00:00:00Z"),
+        ];
+        assert_eq!(canonical_store(&stores, "visdata").unwrap().id, "01C");
+        assert!(canonical_store(&stores, "missing").is_none());
+
+        // A store without a creation time never wins over a dated one
+        let mut undated = store("01", "visdata", "");
+        undated.created_at = None;
+        let stores = vec![undated, store("01D", "visdata", "2025-01-03T00:00:00Z")];
+        assert_eq!(canonical_store(&stores, "visdata").unwrap().id, "01D");
+    }
 }
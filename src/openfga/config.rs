@@ -8,9 +8,27 @@
 //! OpenFGA configuration
 
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::common::redact;
+use crate::config::CacheConfig;
+
+/// How requests to OpenFGA are authenticated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OpenFGAAuthMethod {
+    /// No Authorization header
+    #[default]
+    None,
+    /// `Authorization: Bearer <api_token>`
+    Bearer,
+    /// `Authorization: Basic <base64(api_token)>`, with `api_token` as "user:password"
+    Basic,
+}
 
 /// OpenFGA configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OpenFGAConfig {
     /// OpenFGA HTTP API URL (e.g., "http://localhost:8080")
     pub api_url: String,
@@ -35,9 +53,150 @@ pub struct OpenFGAConfig {
     #[serde(default = "default_true")]
     pub list_only_permitted: bool,
 
+    /// API token or "user:password" credentials, depending on `auth_method`
+    #[serde(default, skip_serializing)]
+    pub api_token: Option<String>,
+
+    /// Authentication method for OpenFGA requests
+    #[serde(default)]
+    pub auth_method: OpenFGAAuthMethod,
+
     /// Request timeout in seconds
     #[serde(default = "default_timeout")]
     pub timeout_seconds: u64,
+
+    /// PEM CA certificate (or bundle) trusted in addition to the system roots
+    #[serde(default)]
+    pub tls_ca_cert_path: Option<String>,
+
+    /// Skip TLS certificate verification (insecure; for testing only)
+    #[serde(default)]
+    pub tls_skip_verify: bool,
+
+    /// Overrides for the system role -> org relation mapping
+    ///
+    /// Keys are role names (case-insensitive), values are relations on the
+    /// `org` type (e.g., "auditor" -> "viewer").
+    #[serde(default)]
+    pub role_relation_overrides: HashMap<String, String>,
+
+    /// Root user granted admin on the bootstrap orgs of a new store
+    #[serde(default = "default_root_email")]
+    pub root_email: String,
+
+    /// Organization bootstrapped in a new store
+    #[serde(default = "default_org")]
+    pub default_org: String,
+
+    /// Also bootstrap the internal `_meta` organization
+    #[serde(default = "default_true")]
+    pub include_meta_org: bool,
+
+    /// Write the bundled authorization model on startup when the store's differs
+    #[serde(default)]
+    pub auto_upgrade_model: bool,
+
+    /// Cloud deployment (hides non-cloud resources such as license)
+    #[serde(default)]
+    pub is_cloud: bool,
+
+    /// Users (emails) that bypass all permission checks
+    ///
+    /// Matched against the checked user ID, case-insensitively, regardless of
+    /// the role the caller passes.
+    #[serde(default)]
+    pub root_emails: HashSet<String>,
+
+    /// Trust a caller-supplied "root" role string to bypass permission checks
+    ///
+    /// Deprecated in favor of `root_emails`: every bypass granted this way is
+    /// logged so the callers can be found. When false (the default), a "root"
+    /// role only bypasses checks if the user holds `admin` on `org:_meta` in
+    /// OpenFGA. This default used to be true: deployments upgrading from it
+    /// must list their root users in `root_emails` or write their `_meta`
+    /// admin tuple (see the README).
+    #[serde(default)]
+    pub trust_root_role_string: bool,
+
+    /// Maximum number of concurrent outbound requests to OpenFGA
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+
+    /// Number of initial tuple batches written concurrently during store bootstrap
+    #[serde(default = "default_init_write_concurrency")]
+    pub init_write_concurrency: usize,
+
+    /// Maximum number of retries for rate-limited, server or network errors
+    ///
+    /// Tuple writes are only retried on connect errors and 429/503 responses.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// Base delay for exponential retry backoff, in milliseconds
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub retry_base_delay_ms: u64,
+
+    /// Permission check result cache
+    #[serde(default)]
+    pub cache: CacheConfig,
+}
+
+impl fmt::Debug for OpenFGAConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Destructured so a new field cannot be left out
+        let Self {
+            api_url,
+            store_id,
+            model_id,
+            store_name,
+            enabled,
+            list_only_permitted,
+            api_token,
+            auth_method,
+            timeout_seconds,
+            tls_ca_cert_path,
+            tls_skip_verify,
+            role_relation_overrides,
+            root_email,
+            default_org,
+            include_meta_org,
+            auto_upgrade_model,
+            is_cloud,
+            root_emails,
+            trust_root_role_string,
+            max_concurrent_requests,
+            init_write_concurrency,
+            max_retries,
+            retry_base_delay_ms,
+            cache,
+        } = self;
+        f.debug_struct("OpenFGAConfig")
+            .field("api_url", api_url)
+            .field("store_id", store_id)
+            .field("model_id", model_id)
+            .field("store_name", store_name)
+            .field("enabled", enabled)
+            .field("list_only_permitted", list_only_permitted)
+            .field("api_token", &redact::optional_secret(api_token))
+            .field("auth_method", auth_method)
+            .field("timeout_seconds", timeout_seconds)
+            .field("tls_ca_cert_path", tls_ca_cert_path)
+            .field("tls_skip_verify", tls_skip_verify)
+            .field("role_relation_overrides", role_relation_overrides)
+            .field("root_email", root_email)
+            .field("default_org", default_org)
+            .field("include_meta_org", include_meta_org)
+            .field("auto_upgrade_model", auto_upgrade_model)
+            .field("is_cloud", is_cloud)
+            .field("root_emails", root_emails)
+            .field("trust_root_role_string", trust_root_role_string)
+            .field("max_concurrent_requests", max_concurrent_requests)
+            .field("init_write_concurrency", init_write_concurrency)
+            .field("max_retries", max_retries)
+            .field("retry_base_delay_ms", retry_base_delay_ms)
+            .field("cache", cache)
+            .finish()
+    }
 }
 
 fn default_store_name() -> String {
@@ -48,10 +207,34 @@ fn default_true() -> bool {
     true
 }
 
+fn default_root_email() -> String {
+    "root@visdata.com".to_string()
+}
+
+fn default_org() -> String {
+    "default".to_string()
+}
+
 fn default_timeout() -> u64 {
     30
 }
 
+fn default_max_concurrent_requests() -> usize {
+    50
+}
+
+fn default_init_write_concurrency() -> usize {
+    4
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_retry_base_delay_ms() -> u64 {
+    100
+}
+
 impl Default for OpenFGAConfig {
     fn default() -> Self {
         Self {
@@ -61,7 +244,24 @@ impl Default for OpenFGAConfig {
             store_name: default_store_name(),
             enabled: true,
             list_only_permitted: true,
+            api_token: None,
+            auth_method: OpenFGAAuthMethod::None,
             timeout_seconds: default_timeout(),
+            tls_ca_cert_path: None,
+            tls_skip_verify: false,
+            role_relation_overrides: HashMap::new(),
+            root_email: default_root_email(),
+            default_org: default_org(),
+            include_meta_org: true,
+            auto_upgrade_model: false,
+            is_cloud: false,
+            root_emails: HashSet::new(),
+            trust_root_role_string: false,
+            max_concurrent_requests: default_max_concurrent_requests(),
+            init_write_concurrency: default_init_write_concurrency(),
+            max_retries: default_max_retries(),
+            retry_base_delay_ms: default_retry_base_delay_ms(),
+            cache: CacheConfig::default(),
         }
     }
 }
@@ -90,4 +290,89 @@ impl OpenFGAConfig {
         self.model_id = Some(id.to_string());
         self
     }
+
+    /// Set the authentication method and token
+    pub fn with_auth(mut self, method: OpenFGAAuthMethod, token: Option<String>) -> Self {
+        self.auth_method = method;
+        self.api_token = token;
+        self
+    }
+
+    /// Set the TLS CA certificate path and whether verification is skipped
+    pub fn with_tls(mut self, ca_cert_path: Option<String>, skip_verify: bool) -> Self {
+        self.tls_ca_cert_path = ca_cert_path;
+        self.tls_skip_verify = skip_verify;
+        self
+    }
+
+    /// Set the role -> relation overrides
+    pub fn with_role_relation_overrides(mut self, overrides: HashMap<String, String>) -> Self {
+        self.role_relation_overrides = overrides
+            .into_iter()
+            .map(|(role, relation)| (role.to_lowercase(), relation))
+            .collect();
+        self
+    }
+
+    /// Set the root user and organizations written when a new store is bootstrapped
+    pub fn with_bootstrap(
+        mut self,
+        root_email: &str,
+        default_org: &str,
+        include_meta: bool,
+    ) -> Self {
+        self.root_email = root_email.to_string();
+        self.default_org = default_org.to_string();
+        self.include_meta_org = include_meta;
+        self
+    }
+
+    /// Set whether an outdated authorization model is upgraded on startup
+    pub fn with_auto_upgrade_model(mut self, enabled: bool) -> Self {
+        self.auto_upgrade_model = enabled;
+        self
+    }
+
+    /// Set whether this is a cloud deployment
+    pub fn with_cloud(mut self, is_cloud: bool) -> Self {
+        self.is_cloud = is_cloud;
+        self
+    }
+
+    /// Set the users that bypass all permission checks
+    pub fn with_root_emails(mut self, emails: HashSet<String>) -> Self {
+        self.root_emails = emails;
+        self
+    }
+
+    /// Set whether the "root" role string is trusted without verification
+    pub fn with_trust_root_role_string(mut self, trust: bool) -> Self {
+        self.trust_root_role_string = trust;
+        self
+    }
+
+    /// Set the maximum number of concurrent outbound requests
+    pub fn with_max_concurrent_requests(mut self, max: usize) -> Self {
+        self.max_concurrent_requests = max;
+        self
+    }
+
+    /// Set how many initial tuple batches are written concurrently
+    pub fn with_init_write_concurrency(mut self, concurrency: usize) -> Self {
+        self.init_write_concurrency = concurrency;
+        self
+    }
+
+    /// Set the retry policy
+    pub fn with_retry(mut self, max_retries: u32, base_delay_ms: u64) -> Self {
+        self.max_retries = max_retries;
+        self.retry_base_delay_ms = base_delay_ms;
+        self
+    }
+
+    /// Set the permission check cache configuration
+    pub fn with_cache(mut self, cache: CacheConfig) -> Self {
+        self.cache = cache;
+        self
+    }
 }
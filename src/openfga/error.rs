@@ -31,6 +31,21 @@ pub enum Error {
     /// Model not found or not created
     ModelNotFound,
 
+    /// Continuation token expired or became invalid during a paginated read
+    PaginationExpired(String),
+
+    /// OpenFGA rejected the request as invalid (400)
+    ValidationFailed(String),
+
+    /// OpenFGA could not find the store, model or endpoint (404)
+    NotFound(String),
+
+    /// OpenFGA reported a conflicting write (409)
+    Conflict(String),
+
+    /// OpenFGA rate limited the request (429)
+    RateLimited,
+
     /// Role not found
     RoleNotFound(String),
 
@@ -73,6 +88,11 @@ impl fmt::Display for Error {
             Error::Http(e) => write!(f, "HTTP error: {}", e),
             Error::StoreNotFound => write!(f, "OpenFGA store not found"),
             Error::ModelNotFound => write!(f, "OpenFGA authorization model not found"),
+            Error::PaginationExpired(msg) => write!(f, "Pagination expired: {}", msg),
+            Error::ValidationFailed(msg) => write!(f, "OpenFGA validation failed: {}", msg),
+            Error::NotFound(msg) => write!(f, "OpenFGA resource not found: {}", msg),
+            Error::Conflict(msg) => write!(f, "OpenFGA conflict: {}", msg),
+            Error::RateLimited => write!(f, "OpenFGA rate limit exceeded"),
             Error::RoleNotFound(name) => write!(f, "Role not found: {}", name),
             Error::GroupNotFound(name) => write!(f, "Group not found: {}", name),
             Error::UserNotFound(email) => write!(f, "User not found: {}", email),
@@ -88,6 +108,47 @@ impl fmt::Display for Error {
     }
 }
 
+/// OpenFGA JSON error body, e.g. `{"code":"validation_error","message":"..."}`
+#[derive(serde::Deserialize)]
+struct OpenFGAErrorBody {
+    #[serde(default)]
+    code: String,
+    #[serde(default)]
+    message: String,
+}
+
+impl Error {
+    /// Classify a non-2xx OpenFGA response
+    ///
+    /// Uses the `code` field of OpenFGA's JSON error body when present, and
+    /// the HTTP status otherwise. `context` names the failed operation, e.g.
+    /// "Check failed".
+    pub fn from_openfga_response(context: &str, status: u16, body: &str) -> Self {
+        let parsed: Option<OpenFGAErrorBody> = serde_json::from_str(body).ok();
+        let (code, message) = match &parsed {
+            Some(err) if !err.code.is_empty() => (err.code.as_str(), err.message.as_str()),
+            _ => ("", body),
+        };
+        let detail = if code.is_empty() {
+            format!("{}: {}", context, message)
+        } else {
+            format!("{}: {}: {}", context, code, message)
+        };
+
+        if status == 429 || code == "rate_limit_exceeded" {
+            Error::RateLimited
+        } else if status == 404 || code.ends_with("_not_found") || code == "undefined_endpoint" {
+            Error::NotFound(detail)
+        } else if status == 409 || code == "aborted" {
+            Error::Conflict(detail)
+        } else if status == 400 || status == 422 || code == "validation_error" {
+            Error::ValidationFailed(detail)
+        } else {
+            Error::OpenFGA(format!("{}: {} - {}", context, status, body))
+        }
+    }
+}
+
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
@@ -103,6 +164,12 @@ impl From<reqwest::Error> for Error {
     }
 }
 
+impl From<crate::common::tls::TlsError> for Error {
+    fn from(err: crate::common::tls::TlsError) -> Self {
+        Error::Config(err.to_string())
+    }
+}
+
 impl From<serde_json::Error> for Error {
     fn from(err: serde_json::Error) -> Self {
         Error::Serialization(err.to_string())
@@ -123,14 +190,23 @@ impl ResponseError for Error {
             Error::PermissionDenied(_) => HttpResponse::Forbidden().json(serde_json::json!({
                 "message": self.to_string()
             })),
-            Error::DuplicateEntry(_) => HttpResponse::Conflict().json(serde_json::json!({
-                "message": self.to_string()
-            })),
-            Error::InvalidPermission(_) | Error::InvalidResourceType(_) | Error::Validation(_) => {
-                HttpResponse::BadRequest().json(serde_json::json!({
+            Error::DuplicateEntry(_) | Error::Conflict(_) => {
+                HttpResponse::Conflict().json(serde_json::json!({
                     "message": self.to_string()
                 }))
             }
+            Error::InvalidPermission(_)
+            | Error::InvalidResourceType(_)
+            | Error::Validation(_)
+            | Error::ValidationFailed(_) => HttpResponse::BadRequest().json(serde_json::json!({
+                "message": self.to_string()
+            })),
+            Error::NotFound(_) => HttpResponse::NotFound().json(serde_json::json!({
+                "message": self.to_string()
+            })),
+            Error::RateLimited => HttpResponse::TooManyRequests().json(serde_json::json!({
+                "message": self.to_string()
+            })),
             _ => HttpResponse::InternalServerError().json(serde_json::json!({
                 "message": self.to_string()
             })),
@@ -171,6 +247,12 @@ mod tests {
         assert_eq!(format!("{}", err), "OpenFGA authorization model not found");
     }
 
+    #[test]
+    fn test_error_display_pagination_expired() {
+        let err = Error::PaginationExpired("invalid continuation token".to_string());
+        assert_eq!(format!("{}", err), "Pagination expired: invalid continuation token");
+    }
+
     #[test]
     fn test_error_display_role_not_found() {
         let err = Error::RoleNotFound("custom_role".to_string());
@@ -339,6 +421,13 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
 
+    #[test]
+    fn test_error_response_pagination_expired() {
+        let err = Error::PaginationExpired("expired".to_string());
+        let resp = err.error_response();
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
     #[test]
     fn test_error_response_internal() {
         let err = Error::Internal("unexpected".to_string());
@@ -346,6 +435,52 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
     }
 
+    #[test]
+    fn test_error_response_openfga_variants() {
+        let cases = [
+            (Error::ValidationFailed("bad".to_string()), StatusCode::BAD_REQUEST),
+            (Error::NotFound("store".to_string()), StatusCode::NOT_FOUND),
+            (Error::Conflict("write".to_string()), StatusCode::CONFLICT),
+            (Error::RateLimited, StatusCode::TOO_MANY_REQUESTS),
+        ];
+        for (err, status) in cases {
+            assert_eq!(err.error_response().status(), status, "{:?}", err);
+        }
+    }
+
+    // ========================================================================
+    // OpenFGA Response Classification Tests
+    // ========================================================================
+
+    #[test]
+    fn test_from_openfga_response() {
+        let body = r#"{"code":"validation_error","message":"invalid tuple"}"#;
+        match Error::from_openfga_response("Write failed", 400, body) {
+            Error::ValidationFailed(msg) => {
+                assert_eq!(msg, "Write failed: validation_error: invalid tuple")
+            }
+            other => panic!("Expected ValidationFailed, got {:?}", other),
+        }
+
+        let body = r#"{"code":"store_id_not_found","message":"store not found"}"#;
+        assert!(matches!(
+            Error::from_openfga_response("Check failed", 400, body),
+            Error::NotFound(_)
+        ));
+        assert!(matches!(
+            Error::from_openfga_response("Write failed", 409, "conflict"),
+            Error::Conflict(msg) if msg == "Write failed: conflict"
+        ));
+        assert!(matches!(
+            Error::from_openfga_response("Check failed", 429, ""),
+            Error::RateLimited
+        ));
+        assert!(matches!(
+            Error::from_openfga_response("Check failed", 500, "boom"),
+            Error::OpenFGA(msg) if msg == "Check failed: 500 - boom"
+        ));
+    }
+
     // ========================================================================
     // From Trait Tests
     // ========================================================================
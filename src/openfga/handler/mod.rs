@@ -0,0 +1,12 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! RBAC HTTP handlers
+
+pub mod resources;
+
+pub use resources::*;
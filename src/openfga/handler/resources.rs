@@ -0,0 +1,29 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Resource metadata HTTP handlers
+
+use actix_web::{get, web, HttpResponse};
+
+use crate::Visdata;
+use super::super::error::Result;
+use super::super::meta::mapping;
+
+/// GET /{org_id}/rbac/resources - Get the resource tree for the permissions UI
+#[get("/{org_id}/rbac/resources")]
+pub async fn list_resources(_path: web::Path<String>) -> Result<HttpResponse> {
+    let is_cloud = Visdata::try_global()
+        .map(|v| v.openfga_config().is_cloud)
+        .unwrap_or(false);
+
+    let resources = mapping::get_resource_tree(is_cloud);
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "code": 200,
+        "data": resources
+    })))
+}
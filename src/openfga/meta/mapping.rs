@@ -11,12 +11,15 @@
 //! for OpenFGA resource management.
 
 use std::collections::{HashMap, HashSet};
-use std::sync::LazyLock;
+use std::sync::{LazyLock, Mutex, PoisonError, RwLock};
 
 use serde::{Deserialize, Serialize};
 
+use crate::openfga::error::{Error, Result};
+use crate::openfga::model::schema;
+
 /// Resource definition for OpenFGA model
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Resource {
     /// Resource key (e.g., "logs", "dashboard")
     pub key: String,
@@ -29,13 +32,16 @@ pub struct Resource {
     /// Whether this resource is visible in UI
     pub visible: bool,
     /// Whether this is a top-level resource
+    #[serde(default)]
     pub top_level: bool,
     /// Whether this resource can have individual entities
+    #[serde(default)]
     pub has_entities: bool,
 }
 
 impl Resource {
-    fn new(
+    /// Create a resource; `top_level` is set when there is no parent
+    pub fn new(
         key: &str,
         display_name: &str,
         parent: Option<&str>,
@@ -127,20 +133,143 @@ pub static NON_CLOUD_RESOURCE_KEYS: LazyLock<HashSet<&'static str>> = LazyLock::
     s
 });
 
+/// Legacy resource keys and the resource type each one now refers to
+///
+/// These are not types in the authorization model, so they are not valid
+/// resource types; use `resolve_resource_key` to map them.
+pub const LEGACY_RESOURCE_ALIASES: &[(&str, &str)] = &[
+    ("templates", "template"),
+    ("functions", "function"),
+    ("reports", "report"),
+    ("destinations", "destination"),
+    ("alert_folders", "afolder"),
+    ("serviceaccounts", "service_accounts"),
+    ("actionscripts", "action_scripts"),
+    ("cipherkeys", "cipher_keys"),
+];
+
+/// Resource types registered at runtime, layered over `OFGA_MODELS`
+///
+/// Entries are interned (see `intern_resource`) so lookups can hand out
+/// `'static` references like the built-in map.
+static REGISTERED_RESOURCES: LazyLock<RwLock<HashMap<String, &'static Resource>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Every resource definition leaked by `register_resource`
+static INTERNED_RESOURCES: LazyLock<Mutex<Vec<&'static Resource>>> =
+    LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// Get a `'static` copy of a resource definition, leaking it only once
+///
+/// Registering a definition seen before (e.g. again after a rolled back
+/// `register_resource_for_org`) reuses its copy, so leaked memory grows with
+/// the number of distinct definitions rather than with registrations.
+fn intern_resource(resource: Resource) -> &'static Resource {
+    let mut interned = INTERNED_RESOURCES.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(&existing) = interned.iter().find(|r| ***r == resource) {
+        return existing;
+    }
+    let leaked: &'static Resource = Box::leak(Box::new(resource));
+    interned.push(leaked);
+    leaked
+}
+
+/// Register a custom resource type (e.g. a plugin's "workflow")
+///
+/// The type must also exist in the deployed authorization model for checks
+/// on it to succeed. Keys colliding with a built-in or already registered
+/// type are rejected, as are keys that are not valid OpenFGA type names and
+/// unknown parents. `top_level` is derived from `parent`.
+pub fn register_resource(mut resource: Resource) -> Result<()> {
+    let key = resource.key.as_str();
+    let valid_char = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+    if key.is_empty() || !key.chars().all(valid_char) {
+        return Err(Error::InvalidResourceType(format!(
+            "invalid resource type key '{}'",
+            key
+        )));
+    }
+    let is_alias = LEGACY_RESOURCE_ALIASES.iter().any(|(alias, _)| *alias == key);
+    if OFGA_MODELS.contains_key(key) || is_alias {
+        return Err(Error::DuplicateEntry(format!(
+            "resource type '{}' collides with a built-in type",
+            key
+        )));
+    }
+    let unknown_parent = resource.parent.as_deref().filter(|p| get_resource(p).is_none());
+    if let Some(parent) = unknown_parent {
+        return Err(Error::InvalidResourceType(format!(
+            "unknown parent '{}' for resource type '{}'",
+            parent, key
+        )));
+    }
+    resource.top_level = resource.parent.is_none();
+
+    let mut registered = REGISTERED_RESOURCES.write().unwrap_or_else(PoisonError::into_inner);
+    if registered.contains_key(&resource.key) {
+        return Err(Error::DuplicateEntry(format!(
+            "resource type '{}' is already registered",
+            resource.key
+        )));
+    }
+    registered.insert(resource.key.clone(), intern_resource(resource));
+    Ok(())
+}
+
+/// Remove a registered resource type; returns whether it was registered
+///
+/// Built-in types cannot be removed.
+pub fn unregister_resource(key: &str) -> bool {
+    REGISTERED_RESOURCES
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .remove(key)
+        .is_some()
+}
+
+/// Get a registered (non built-in) resource type
+fn get_registered_resource(key: &str) -> Option<&'static Resource> {
+    REGISTERED_RESOURCES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(key)
+        .copied()
+}
+
+/// All built-in and registered resource types, in no particular order
+fn all_resources() -> Vec<&'static Resource> {
+    let registered = REGISTERED_RESOURCES.read().unwrap_or_else(PoisonError::into_inner);
+    OFGA_MODELS.values().chain(registered.values().copied()).collect()
+}
+
+/// Resolve a resource key or legacy alias to the resource type key
+pub fn resolve_resource_key(key: &str) -> Option<&'static str> {
+    if let Some((&key, _)) = OFGA_MODELS.get_key_value(key) {
+        return Some(key);
+    }
+    if let Some(resource) = get_registered_resource(key) {
+        return Some(resource.key.as_str());
+    }
+    LEGACY_RESOURCE_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == key)
+        .map(|(_, key)| *key)
+}
+
 /// Get resource by key
 pub fn get_resource(key: &str) -> Option<&'static Resource> {
-    OFGA_MODELS.get(key)
+    OFGA_MODELS.get(key).or_else(|| get_registered_resource(key))
 }
 
 /// Check if a resource type exists
 pub fn is_valid_resource_type(key: &str) -> bool {
-    OFGA_MODELS.contains_key(key)
+    get_resource(key).is_some()
 }
 
 /// Get all visible resource types
 pub fn get_visible_resources() -> Vec<&'static Resource> {
-    let mut resources: Vec<_> = OFGA_MODELS
-        .values()
+    let mut resources: Vec<_> = all_resources()
+        .into_iter()
         .filter(|r| r.visible)
         .collect();
     resources.sort_by_key(|r| r.order);
@@ -149,8 +278,8 @@ pub fn get_visible_resources() -> Vec<&'static Resource> {
 
 /// Get all top-level resource types
 pub fn get_top_level_resources() -> Vec<&'static Resource> {
-    let mut resources: Vec<_> = OFGA_MODELS
-        .values()
+    let mut resources: Vec<_> = all_resources()
+        .into_iter()
         .filter(|r| r.top_level && r.visible)
         .collect();
     resources.sort_by_key(|r| r.order);
@@ -159,14 +288,108 @@ pub fn get_top_level_resources() -> Vec<&'static Resource> {
 
 /// Get child resources for a parent type
 pub fn get_child_resources(parent_key: &str) -> Vec<&'static Resource> {
-    let mut resources: Vec<_> = OFGA_MODELS
-        .values()
+    let mut resources: Vec<_> = all_resources()
+        .into_iter()
         .filter(|r| r.parent.as_deref() == Some(parent_key))
         .collect();
     resources.sort_by_key(|r| r.order);
     resources
 }
 
+/// Get the chain of resources from the top level down to `key`
+///
+/// Follows `parent` links, so `get_resource_path("logs")` is `[stream, logs]`.
+/// Returns an empty list for unknown keys.
+pub fn get_resource_path(key: &str) -> Vec<&'static Resource> {
+    let mut path = Vec::new();
+    let mut current = get_resource(key);
+    while let Some(resource) = current {
+        // Guard against a parent cycle among registered types
+        if path.iter().any(|r: &&Resource| r.key == resource.key) {
+            break;
+        }
+        path.push(resource);
+        current = resource.parent.as_deref().and_then(get_resource);
+    }
+    path.reverse();
+    path
+}
+
+/// Get resource by display name (case-insensitive, e.g. "Dashboards")
+pub fn get_resource_by_display_name(name: &str) -> Option<&'static Resource> {
+    all_resources()
+        .into_iter()
+        .find(|r| r.display_name.eq_ignore_ascii_case(name))
+}
+
+/// Resource tree node for the permissions UI
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceNode {
+    /// Resource metadata (key, display_name, order, ...)
+    #[serde(flatten)]
+    pub resource: Resource,
+    /// Whether this resource contains child resource types
+    pub is_container: bool,
+    /// Permissions that can be granted on this type (e.g., "ALLOW_GET")
+    pub allowed_permissions: Vec<String>,
+    /// Child resource types
+    pub children: Vec<ResourceNode>,
+}
+
+/// Get the permission relations (ALLOW_*) defined on a resource type in the model
+pub fn get_allowed_permissions(key: &str) -> Vec<String> {
+    let mut permissions: Vec<String> = schema::get_type_relations(key)
+        .into_iter()
+        .filter(|r| r.starts_with("ALLOW_"))
+        .collect();
+    permissions.sort();
+    permissions
+}
+
+fn build_resource_node(resource: &Resource, is_cloud: bool) -> ResourceNode {
+    let children: Vec<ResourceNode> = get_child_resources(&resource.key)
+        .into_iter()
+        .filter(|r| r.visible && is_available(r, is_cloud))
+        .map(|r| build_resource_node(r, is_cloud))
+        .collect();
+
+    ResourceNode {
+        resource: resource.clone(),
+        is_container: !children.is_empty(),
+        allowed_permissions: get_allowed_permissions(&resource.key),
+        children,
+    }
+}
+
+fn is_available(resource: &Resource, is_cloud: bool) -> bool {
+    !is_cloud || !NON_CLOUD_RESOURCE_KEYS.contains(resource.key.as_str())
+}
+
+/// Get the full resource hierarchy for the permissions UI
+///
+/// Top-level resources are annotated with their visible children. Visible
+/// children of hidden parents (e.g., logs/metrics/traces under stream) are
+/// promoted to the top level so they remain grantable. Non-cloud resources
+/// are dropped when `is_cloud` is set.
+pub fn get_resource_tree(is_cloud: bool) -> Vec<ResourceNode> {
+    let mut roots: Vec<&'static Resource> = get_top_level_resources();
+    roots.extend(all_resources().into_iter().filter(|r| {
+        r.visible
+            && r
+                .parent
+                .as_deref()
+                .and_then(get_resource)
+                .is_some_and(|p| p.top_level && !p.visible)
+    }));
+    roots.sort_by_key(|r| r.order);
+
+    roots
+        .into_iter()
+        .filter(|r| is_available(r, is_cloud))
+        .map(|r| build_resource_node(r, is_cloud))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -217,10 +440,141 @@ mod tests {
         assert!(stream_children.iter().any(|r| r.key == "traces"));
     }
 
+    #[test]
+    fn test_get_resource_path() {
+        let keys = |key| get_resource_path(key).iter().map(|r| r.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(keys("logs"), vec!["stream", "logs"]);
+        assert_eq!(keys("dashboard"), vec!["dfolder", "dashboard"]);
+        assert_eq!(keys("user"), vec!["user"]);
+        assert!(keys("unknown").is_empty());
+    }
+
+    #[test]
+    fn test_get_resource_by_display_name() {
+        assert_eq!(get_resource_by_display_name("Dashboards").unwrap().key, "dashboard");
+        assert_eq!(get_resource_by_display_name("kv store").unwrap().key, "kv");
+        assert!(get_resource_by_display_name("Dashboard").is_none());
+    }
+
+    #[test]
+    fn test_resolve_resource_key() {
+        assert_eq!(resolve_resource_key("logs"), Some("logs"));
+        assert_eq!(resolve_resource_key("serviceaccounts"), Some("service_accounts"));
+        assert_eq!(resolve_resource_key("unknown"), None);
+        assert!(!is_valid_resource_type("serviceaccounts"));
+
+        for (alias, key) in LEGACY_RESOURCE_ALIASES {
+            assert!(!OFGA_MODELS.contains_key(alias));
+            assert!(OFGA_MODELS.contains_key(key), "alias {} points to unknown {}", alias, key);
+        }
+    }
+
+    fn custom_resource(key: &str, parent: Option<&str>) -> Resource {
+        Resource::new(key, "Workflows", parent, 100, true, true)
+    }
+
+    #[test]
+    fn test_register_resource() {
+        register_resource(custom_resource("test_workflow", None)).unwrap();
+        register_resource(custom_resource("test_workflow_step", Some("test_workflow"))).unwrap();
+
+        assert!(is_valid_resource_type("test_workflow"));
+        assert!(get_resource("test_workflow").unwrap().top_level);
+        assert!(get_visible_resources().iter().any(|r| r.key == "test_workflow"));
+        assert_eq!(get_child_resources("test_workflow")[0].key, "test_workflow_step");
+        assert_eq!(get_resource_path("test_workflow_step").len(), 2);
+        assert_eq!(resolve_resource_key("test_workflow"), Some("test_workflow"));
+
+        assert!(matches!(
+            register_resource(custom_resource("test_workflow", None)),
+            Err(Error::DuplicateEntry(_))
+        ));
+
+        assert!(unregister_resource("test_workflow_step"));
+        assert!(unregister_resource("test_workflow"));
+        assert!(!unregister_resource("test_workflow"));
+        assert!(!is_valid_resource_type("test_workflow"));
+    }
+
+    #[test]
+    fn test_reregister_resource_reuses_definition() {
+        register_resource(custom_resource("test_reregister", None)).unwrap();
+        let first: *const Resource = get_resource("test_reregister").unwrap();
+        assert!(unregister_resource("test_reregister"));
+
+        register_resource(custom_resource("test_reregister", None)).unwrap();
+        let second: *const Resource = get_resource("test_reregister").unwrap();
+        assert_eq!(first, second);
+        assert!(unregister_resource("test_reregister"));
+    }
+
+    #[test]
+    fn test_register_resource_rejects_invalid() {
+        for key in ["logs", "templates"] {
+            assert!(matches!(
+                register_resource(custom_resource(key, None)),
+                Err(Error::DuplicateEntry(_))
+            ));
+        }
+        for key in ["", "bad:key", "bad#key"] {
+            assert!(matches!(
+                register_resource(custom_resource(key, None)),
+                Err(Error::InvalidResourceType(_))
+            ));
+        }
+        assert!(matches!(
+            register_resource(custom_resource("test_orphan", Some("test_missing"))),
+            Err(Error::InvalidResourceType(_))
+        ));
+        assert!(!unregister_resource("logs"));
+        assert!(is_valid_resource_type("logs"));
+    }
+
     #[test]
     fn test_non_cloud_resources() {
         assert!(NON_CLOUD_RESOURCE_KEYS.contains("license"));
         assert!(NON_CLOUD_RESOURCE_KEYS.contains("cipher_keys"));
         assert!(!NON_CLOUD_RESOURCE_KEYS.contains("logs"));
     }
+
+    #[test]
+    fn test_get_resource_tree() {
+        let tree = get_resource_tree(false);
+
+        // Dashboard folders contain dashboards
+        let dfolder = tree.iter().find(|n| n.resource.key == "dfolder").unwrap();
+        assert!(dfolder.is_container);
+        assert!(dfolder.children.iter().any(|c| c.resource.key == "dashboard"));
+
+        // Stream children are promoted since stream itself is hidden
+        assert!(!tree.iter().any(|n| n.resource.key == "stream"));
+        assert!(tree.iter().any(|n| n.resource.key == "logs"));
+        // Children are not duplicated at the top level
+        assert!(!tree.iter().any(|n| n.resource.key == "dashboard"));
+
+        // Sorted by order
+        assert!(tree.windows(2).all(|w| w[0].resource.order <= w[1].resource.order));
+
+        let logs = tree.iter().find(|n| n.resource.key == "logs").unwrap();
+        assert!(logs.allowed_permissions.contains(&"ALLOW_GET".to_string()));
+    }
+
+    #[test]
+    fn test_get_resource_tree_cloud_filtering() {
+        assert!(get_resource_tree(false).iter().any(|n| n.resource.key == "license"));
+        let cloud = get_resource_tree(true);
+        assert!(!cloud.iter().any(|n| n.resource.key == "license"));
+        assert!(!cloud.iter().any(|n| n.resource.key == "cipher_keys"));
+    }
+
+    #[test]
+    fn test_resource_node_serialization() {
+        let tree = get_resource_tree(false);
+        let json = serde_json::to_value(&tree).unwrap();
+        let first = &json[0];
+        assert!(first.get("display_name").is_some());
+        assert!(first.get("key").is_some());
+        assert!(first.get("children").is_some());
+        assert!(first.get("is_container").is_some());
+    }
 }
@@ -0,0 +1,101 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Authorization metrics (through the `metrics` facade)
+//!
+//! Metrics go to whichever recorder the host app installs; nothing is
+//! recorded before that. Call `register()` once the recorder is installed to
+//! publish descriptions and units, and configure the exporter's check latency
+//! histogram with `CHECK_LATENCY_BUCKETS`, e.g. for Prometheus:
+//!
+//! ```ignore
+//! use visdata::openfga::metrics as authz_metrics;
+//!
+//! PrometheusBuilder::new()
+//!     .set_buckets_for_metric(
+//!         Matcher::Full(authz_metrics::CHECK_DURATION.to_string()),
+//!         authz_metrics::CHECK_LATENCY_BUCKETS,
+//!     )?
+//!     .install()?;
+//! authz_metrics::register();
+//! ```
+
+use std::sync::Once;
+use std::time::Duration;
+
+use metrics::{Unit, counter, describe_counter, describe_histogram, histogram};
+
+/// Permission checks decided, labeled by `result` ("allowed" or "denied")
+pub const CHECKS_TOTAL: &str = "visdata_authz_checks_total";
+
+/// Time to decide a permission check, in seconds
+pub const CHECK_DURATION: &str = "visdata_authz_check_duration_seconds";
+
+/// Permission cache lookups, labeled by `result` ("hit" or "miss")
+pub const CACHE_LOOKUPS_TOTAL: &str = "visdata_authz_cache_lookups_total";
+
+/// Failed OpenFGA requests, labeled by `context` (e.g. "Check failed")
+pub const OPENFGA_ERRORS_TOTAL: &str = "visdata_openfga_errors_total";
+
+/// Histogram buckets for `CHECK_DURATION`, in seconds
+///
+/// Cached checks finish in microseconds; checks against OpenFGA usually take
+/// a few milliseconds, so the buckets are densest between 1ms and 100ms.
+pub const CHECK_LATENCY_BUCKETS: &[f64] = &[
+    0.0001, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+];
+
+static REGISTER: Once = Once::new();
+
+/// Describe the authorization metrics to the installed recorder
+///
+/// Safe to call more than once; only the first call has an effect.
+pub fn register() {
+    REGISTER.call_once(|| {
+        describe_counter!(CHECKS_TOTAL, Unit::Count, "Permission checks decided");
+        describe_histogram!(CHECK_DURATION, Unit::Seconds, "Time to decide a permission check");
+        describe_counter!(CACHE_LOOKUPS_TOTAL, Unit::Count, "Permission cache lookups");
+        describe_counter!(OPENFGA_ERRORS_TOTAL, Unit::Count, "Failed OpenFGA requests");
+    });
+}
+
+/// Record a decided permission check and how long it took
+pub fn record_check(allowed: bool, elapsed: Duration) {
+    let result = if allowed { "allowed" } else { "denied" };
+    counter!(CHECKS_TOTAL, "result" => result).increment(1);
+    histogram!(CHECK_DURATION).record(elapsed.as_secs_f64());
+}
+
+/// Record a permission cache lookup
+pub fn record_cache_lookup(hit: bool) {
+    let result = if hit { "hit" } else { "miss" };
+    counter!(CACHE_LOOKUPS_TOTAL, "result" => result).increment(1);
+}
+
+/// Record a failed OpenFGA request
+pub fn record_openfga_error(context: &'static str) {
+    counter!(OPENFGA_ERRORS_TOTAL, "context" => context).increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_latency_buckets_ascending() {
+        assert!(CHECK_LATENCY_BUCKETS.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_record_without_recorder() {
+        register();
+        register();
+        record_check(true, Duration::from_millis(3));
+        record_cache_lookup(false);
+        record_openfga_error("Check failed");
+    }
+}
@@ -0,0 +1,289 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Actix permission guard
+//!
+//! The authentication layer stores an `AuthContext` in the request
+//! extensions. `RequirePermission` then runs `checker::authorize` for the
+//! request method and answers 403 on denial, 400 on an invalid request and
+//! 503 when OpenFGA could not be reached, instead of treating every error as
+//! a denial like `check_permissions`.
+//!
+//! ```ignore
+//! web::resource("/{org_id}/dashboards/{dashboard_id}")
+//!     .wrap(RequirePermission::for_path("dashboard", "dashboard_id"))
+//!     .route(web::get().to(get_dashboard))
+//! ```
+
+use std::future::{Ready, ready};
+use std::rc::Rc;
+
+use actix_web::body::EitherBody;
+use actix_web::dev::{Payload, Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+use actix_web::error::InternalError;
+use actix_web::{FromRequest, HttpMessage, HttpRequest, HttpResponse, ResponseError};
+use futures::future::LocalBoxFuture;
+
+use super::error::Error;
+use super::service::checker;
+
+/// Identity of the caller, as checked against OpenFGA
+///
+/// Inserted into the request extensions once the request is authenticated.
+/// Handlers can take it as an extractor; it is rejected with 401 if missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthContext {
+    /// User email
+    pub user_id: String,
+    /// Organization the request targets
+    pub org_id: String,
+    /// Organization role of the user (e.g. "root", "admin")
+    pub role: String,
+}
+
+impl AuthContext {
+    /// Create a new auth context
+    pub fn new(
+        user_id: impl Into<String>,
+        org_id: impl Into<String>,
+        role: impl Into<String>,
+    ) -> Self {
+        Self {
+            user_id: user_id.into(),
+            org_id: org_id.into(),
+            role: role.into(),
+        }
+    }
+}
+
+impl FromRequest for AuthContext {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let context = req.extensions().get::<AuthContext>().cloned();
+        ready(context.ok_or_else(|| {
+            InternalError::from_response("missing auth context", unauthenticated_response())
+                .into()
+        }))
+    }
+}
+
+/// Response for a request without an `AuthContext`
+fn unauthenticated_response() -> HttpResponse {
+    HttpResponse::Unauthorized().json(serde_json::json!({
+        "message": "Unauthorized access"
+    }))
+}
+
+/// Response for a failed `checker::authorize` call
+///
+/// Only errors reaching OpenFGA (transport failures, 5xx, rate limiting, a
+/// missing client, store or model) answer 503. Denials and objects OpenFGA
+/// does not know answer 403; invalid requests such as an unknown resource
+/// type keep their own status (400).
+pub fn guard_error_response(err: &Error) -> HttpResponse {
+    match err {
+        Error::Http(_)
+        | Error::OpenFGA(_)
+        | Error::RateLimited
+        | Error::NotInitialized(_)
+        | Error::StoreNotFound
+        | Error::ModelNotFound => HttpResponse::ServiceUnavailable().json(serde_json::json!({
+            "message": format!("Authorization unavailable: {}", err)
+        })),
+        Error::NotFound(_) => HttpResponse::Forbidden().json(serde_json::json!({
+            "message": format!("Permission denied: {}", err)
+        })),
+        _ => err.error_response(),
+    }
+}
+
+/// Resolves the "resource_type:entity_id" object a request acts on
+type ObjectResolver = dyn Fn(&ServiceRequest) -> Option<String>;
+
+/// Middleware requiring permission on the object a request acts on
+///
+/// The permission follows from the request method (see
+/// `Permission::from_method`). Requests the resolver returns `None` for
+/// pass through unchecked.
+#[derive(Clone)]
+pub struct RequirePermission {
+    resolver: Rc<ObjectResolver>,
+}
+
+impl RequirePermission {
+    /// Guard the object returned by `resolver`
+    pub fn new<F>(resolver: F) -> Self
+    where
+        F: Fn(&ServiceRequest) -> Option<String> + 'static,
+    {
+        Self {
+            resolver: Rc::new(resolver),
+        }
+    }
+
+    /// Guard "{resource_type}:{entity_id}", taking the entity from a path parameter
+    pub fn for_path(resource_type: &str, param: &str) -> Self {
+        let resource_type = resource_type.to_string();
+        let param = param.to_string();
+        Self::new(move |req| {
+            req.match_info()
+                .get(&param)
+                .map(|entity_id| format!("{}:{}", resource_type, entity_id))
+        })
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequirePermission
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Transform = RequirePermissionMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequirePermissionMiddleware {
+            service: Rc::new(service),
+            resolver: self.resolver.clone(),
+        }))
+    }
+}
+
+/// Service created by `RequirePermission`
+pub struct RequirePermissionMiddleware<S> {
+    service: Rc<S>,
+    resolver: Rc<ObjectResolver>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequirePermissionMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let object = (self.resolver)(&req);
+        let context = req.extensions().get::<AuthContext>().cloned();
+
+        Box::pin(async move {
+            let object = match object {
+                Some(object) => object,
+                None => return service.call(req).await.map(ServiceResponse::map_into_left_body),
+            };
+            let context = match context {
+                Some(context) => context,
+                None => {
+                    let response = unauthenticated_response();
+                    return Ok(req.into_response(response).map_into_right_body());
+                }
+            };
+
+            let method = req.method().as_str().to_string();
+            let result = checker::authorize(
+                &context.user_id,
+                &context.org_id,
+                &method,
+                &object,
+                &context.role,
+            )
+            .await;
+            match result {
+                Ok(()) => service.call(req).await.map(ServiceResponse::map_into_left_body),
+                Err(e) => {
+                    tracing::warn!(
+                        "[RBAC] Request guard rejected {} {}: {}",
+                        method, object, e
+                    );
+                    let response = guard_error_response(&e);
+                    Ok(req.into_response(response).map_into_right_body())
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use actix_web::test::{self, TestRequest};
+    use actix_web::{App, web};
+
+    #[test]
+    fn test_guard_error_response_status() {
+        let denied = Error::PermissionDenied("GET on dashboard:d1".to_string());
+        assert_eq!(guard_error_response(&denied).status(), StatusCode::FORBIDDEN);
+
+        let down = Error::OpenFGA("connection refused".to_string());
+        assert_eq!(guard_error_response(&down).status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            guard_error_response(&Error::RateLimited).status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+
+        // Bad input is the caller's fault, not an outage
+        let invalid = Error::InvalidResourceType("widgets".to_string());
+        assert_eq!(guard_error_response(&invalid).status(), StatusCode::BAD_REQUEST);
+        let invalid = Error::Validation("empty object".to_string());
+        assert_eq!(guard_error_response(&invalid).status(), StatusCode::BAD_REQUEST);
+        let unknown = Error::NotFound("Check failed: type_not_found".to_string());
+        assert_eq!(guard_error_response(&unknown).status(), StatusCode::FORBIDDEN);
+    }
+
+    #[actix_web::test]
+    async fn test_auth_context_extractor() {
+        let req = TestRequest::default().to_http_request();
+        assert!(AuthContext::extract(&req).await.is_err());
+
+        let context = AuthContext::new("user@example.com", "default", "admin");
+        req.extensions_mut().insert(context.clone());
+        assert_eq!(AuthContext::extract(&req).await.unwrap(), context);
+    }
+
+    #[actix_web::test]
+    async fn test_require_permission_without_context() {
+        let app = test::init_service(
+            App::new().service(
+                web::resource("/dashboards/{dashboard_id}")
+                    .wrap(RequirePermission::for_path("dashboard", "dashboard_id"))
+                    .to(HttpResponse::Ok),
+            ),
+        )
+        .await;
+
+        let req = TestRequest::get().uri("/dashboards/d1").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_web::test]
+    async fn test_require_permission_skips_unresolved_objects() {
+        let app = test::init_service(
+            App::new().service(
+                web::resource("/health")
+                    .wrap(RequirePermission::new(|_| None))
+                    .to(HttpResponse::Ok),
+            ),
+        )
+        .await;
+
+        let req = TestRequest::get().uri("/health").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+}
@@ -12,7 +12,10 @@
 //! ## Module Structure
 //!
 //! - `authorizer` - Permission checking API (is_allowed, roles, groups)
+//! - `handler` - HTTP handlers (resource tree)
 //! - `meta` - Resource mappings (OFGA_MODELS)
+//! - `metrics` - Authorization metrics
+//! - `middleware` - Actix permission guard
 //! - `model` - FGA schema and resource definitions
 //! - `service` - Internal service layer
 //! - `config` - OpenFGA configuration
@@ -23,7 +26,10 @@ pub mod authorizer;
 pub mod client;
 pub mod config;
 pub mod error;
+pub mod handler;
 pub mod meta;
+pub mod metrics;
+pub mod middleware;
 pub mod model;
 pub mod service;
 pub mod types;
@@ -33,7 +39,7 @@ pub mod types;
 // ============================================================================
 
 pub use client::OpenFGAClient;
-pub use config::OpenFGAConfig;
+pub use config::{OpenFGAAuthMethod, OpenFGAConfig};
 pub use error::{Error as RbacError, Result as RbacResult};
 
 // Re-export authorizer for compatibility with o2_openfga::authorizer
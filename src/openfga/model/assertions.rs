@@ -0,0 +1,225 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Expected behavior of the bundled authorization model
+//!
+//! Assertions are evaluated against a store holding the default initial
+//! tuples. The root user relies on those tuples; the admin, editor and viewer
+//! users and the custom role are supplied as contextual tuples, so running the
+//! assertions writes nothing to the store.
+
+use super::schema::{
+    DEFAULT_ORG, DEFAULT_ROOT_EMAIL, org_type, public_user, role_type, user_type,
+};
+use crate::openfga::types::{Assertion, TupleKey};
+
+const ADMIN_EMAIL: &str = "admin@example.com";
+const EDITOR_EMAIL: &str = "editor@example.com";
+const VIEWER_EMAIL: &str = "viewer@example.com";
+const ROLE_MEMBER_EMAIL: &str = "member@example.com";
+
+/// Custom role granting read access to all streams of the default org
+const STREAM_READER_ROLE: &str = "stream_reader";
+
+/// Custom role granting full access to all dashboards of the default org
+const DASHBOARD_ADMIN_ROLE: &str = "dashboard_admin";
+
+/// Tuples making `email` a member of the default org with `relation`
+fn org_member(email: &str, relation: &str) -> Vec<TupleKey> {
+    let org = org_type(DEFAULT_ORG);
+    vec![
+        TupleKey::new(user_type(email), relation, org.clone()),
+        TupleKey::new(user_type(email), "org_context", org),
+    ]
+}
+
+/// Tuples assigning `email` the stream reader role in the default org
+fn stream_reader(email: &str) -> Vec<TupleKey> {
+    let role = role_type(DEFAULT_ORG, STREAM_READER_ROLE);
+    vec![
+        TupleKey::new(user_type(email), "org_context", org_type(DEFAULT_ORG)),
+        TupleKey::new(org_type(DEFAULT_ORG), "owningOrg", role.clone()),
+        TupleKey::new(user_type(email), "assigned", role.clone()),
+        TupleKey::new(
+            format!("{}#has", role),
+            "ALLOW_GET",
+            format!("stream:_all_{}", DEFAULT_ORG),
+        ),
+    ]
+}
+
+/// Tuples assigning `email` the dashboard admin role in the default org
+fn dashboard_admin(email: &str) -> Vec<TupleKey> {
+    let role = role_type(DEFAULT_ORG, DASHBOARD_ADMIN_ROLE);
+    vec![
+        TupleKey::new(user_type(email), "org_context", org_type(DEFAULT_ORG)),
+        TupleKey::new(org_type(DEFAULT_ORG), "owningOrg", role.clone()),
+        TupleKey::new(user_type(email), "assigned", role.clone()),
+        TupleKey::new(
+            format!("{}#has", role),
+            "ALLOW_ALL",
+            format!("dashboard:_all_{}", DEFAULT_ORG),
+        ),
+    ]
+}
+
+/// Expected allow/deny results for root, admin, editor and viewer
+pub fn default_assertions() -> Vec<Assertion> {
+    let org = org_type(DEFAULT_ORG);
+    let streams = format!("stream:_all_{}", DEFAULT_ORG);
+    let dashboards = format!("dashboard:_all_{}", DEFAULT_ORG);
+    let roles = format!("role:_all_{}", DEFAULT_ORG);
+    let root = user_type(DEFAULT_ROOT_EMAIL);
+    let admin = user_type(ADMIN_EMAIL);
+    let editor = user_type(EDITOR_EMAIL);
+    let viewer = user_type(VIEWER_EMAIL);
+    let member = user_type(ROLE_MEMBER_EMAIL);
+    let nobody = user_type("nobody@example.com");
+
+    let mut assertions = Vec::new();
+
+    // Root administers the default org through the initial tuples
+    for relation in ["GET", "LIST", "POST", "PUT", "DELETE"] {
+        assertions.push(Assertion::allow(root.as_str(), relation, org.as_str()));
+    }
+
+    // Admins and editors manage the org
+    for (user, email, relation) in [
+        (&admin, ADMIN_EMAIL, "admin"),
+        (&editor, EDITOR_EMAIL, "editor"),
+    ] {
+        for action in ["GET", "LIST", "POST", "PUT", "DELETE"] {
+            assertions.push(
+                Assertion::allow(user.as_str(), action, org.as_str())
+                    .with_contextual_tuples(org_member(email, relation)),
+            );
+        }
+    }
+
+    // Viewers read the org and org-owned resources but change nothing
+    for action in ["GET", "LIST"] {
+        assertions.push(
+            Assertion::allow(viewer.as_str(), action, org.as_str())
+                .with_contextual_tuples(org_member(VIEWER_EMAIL, "viewer")),
+        );
+    }
+    assertions.push(
+        Assertion::allow(viewer.as_str(), "GET", roles.as_str())
+            .with_contextual_tuples(org_member(VIEWER_EMAIL, "viewer")),
+    );
+    for action in ["POST", "PUT", "DELETE"] {
+        assertions.push(
+            Assertion::deny(viewer.as_str(), action, org.as_str())
+                .with_contextual_tuples(org_member(VIEWER_EMAIL, "viewer")),
+        );
+    }
+    assertions.push(
+        Assertion::deny(viewer.as_str(), "PUT", roles.as_str())
+            .with_contextual_tuples(org_member(VIEWER_EMAIL, "viewer")),
+    );
+
+    // Org roles do not apply without the org context
+    assertions.push(
+        Assertion::deny(viewer.as_str(), "GET", org.as_str()).with_contextual_tuples(vec![
+            TupleKey::new(viewer.as_str(), "viewer", org.as_str()),
+        ]),
+    );
+
+    // Stream access comes only from custom roles
+    assertions.push(
+        Assertion::deny(admin.as_str(), "GET", streams.as_str())
+            .with_contextual_tuples(org_member(ADMIN_EMAIL, "admin")),
+    );
+    assertions.push(
+        Assertion::allow(member.as_str(), "GET", streams.as_str())
+            .with_contextual_tuples(stream_reader(ROLE_MEMBER_EMAIL)),
+    );
+    assertions.push(
+        Assertion::deny(member.as_str(), "PUT", streams.as_str())
+            .with_contextual_tuples(stream_reader(ROLE_MEMBER_EMAIL)),
+    );
+
+    // An ALLOW_ALL grant satisfies every method relation
+    for action in ["GET", "LIST", "POST", "PUT", "DELETE"] {
+        assertions.push(
+            Assertion::allow(member.as_str(), action, dashboards.as_str())
+                .with_contextual_tuples(dashboard_admin(ROLE_MEMBER_EMAIL)),
+        );
+    }
+
+    // Owners have full access to what they own, and only to that
+    let owned_dashboard = vec![TupleKey::new(member.as_str(), "owner", "dashboard:owned")];
+    for action in ["GET", "PUT", "DELETE"] {
+        assertions.push(
+            Assertion::allow(member.as_str(), action, "dashboard:owned")
+                .with_contextual_tuples(owned_dashboard.clone()),
+        );
+    }
+    assertions.push(
+        Assertion::deny(nobody.as_str(), "GET", "dashboard:owned")
+            .with_contextual_tuples(owned_dashboard.clone()),
+    );
+    assertions.push(
+        Assertion::deny(member.as_str(), "GET", "dashboard:other")
+            .with_contextual_tuples(owned_dashboard),
+    );
+
+    // Public resources are readable by anyone, but only as granted
+    let public_dashboard = vec![TupleKey::new(public_user(), "ALLOW_GET", "dashboard:shared")];
+    assertions.push(
+        Assertion::allow(nobody.as_str(), "GET", "dashboard:shared")
+            .with_contextual_tuples(public_dashboard.clone()),
+    );
+    assertions.push(
+        Assertion::deny(nobody.as_str(), "PUT", "dashboard:shared")
+            .with_contextual_tuples(public_dashboard),
+    );
+
+    // Unknown users get nothing
+    for object in [&org, &streams, &roles] {
+        assertions.push(Assertion::deny(nobody.as_str(), "GET", object.as_str()));
+    }
+
+    assertions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::openfga::model::schema::get_authorization_model_json;
+    use serde_json::Value;
+
+    /// Whether the bundled model defines `relation` on the type of `object`
+    fn has_relation(model: &Value, object: &str, relation: &str) -> bool {
+        let object_type = object.split(':').next().unwrap();
+        model["type_definitions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|def| def["type"] == object_type)
+            .is_some_and(|def| def["relations"].get(relation).is_some())
+    }
+
+    #[test]
+    fn test_default_assertions_match_model() {
+        let model: Value = serde_json::from_str(get_authorization_model_json()).unwrap();
+        let assertions = default_assertions();
+        assert!(assertions.iter().any(|a| a.expectation));
+        assert!(assertions.iter().any(|a| !a.expectation));
+
+        for assertion in &assertions {
+            let tuples = std::iter::once(&assertion.tuple_key).chain(&assertion.contextual_tuples);
+            for tuple in tuples {
+                assert!(
+                    has_relation(&model, &tuple.object, &tuple.relation),
+                    "unknown relation in {:?}",
+                    tuple
+                );
+            }
+        }
+    }
+}
@@ -7,8 +7,10 @@
 
 //! Authorization model definitions
 
+pub mod assertions;
 pub mod resources;
 pub mod schema;
 
+pub use assertions::default_assertions;
 pub use resources::{RESOURCE_TYPES, get_resource, get_all_resources};
-pub use schema::{get_authorization_model_json, get_initial_tuples};
+pub use schema::{get_authorization_model_json, get_initial_tuples, get_initial_tuples_default};
@@ -5,174 +5,22 @@
 // the Free Software Foundation, either version 3 of the License, or
 // (at your option) any later version.
 
-//! Resource type definitions (compatible with OFGA_MODELS)
+//! Resource type helpers
 //!
-//! This module defines all 30+ resource types from store.yaml.
+//! Resource types are defined once in `meta::mapping::OFGA_MODELS`; this
+//! module re-exports them as `RESOURCE_TYPES` along with object parsing
+//! helpers.
 
-use once_cell::sync::Lazy;
-use std::collections::HashMap;
+pub use crate::openfga::meta::mapping::{
+    OFGA_MODELS as RESOURCE_TYPES, get_child_resources, get_resource, get_top_level_resources,
+    is_valid_resource_type, resolve_resource_key,
+};
 
-use super::super::types::Resource;
-
-/// Helper to create a resource with default values
-fn resource(
-    key: &str,
-    label: &str,
-    parent: Option<&str>,
-    order: i32,
-    visible: bool,
-    has_entities: bool,
-) -> Resource {
-    let top_level = parent.is_none();
-    Resource {
-        key: key.to_string(),
-        label: label.to_string(),
-        parent: parent.map(|s| s.to_string()),
-        order,
-        visible,
-        top_level,
-        has_entities,
-    }
-}
-
-/// Resource types mapping (compatible with o2_openfga::meta::mapping::OFGA_MODELS)
-///
-/// This includes all 30+ resource types defined in store.yaml:
-/// - Core types: user, group, role, org
-/// - Stream hierarchy: stream, logs, metrics, traces, metadata, index
-/// - Dashboard hierarchy: dfolder, dashboard, template, savedviews
-/// - Alert hierarchy: afolder, alert, destination
-/// - Report hierarchy: rfolder, report
-/// - Functions: function, pipeline
-/// - System: settings, kv, enrichment_table, summary, syslog-route
-/// - Security: passcode, rumtoken, service_accounts, cipher_keys
-/// - Other: search_jobs, action_scripts, ratelimit, ai, re_patterns, license
-pub static RESOURCE_TYPES: Lazy<HashMap<&'static str, Resource>> = Lazy::new(|| {
-    let mut m = HashMap::new();
-
-    // ========================================================================
-    // Core types
-    // ========================================================================
-    m.insert("user", resource("user", "Users", None, 1, true, true));
-    m.insert("group", resource("group", "Groups", None, 2, true, true));
-    m.insert("role", resource("role", "Roles", None, 3, true, true));
-    m.insert("org", resource("org", "Organizations", None, 4, true, false));
-
-    // ========================================================================
-    // Stream hierarchy (data streams)
-    // ========================================================================
-    m.insert("stream", resource("stream", "Streams", None, 10, false, true));
-    m.insert("logs", resource("logs", "Logs", Some("stream"), 11, true, true));
-    m.insert("metrics", resource("metrics", "Metrics", Some("stream"), 12, true, true));
-    m.insert("traces", resource("traces", "Traces", Some("stream"), 13, true, true));
-    m.insert("metadata", resource("metadata", "Metadata", Some("stream"), 14, false, true));
-    m.insert("index", resource("index", "Index", Some("stream"), 15, true, true));
-
-    // ========================================================================
-    // Dashboard hierarchy
-    // ========================================================================
-    m.insert("dfolder", resource("dfolder", "Dashboard Folders", None, 20, true, true));
-    m.insert("dashboard", resource("dashboard", "Dashboards", Some("dfolder"), 21, true, true));
-    m.insert("template", resource("template", "Templates", None, 22, true, true));
-    m.insert("savedviews", resource("savedviews", "Saved Views", None, 23, true, true));
-
-    // ========================================================================
-    // Alert hierarchy
-    // ========================================================================
-    m.insert("afolder", resource("afolder", "Alert Folders", None, 30, true, true));
-    m.insert("alert", resource("alert", "Alerts", Some("afolder"), 31, true, true));
-    m.insert("destination", resource("destination", "Destinations", None, 32, true, true));
-
-    // ========================================================================
-    // Report hierarchy
-    // ========================================================================
-    m.insert("rfolder", resource("rfolder", "Report Folders", None, 40, true, true));
-    m.insert("report", resource("report", "Reports", Some("rfolder"), 41, true, true));
-
-    // ========================================================================
-    // Functions and pipelines
-    // ========================================================================
-    m.insert("function", resource("function", "Functions", None, 50, true, true));
-    m.insert("pipeline", resource("pipeline", "Pipelines", None, 51, true, true));
-
-    // ========================================================================
-    // System resources
-    // ========================================================================
-    m.insert("settings", resource("settings", "Settings", None, 60, true, false));
-    m.insert("kv", resource("kv", "KV Store", None, 61, true, true));
-    m.insert("enrichment_table", resource("enrichment_table", "Enrichment Tables", None, 62, true, true));
-    m.insert("summary", resource("summary", "Summary", None, 63, true, true));
-    m.insert("syslog-route", resource("syslog-route", "Syslog Routes", None, 64, true, true));
-
-    // ========================================================================
-    // Security and authentication
-    // ========================================================================
-    m.insert("passcode", resource("passcode", "Passcodes", None, 70, true, true));
-    m.insert("rumtoken", resource("rumtoken", "RUM Tokens", None, 71, true, true));
-    m.insert("service_accounts", resource("service_accounts", "Service Accounts", None, 72, true, true));
-    m.insert("cipher_keys", resource("cipher_keys", "Cipher Keys", None, 73, true, true));
-
-    // ========================================================================
-    // Other resources
-    // ========================================================================
-    m.insert("search_jobs", resource("search_jobs", "Search Jobs", None, 80, true, true));
-    m.insert("action_scripts", resource("action_scripts", "Action Scripts", None, 81, true, true));
-    m.insert("ratelimit", resource("ratelimit", "Rate Limits", None, 82, true, true));
-    m.insert("ai", resource("ai", "AI", None, 83, true, false));
-    m.insert("re_patterns", resource("re_patterns", "Regex Patterns", None, 84, true, true));
-    m.insert("license", resource("license", "License", None, 90, true, false));
-
-    // ========================================================================
-    // Legacy/backward compatibility aliases
-    // ========================================================================
-    // These provide compatibility with older code that uses different names
-    m.insert("templates", resource("templates", "Templates", None, 22, true, true));
-    m.insert("functions", resource("functions", "Functions", None, 50, true, true));
-    m.insert("reports", resource("reports", "Reports", None, 41, true, true));
-    m.insert("destinations", resource("destinations", "Destinations", None, 32, true, true));
-    m.insert("alert_folders", resource("alert_folders", "Alert Folders", None, 30, true, true));
-    m.insert("serviceaccounts", resource("serviceaccounts", "Service Accounts", None, 72, true, true));
-    m.insert("actionscripts", resource("actionscripts", "Action Scripts", None, 81, true, true));
-    m.insert("cipherkeys", resource("cipherkeys", "Cipher Keys", None, 73, true, true));
-
-    m
-});
-
-/// Get resource by key
-pub fn get_resource(key: &str) -> Option<&Resource> {
-    RESOURCE_TYPES.get(key)
-}
+use crate::openfga::meta::mapping::{Resource, get_visible_resources};
 
 /// Get all visible resources sorted by order
 pub fn get_all_resources() -> Vec<&'static Resource> {
-    let mut resources: Vec<_> = RESOURCE_TYPES.values().filter(|r| r.visible).collect();
-    resources.sort_by_key(|r| r.order);
-    resources
-}
-
-/// Get top-level resources (no parent)
-pub fn get_top_level_resources() -> Vec<&'static Resource> {
-    let mut resources: Vec<_> = RESOURCE_TYPES
-        .values()
-        .filter(|r| r.top_level && r.visible)
-        .collect();
-    resources.sort_by_key(|r| r.order);
-    resources
-}
-
-/// Get child resources for a parent type
-pub fn get_child_resources(parent_key: &str) -> Vec<&'static Resource> {
-    let mut resources: Vec<_> = RESOURCE_TYPES
-        .values()
-        .filter(|r| r.parent.as_deref() == Some(parent_key))
-        .collect();
-    resources.sort_by_key(|r| r.order);
-    resources
-}
-
-/// Check if a resource type is valid
-pub fn is_valid_resource_type(key: &str) -> bool {
-    RESOURCE_TYPES.contains_key(key)
+    get_visible_resources()
 }
 
 /// Get the OpenFGA type name for a resource
@@ -187,6 +35,35 @@ pub fn parse_object(object: &str) -> Option<(&str, &str)> {
     object.split_once(':')
 }
 
+/// Get the parent resource type (e.g. "dfolder" for "dashboard")
+pub fn get_parent_type(resource_type: &str) -> Option<&'static str> {
+    RESOURCE_TYPES.get(resource_type).and_then(|r| r.parent.as_deref())
+}
+
+/// Split a parent-scoped entity ID ("folder/entity") for types with a parent
+///
+/// Returns `(Some(folder), entity)` for e.g. dashboard "myfolder/mydash",
+/// `(None, entity_id)` when there is no folder prefix or the type has no
+/// parent, and `None` when the scoped ID is malformed (empty folder or
+/// entity, or more than one '/').
+pub fn split_parent_scope<'a>(
+    resource_type: &str,
+    entity_id: &'a str,
+) -> Option<(Option<&'a str>, &'a str)> {
+    if get_parent_type(resource_type).is_none() {
+        return Some((None, entity_id));
+    }
+    match entity_id.split_once('/') {
+        None => Some((None, entity_id)),
+        Some((folder, entity))
+            if !folder.is_empty() && !entity.is_empty() && !entity.contains('/') =>
+        {
+            Some((Some(folder), entity))
+        }
+        Some(_) => None,
+    }
+}
+
 /// Check if entity is an "all org" wildcard
 pub fn is_all_org_entity(entity: &str, org_id: &str) -> bool {
     entity == format!("_all_{}", org_id) || entity == "_all" || entity.starts_with("_all")
@@ -195,6 +72,7 @@ pub fn is_all_org_entity(entity: &str, org_id: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::openfga::meta::mapping::OFGA_MODELS;
 
     #[test]
     fn test_resource_types_count() {
@@ -202,6 +80,26 @@ mod tests {
         assert!(RESOURCE_TYPES.len() >= 30);
     }
 
+    #[test]
+    fn test_resource_types_match_model() {
+        let mut keys: Vec<&str> = RESOURCE_TYPES.keys().copied().collect();
+        let mut mapping_keys: Vec<&str> = OFGA_MODELS.keys().copied().collect();
+        keys.sort();
+        mapping_keys.sort();
+        assert_eq!(keys, mapping_keys);
+
+        // Every resource type must be a type in the authorization model
+        for key in keys {
+            assert!(
+                !super::super::schema::get_type_relations(key).is_empty(),
+                "{} missing from the model",
+                key
+            );
+            assert_eq!(get_resource(key).unwrap().key, key);
+            assert!(is_valid_resource_type(key));
+        }
+    }
+
     #[test]
     fn test_core_types_exist() {
         assert!(RESOURCE_TYPES.contains_key("user"));
@@ -251,6 +149,24 @@ mod tests {
         assert_eq!(report.parent.as_deref(), Some("rfolder"));
     }
 
+    #[test]
+    fn test_split_parent_scope() {
+        assert_eq!(get_parent_type("dashboard"), Some("dfolder"));
+        assert_eq!(get_parent_type("dfolder"), None);
+
+        assert_eq!(
+            split_parent_scope("dashboard", "myfolder/mydash"),
+            Some((Some("myfolder"), "mydash"))
+        );
+        assert_eq!(split_parent_scope("alert", "a1"), Some((None, "a1")));
+        assert_eq!(split_parent_scope("dashboard", "/mydash"), None);
+        assert_eq!(split_parent_scope("dashboard", "myfolder/"), None);
+        assert_eq!(split_parent_scope("report", "a/b/c"), None);
+
+        // Types without a parent keep the entity ID as-is
+        assert_eq!(split_parent_scope("function", "a/b"), Some((None, "a/b")));
+    }
+
     #[test]
     fn test_get_all_resources() {
         let resources = get_all_resources();
@@ -10,6 +10,11 @@
 //! This model is equivalent to the DSL in visdata_deploy/openfga/store.yaml
 //! and defines the complete RBAC permission system.
 
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use serde_json::Value;
+
 use super::super::types::TupleKey;
 
 /// Get the OpenFGA authorization model in JSON format
@@ -31,137 +36,229 @@ pub fn get_authorization_model_json() -> &'static str {
     include_str!("authorization_model.json")
 }
 
+/// Parsed authorization model (cached for relation lookups)
+static PARSED_MODEL: LazyLock<serde_json::Value> = LazyLock::new(|| {
+    serde_json::from_str(get_authorization_model_json()).unwrap_or_default()
+});
+
+/// Get the relation names defined on a type in the authorization model
+///
+/// Returns an empty list if the type is not defined in the model.
+pub fn get_type_relations(type_name: &str) -> Vec<String> {
+    PARSED_MODEL["type_definitions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|def| def["type"].as_str() == Some(type_name))
+        .and_then(|def| def["relations"].as_object())
+        .map(|relations| relations.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Type-level differences between two authorization models
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelDiff {
+    /// Types only in the desired model
+    pub added: Vec<String>,
+    /// Types only in the current model
+    pub removed: Vec<String>,
+    /// Types whose definitions differ
+    pub changed: Vec<String>,
+}
+
+impl ModelDiff {
+    /// Whether the models define the same types identically
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare the `type_definitions` of two models, ignoring ordering
+///
+/// Definitions are compared semantically: array order, and fields that are
+/// null or empty (as OpenFGA returns for unset metadata), are ignored.
+pub fn diff_type_definitions(current: &Value, desired: &Value) -> ModelDiff {
+    let current = type_definitions(current);
+    let desired = type_definitions(desired);
+
+    let mut diff = ModelDiff::default();
+    for (name, definition) in &desired {
+        match current.get(name) {
+            None => diff.added.push(name.clone()),
+            Some(existing) if existing != definition => diff.changed.push(name.clone()),
+            Some(_) => {}
+        }
+    }
+    diff.removed = current.keys().filter(|name| !desired.contains_key(*name)).cloned().collect();
+    diff
+}
+
+/// Canonical type definitions of a model, keyed by type name
+fn type_definitions(model: &Value) -> BTreeMap<String, Value> {
+    model["type_definitions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|def| Some((def["type"].as_str()?.to_string(), canonical(def))))
+        .collect()
+}
+
+/// Drop null and empty values and sort arrays, so equal definitions compare equal
+fn canonical(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), canonical(v)))
+                .filter(|(_, v)| !is_empty_value(v))
+                .collect(),
+        ),
+        Value::Array(items) => {
+            let mut items: Vec<Value> = items.iter().map(canonical).collect();
+            items.sort_by_cached_key(|v| v.to_string());
+            Value::Array(items)
+        }
+        _ => value.clone(),
+    }
+}
+
+fn is_empty_value(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        Value::Object(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+/// Root user email used by `get_initial_tuples_default`
+pub const DEFAULT_ROOT_EMAIL: &str = "root@visdata.com";
+
+/// Default organization used by `get_initial_tuples_default`
+pub const DEFAULT_ORG: &str = "default";
+
+/// Internal organization holding audit logs and other system data
+pub const META_ORG: &str = "_meta";
+
+/// Resource types owned by every organization via an `_all_{org}` object
+const ORG_RESOURCE_TYPES: &[&str] = &[
+    "stream",
+    "logs",
+    "metrics",
+    "traces",
+    "metadata",
+    "index",
+    "dashboard",
+    "dfolder",
+    "savedviews",
+    "report",
+    "rfolder",
+    "alert",
+    "afolder",
+    "template",
+    "destination",
+    "function",
+    "pipeline",
+    "enrichment_table",
+    "summary",
+    "settings",
+    "kv",
+    "syslog-route",
+    "ratelimit",
+    "cipher_keys",
+    "license",
+    "user",
+    "group",
+    "role",
+    "passcode",
+    "rumtoken",
+    "service_accounts",
+    "search_jobs",
+    "action_scripts",
+    "ai",
+    "re_patterns",
+];
+
+/// Stream types whose `_all_{org}` objects have `stream:_all_{org}` as parent
+const STREAM_CHILD_TYPES: &[&str] = &["logs", "metrics", "traces", "index", "metadata"];
+
+/// Folder types that get a `default` folder in every organization
+const FOLDER_TYPES: &[&str] = &["dfolder", "afolder"];
+
 /// Get the initial tuples for bootstrapping the system
 ///
 /// These tuples set up:
-/// - Root user with admin access to default and _meta organizations
+/// - Root user with admin access to `default_org` (and `_meta` if `include_meta`)
+/// - The self-referential `selfParent` tuple of each organization
 /// - Organization resource ownership relationships
 /// - Folder hierarchies
 /// - Stream parent relationships
-pub fn get_initial_tuples() -> Vec<TupleKey> {
-    vec![
-        // ============================================
-        // Root 用户配置
-        // ============================================
-        TupleKey::new("org:default", "owningOrg", "user:root@visdata.com"),
-        TupleKey::new("user:root@visdata.com", "admin", "org:default"),
-        TupleKey::new("user:root@visdata.com", "org_context", "org:default"),
-        TupleKey::new("user:root@visdata.com", "admin", "org:_meta"),
-        TupleKey::new("user:root@visdata.com", "org_context", "org:_meta"),
-
-        // ============================================
-        // Default 组织 - 资源所有权
-        // ============================================
-        TupleKey::new("org:default", "owningOrg", "stream:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "logs:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "metrics:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "traces:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "metadata:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "index:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "dashboard:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "dfolder:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "savedviews:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "report:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "rfolder:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "alert:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "afolder:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "template:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "destination:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "function:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "pipeline:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "enrichment_table:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "summary:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "settings:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "kv:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "syslog-route:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "ratelimit:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "cipher_keys:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "license:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "user:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "group:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "role:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "passcode:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "rumtoken:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "service_accounts:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "search_jobs:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "action_scripts:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "ai:_all_default"),
-        TupleKey::new("org:default", "owningOrg", "re_patterns:_all_default"),
-
-        // ============================================
-        // Default 组织 - 文件夹层级
-        // ============================================
-        TupleKey::new("org:default", "owningOrg", "dfolder:default"),
-        TupleKey::new("dfolder:_all_default", "selfParent", "dfolder:default"),
-        TupleKey::new("org:default", "owningOrg", "afolder:default"),
-        TupleKey::new("afolder:_all_default", "selfParent", "afolder:default"),
-
-        // ============================================
-        // Default 组织 - Stream 父子关系
-        // ============================================
-        TupleKey::new("stream:_all_default", "parent", "logs:_all_default"),
-        TupleKey::new("stream:_all_default", "parent", "metrics:_all_default"),
-        TupleKey::new("stream:_all_default", "parent", "traces:_all_default"),
-        TupleKey::new("stream:_all_default", "parent", "index:_all_default"),
-        TupleKey::new("stream:_all_default", "parent", "metadata:_all_default"),
-
-        // ============================================
-        // _meta 组织 - 资源所有权
-        // ============================================
-        TupleKey::new("org:_meta", "owningOrg", "logs:audit"),
-        TupleKey::new("org:_meta", "owningOrg", "stream:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "logs:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "metrics:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "traces:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "metadata:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "index:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "dashboard:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "dfolder:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "savedviews:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "report:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "rfolder:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "alert:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "afolder:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "template:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "destination:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "function:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "pipeline:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "enrichment_table:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "summary:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "settings:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "kv:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "syslog-route:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "ratelimit:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "cipher_keys:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "license:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "user:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "group:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "role:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "passcode:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "rumtoken:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "service_accounts:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "search_jobs:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "action_scripts:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "ai:_all__meta"),
-        TupleKey::new("org:_meta", "owningOrg", "re_patterns:_all__meta"),
-
-        // ============================================
-        // _meta 组织 - 文件夹层级
-        // ============================================
-        TupleKey::new("org:_meta", "owningOrg", "dfolder:default"),
-        TupleKey::new("dfolder:_all__meta", "selfParent", "dfolder:default"),
-        TupleKey::new("org:_meta", "owningOrg", "afolder:default"),
-        TupleKey::new("afolder:_all__meta", "selfParent", "afolder:default"),
-
-        // ============================================
-        // _meta 组织 - Stream 父子关系
-        // ============================================
-        TupleKey::new("stream:_all__meta", "parent", "logs:_all__meta"),
-        TupleKey::new("stream:_all__meta", "parent", "metrics:_all__meta"),
-        TupleKey::new("stream:_all__meta", "parent", "traces:_all__meta"),
-        TupleKey::new("stream:_all__meta", "parent", "index:_all__meta"),
-        TupleKey::new("stream:_all__meta", "parent", "metadata:_all__meta"),
-    ]
+pub fn get_initial_tuples(
+    root_email: &str,
+    default_org: &str,
+    include_meta: bool,
+) -> Vec<TupleKey> {
+    let root = user_type(root_email);
+    let mut orgs = vec![default_org];
+    if include_meta && default_org != META_ORG {
+        orgs.push(META_ORG);
+    }
+
+    // Root user
+    let mut tuples = vec![TupleKey::new(org_type(default_org), "owningOrg", root.clone())];
+    for org in &orgs {
+        tuples.push(TupleKey::new(root.clone(), "admin", org_type(org)));
+        tuples.push(TupleKey::new(root.clone(), "org_context", org_type(org)));
+    }
+
+    for org in &orgs {
+        let org_object = org_type(org);
+        // Same marker as `get_org_creation_tuples`, so `org_exists` finds bootstrapped orgs
+        tuples.push(TupleKey::new(org_object.clone(), "selfParent", org_object.clone()));
+        if *org == META_ORG {
+            tuples.push(TupleKey::new(org_object.clone(), "owningOrg", "logs:audit"));
+        }
+
+        // Resource ownership
+        for resource_type in ORG_RESOURCE_TYPES {
+            tuples.push(org_resource_ownership_tuple(org, resource_type));
+        }
+
+        // Folder hierarchies
+        for folder_type in FOLDER_TYPES {
+            let folder = format!("{}:default", folder_type);
+            tuples.push(TupleKey::new(org_object.clone(), "owningOrg", folder.clone()));
+            tuples.push(TupleKey::new(
+                format!("{}:_all_{}", folder_type, org),
+                "selfParent",
+                folder,
+            ));
+        }
+
+        // Stream parent relationships
+        for child_type in STREAM_CHILD_TYPES {
+            tuples.push(TupleKey::new(
+                format!("stream:_all_{}", org),
+                "parent",
+                format!("{}:_all_{}", child_type, org),
+            ));
+        }
+    }
+    tuples
+}
+
+/// Tuple making `org_id` the owner of the org-wide `_all_{org_id}` object of a type
+pub fn org_resource_ownership_tuple(org_id: &str, resource_type: &str) -> TupleKey {
+    TupleKey::new(
+        org_type(org_id),
+        "owningOrg",
+        format!("{}:_all_{}", resource_type, org_id),
+    )
+}
+
+/// Initial tuples for the built-in root user and `default` org, including `_meta`
+pub fn get_initial_tuples_default() -> Vec<TupleKey> {
+    get_initial_tuples(DEFAULT_ROOT_EMAIL, DEFAULT_ORG, true)
 }
 
 /// Generate organization-scoped type name
@@ -174,6 +271,14 @@ pub fn user_type(user_email: &str) -> String {
     format!("user:{}", user_email)
 }
 
+/// Wildcard user matching every user ("user:*")
+///
+/// Resource `ALLOW_GET` and `ALLOW_LIST` relations accept it, making a
+/// resource readable by all authenticated users.
+pub fn public_user() -> String {
+    user_type("*")
+}
+
 /// Generate role type name
 pub fn role_type(org_id: &str, role_name: &str) -> String {
     format!("role:{}_{}", org_id, role_name)
@@ -184,6 +289,30 @@ pub fn group_type(org_id: &str, group_name: &str) -> String {
     format!("group:{}_{}", org_id, group_name)
 }
 
+/// Generate literal type name used to store free-form metadata values
+/// Format: "literal:{base64url(value)}"
+///
+/// OpenFGA identifiers cannot contain whitespace, so the value is encoded.
+pub fn literal_type(value: &str) -> String {
+    use base64::Engine;
+    format!(
+        "literal:{}",
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(value)
+    )
+}
+
+/// Decode a literal type name back into its value
+///
+/// Returns `None` if the name is not a literal or is not valid base64url UTF-8.
+pub fn parse_literal(name: &str) -> Option<String> {
+    use base64::Engine;
+    let encoded = name.strip_prefix("literal:")?;
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(encoded)
+        .ok()?;
+    String::from_utf8(bytes).ok()
+}
+
 /// Generate resource object name
 /// Format: "{resource_type}:{entity_id}"
 /// e.g., "logs:my_stream" or "dashboard:my_dashboard"
@@ -213,10 +342,89 @@ mod tests {
         assert!(parsed.get("type_definitions").is_some());
     }
 
+    #[test]
+    fn test_diff_type_definitions() {
+        let desired: Value = serde_json::from_str(get_authorization_model_json()).unwrap();
+        assert!(diff_type_definitions(&desired, &desired).is_empty());
+
+        // Reordering and OpenFGA's empty metadata fields are not changes
+        let mut current = desired.clone();
+        let defs = current["type_definitions"].as_array_mut().unwrap();
+        defs.reverse();
+        defs[0]["metadata"]["module"] = Value::String(String::new());
+        defs[1]["metadata"]["source_info"] = Value::Null;
+        assert!(diff_type_definitions(&current, &desired).is_empty());
+
+        // Drop one type, add a stale one and change a relation
+        let mut current = desired.clone();
+        let defs = current["type_definitions"].as_array_mut().unwrap();
+        let dropped = defs.remove(0)["type"].as_str().unwrap().to_string();
+        defs.push(serde_json::json!({ "type": "legacy_widget" }));
+        let org = defs.iter_mut().find(|d| d["type"] == "org").unwrap();
+        org["relations"]["extra"] =
+            serde_json::json!({ "computedUserset": { "relation": "admin" } });
+
+        let diff = diff_type_definitions(&current, &desired);
+        assert_eq!(diff.added, vec![dropped]);
+        assert_eq!(diff.removed, vec!["legacy_widget".to_string()]);
+        assert_eq!(diff.changed, vec!["org".to_string()]);
+    }
+
+    #[test]
+    fn test_resource_read_relations_accept_public_user() {
+        let model: Value = serde_json::from_str(get_authorization_model_json()).unwrap();
+        let public = serde_json::json!({ "type": "user", "wildcard": {} });
+        for def in model["type_definitions"].as_array().unwrap() {
+            let Some(relations) = def["metadata"]["relations"].as_object() else {
+                continue;
+            };
+            let def_type = def["type"].as_str().unwrap();
+            let is_resource = !["user", "group", "role", "org"].contains(&def_type);
+            let allow_relations = relations.iter().filter(|(name, _)| name.starts_with("ALLOW_"));
+            for (name, relation) in allow_relations {
+                let types = relation["directly_related_user_types"].as_array().unwrap();
+                let is_read = name == "ALLOW_GET" || name == "ALLOW_LIST";
+                let expected = is_resource && is_read;
+                assert_eq!(types.contains(&public), expected, "{} {}", def_type, name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_resource_owner_has_full_access() {
+        let model: Value = serde_json::from_str(get_authorization_model_json()).unwrap();
+        let owner = serde_json::json!({ "computedUserset": { "relation": "owner" } });
+        for def in model["type_definitions"].as_array().unwrap() {
+            let def_type = def["type"].as_str().unwrap();
+            let is_resource = !["user", "group", "role", "org"].contains(&def_type);
+            if !is_resource || def.get("relations").is_none() {
+                continue;
+            }
+            let children = def["relations"]["ALLOW_ALL"]["union"]["child"].as_array().unwrap();
+            assert!(children.contains(&owner), "{}", def_type);
+            assert_eq!(
+                def["metadata"]["relations"]["owner"]["directly_related_user_types"],
+                serde_json::json!([{ "type": "user" }]),
+                "{}",
+                def_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_type_relations() {
+        let relations = get_type_relations("org");
+        assert!(relations.contains(&"admin".to_string()));
+        assert!(relations.contains(&"viewer".to_string()));
+        assert!(relations.contains(&"org_context".to_string()));
+        assert!(get_type_relations("nonexistent").is_empty());
+    }
+
     #[test]
     fn test_type_generation() {
         assert_eq!(org_type("default"), "org:default");
         assert_eq!(user_type("alice@example.com"), "user:alice@example.com");
+        assert_eq!(public_user(), "user:*");
         assert_eq!(role_type("default", "admin"), "role:default_admin");
         assert_eq!(group_type("default", "developers"), "group:default_developers");
         assert_eq!(
@@ -229,9 +437,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_literal_round_trip() {
+        let literal = literal_type("Platform Team / SRE");
+        assert!(literal.starts_with("literal:"));
+        assert!(!literal.contains(' '));
+        assert_eq!(parse_literal(&literal).as_deref(), Some("Platform Team / SRE"));
+        assert_eq!(parse_literal(&literal_type("")).as_deref(), Some(""));
+        assert_eq!(parse_literal("user:alice@example.com"), None);
+        assert_eq!(parse_literal("literal:not base64!"), None);
+    }
+
     #[test]
     fn test_initial_tuples_not_empty() {
-        let tuples = get_initial_tuples();
+        let tuples = get_initial_tuples_default();
         assert!(!tuples.is_empty());
         // Should have tuples for root user
         assert!(tuples.iter().any(|t| t.user.contains("root@visdata.com")));
@@ -240,4 +459,29 @@ mod tests {
         // Should have tuples for _meta org
         assert!(tuples.iter().any(|t| t.user == "org:_meta" || t.object.contains("_meta")));
     }
+
+    #[test]
+    fn test_initial_tuples_custom() {
+        let root = "user:admin@corp.example";
+        let tuples = get_initial_tuples("admin@corp.example", "acme", false);
+
+        assert!(tuples.contains(&TupleKey::new("org:acme", "owningOrg", root)));
+        assert!(tuples.contains(&TupleKey::new(root, "admin", "org:acme")));
+        assert!(tuples.contains(&TupleKey::new("org:acme", "owningOrg", "logs:_all_acme")));
+        assert!(tuples.contains(&TupleKey::new("stream:_all_acme", "parent", "logs:_all_acme")));
+        assert!(tuples.contains(&TupleKey::new("org:acme", "selfParent", "org:acme")));
+        assert!(tuples.contains(&TupleKey::new(
+            "dfolder:_all_acme",
+            "selfParent",
+            "dfolder:default"
+        )));
+        assert!(!tuples.iter().any(|t| t.user.contains("root@visdata.com")));
+        assert!(!tuples.iter().any(|t| t.user.contains("_meta") || t.object.contains("_meta")));
+        assert!(!tuples.iter().any(|t| t.object.contains("_all_default")));
+
+        let with_meta = get_initial_tuples("admin@corp.example", "acme", true);
+        assert!(with_meta.contains(&TupleKey::new("org:_meta", "owningOrg", "logs:audit")));
+        assert!(with_meta.contains(&TupleKey::new(root, "admin", "org:_meta")));
+        assert_eq!(with_meta.len(), 2 * tuples.len());
+    }
 }
@@ -0,0 +1,244 @@
+// Copyright 2025 VisData Inc.
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+//! Permission check result cache
+//!
+//! Caches `check` results keyed on `(user, relation, object, model_id)` for
+//! `CacheConfig::ttl_seconds`. Tuple writes invalidate affected entries via
+//! `invalidate_tuples`.
+
+use crate::common::clock::Clock;
+use crate::config::CacheConfig;
+use super::super::types::TupleKey;
+
+/// Cache key: (user, relation, object, model_id)
+type CacheKey = (String, String, String, String);
+
+/// Global permission cache used by the checker
+pub(crate) static PERMISSION_CACHE: once_cell::sync::Lazy<PermissionCache> =
+    once_cell::sync::Lazy::new(PermissionCache::default);
+
+/// Bounded TTL cache of permission check results
+#[derive(Default)]
+pub struct PermissionCache {
+    entries: dashmap::DashMap<CacheKey, (bool, i64)>,
+}
+
+impl PermissionCache {
+    fn key(tuple_key: &TupleKey, model_id: Option<&str>) -> CacheKey {
+        (
+            tuple_key.user.clone(),
+            tuple_key.relation.clone(),
+            tuple_key.object.clone(),
+            model_id.unwrap_or_default().to_string(),
+        )
+    }
+
+    /// Get a cached result if caching is enabled and the entry has not expired
+    pub fn get(
+        &self,
+        config: &CacheConfig,
+        tuple_key: &TupleKey,
+        model_id: Option<&str>,
+        clock: &dyn Clock,
+    ) -> Option<bool> {
+        if !config.enabled {
+            return None;
+        }
+        let key = Self::key(tuple_key, model_id);
+        let (allowed, cached_at) = *self.entries.get(&key)?;
+        if clock.timestamp() - cached_at < config.ttl_seconds as i64 {
+            return Some(allowed);
+        }
+        self.entries.remove(&key);
+        None
+    }
+
+    /// Store a check result, evicting expired or oldest entries when full
+    pub fn insert(
+        &self,
+        config: &CacheConfig,
+        tuple_key: &TupleKey,
+        model_id: Option<&str>,
+        allowed: bool,
+        clock: &dyn Clock,
+    ) {
+        if !config.enabled || config.max_entries == 0 {
+            return;
+        }
+        let now = clock.timestamp();
+        if self.entries.len() >= config.max_entries {
+            let ttl = config.ttl_seconds as i64;
+            self.entries.retain(|_, (_, cached_at)| now - *cached_at < ttl);
+        }
+        while self.entries.len() >= config.max_entries {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|entry| entry.value().1)
+                .map(|entry| entry.key().clone());
+            match oldest {
+                Some(key) => {
+                    self.entries.remove(&key);
+                }
+                None => break,
+            }
+        }
+        self.entries
+            .insert(Self::key(tuple_key, model_id), (allowed, now));
+    }
+
+    /// Invalidate entries affected by written or deleted tuples
+    ///
+    /// Tuples granting directly to a user only flush that user's entries (and
+    /// entries on the tuple's object). Anything else (usersets such as
+    /// `role:x#has`, parent links, wildcards) can change permissions
+    /// transitively, so the whole cache is flushed.
+    pub fn invalidate_tuples<'a>(&self, tuples: impl IntoIterator<Item = &'a TupleKey>) {
+        let mut users = Vec::new();
+        let mut objects = Vec::new();
+        for tuple in tuples {
+            if !is_direct_user(&tuple.user) {
+                self.clear();
+                return;
+            }
+            users.push(tuple.user.as_str());
+            objects.push(tuple.object.as_str());
+        }
+        if users.is_empty() {
+            return;
+        }
+        self.entries.retain(|(user, _, object, _), _| {
+            !users.contains(&user.as_str()) && !objects.contains(&object.as_str())
+        });
+    }
+
+    /// Remove all entries
+    pub fn clear(&self) {
+        self.entries.clear();
+    }
+
+    /// Number of cached entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Whether a tuple user is a concrete user (not a userset, wildcard or other type)
+fn is_direct_user(user: &str) -> bool {
+    user.starts_with("user:") && !user.contains('#') && user != "user:*"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::clock::MockClock;
+
+    fn config(max_entries: usize) -> CacheConfig {
+        CacheConfig {
+            enabled: true,
+            ttl_seconds: 300,
+            max_entries,
+        }
+    }
+
+    fn check(user: &str, object: &str) -> TupleKey {
+        TupleKey::new(format!("user:{}", user), "can_read", object)
+    }
+
+    #[test]
+    fn test_cache_hit_and_expiry() {
+        let cache = PermissionCache::default();
+        let clock = MockClock::new(1_700_000_000);
+        let config = config(10);
+        let tuple = check("alice", "dashboard:d1");
+
+        assert_eq!(cache.get(&config, &tuple, Some("m1"), &clock), None);
+        cache.insert(&config, &tuple, Some("m1"), true, &clock);
+        assert_eq!(cache.get(&config, &tuple, Some("m1"), &clock), Some(true));
+
+        // Different model ID is a different entry
+        assert_eq!(cache.get(&config, &tuple, Some("m2"), &clock), None);
+
+        clock.advance(std::time::Duration::from_secs(300));
+        assert_eq!(cache.get(&config, &tuple, Some("m1"), &clock), None);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_cache_disabled() {
+        let cache = PermissionCache::default();
+        let clock = MockClock::new(1_700_000_000);
+        let config = CacheConfig {
+            enabled: false,
+            ..config(10)
+        };
+        let tuple = check("alice", "dashboard:d1");
+
+        cache.insert(&config, &tuple, None, true, &clock);
+        assert!(cache.is_empty());
+        assert_eq!(cache.get(&config, &tuple, None, &clock), None);
+    }
+
+    #[test]
+    fn test_cache_evicts_oldest_when_full() {
+        let cache = PermissionCache::default();
+        let clock = MockClock::new(1_700_000_000);
+        let config = config(2);
+
+        cache.insert(&config, &check("a", "dashboard:d1"), None, true, &clock);
+        clock.advance(std::time::Duration::from_secs(1));
+        cache.insert(&config, &check("b", "dashboard:d1"), None, true, &clock);
+        clock.advance(std::time::Duration::from_secs(1));
+        cache.insert(&config, &check("c", "dashboard:d1"), None, true, &clock);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&config, &check("a", "dashboard:d1"), None, &clock), None);
+        assert_eq!(cache.get(&config, &check("c", "dashboard:d1"), None, &clock), Some(true));
+    }
+
+    #[test]
+    fn test_invalidate_direct_user_tuples() {
+        let cache = PermissionCache::default();
+        let clock = MockClock::new(1_700_000_000);
+        let config = config(10);
+
+        cache.insert(&config, &check("alice", "dashboard:d1"), None, true, &clock);
+        cache.insert(&config, &check("bob", "dashboard:d2"), None, false, &clock);
+        cache.insert(&config, &check("carol", "dashboard:d3"), None, true, &clock);
+
+        cache.invalidate_tuples(&[
+            TupleKey::new("user:alice", "member", "group:default_devs"),
+            TupleKey::new("user:dave", "owner", "dashboard:d2"),
+        ]);
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&config, &check("carol", "dashboard:d3"), None, &clock), Some(true));
+    }
+
+    #[test]
+    fn test_invalidate_transitive_tuples_flushes_all() {
+        let cache = PermissionCache::default();
+        let clock = MockClock::new(1_700_000_000);
+        let config = config(10);
+
+        cache.insert(&config, &check("alice", "dashboard:d1"), None, true, &clock);
+        cache.insert(&config, &check("bob", "dashboard:d2"), None, false, &clock);
+
+        cache.invalidate_tuples(&[TupleKey::new(
+            "role:default_editor#has",
+            "can_read",
+            "dashboard:d9",
+        )]);
+        assert!(cache.is_empty());
+    }
+}
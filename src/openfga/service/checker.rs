@@ -7,81 +7,453 @@
 
 //! Permission checking service (compatible with o2_openfga::authorizer::authz)
 
+use std::collections::HashMap;
+use std::time::Instant;
+
 use crate::Visdata;
-use super::super::error::Result;
+use crate::common::clock::{Clock, SystemClock};
+use super::super::config::OpenFGAConfig;
+use super::super::error::{Error, Result};
+use super::super::metrics;
 use super::super::model::{resources, schema};
-use super::super::types::{Permission, TupleKey};
+use super::super::types::{AuthDecision, ConsistencyPreference, Permission, TupleKey};
+use super::cache::PERMISSION_CACHE;
+use super::tuples;
+
+/// Cache of verified `_meta` admin membership for the root bypass (user -> (is_admin, checked_at))
+static ROOT_ADMIN_CACHE: once_cell::sync::Lazy<dashmap::DashMap<String, (bool, i64)>> =
+    once_cell::sync::Lazy::new(dashmap::DashMap::new);
+
+/// How long a root verification result is cached, in seconds
+const ROOT_ADMIN_CACHE_TTL_SECS: i64 = 60;
+
+/// Most users remembered in `LEGACY_ROOT_WARNED` before it is cleared
+const LEGACY_ROOT_WARNED_MAX: usize = 1024;
+
+/// Users already warned about for the deprecated role-string bypass
+static LEGACY_ROOT_WARNED: once_cell::sync::Lazy<dashmap::DashSet<String>> =
+    once_cell::sync::Lazy::new(dashmap::DashSet::new);
+
+/// Root bypass decided without contacting OpenFGA
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RootBypass {
+    /// The user is a configured root user
+    Bypass,
+    /// Run the normal permission check
+    Check,
+    /// The "root" role string is trusted (deprecated)
+    TrustedRoleString,
+    /// The "root" role must be verified against `_meta` admin
+    Verify,
+}
+
+/// Decide the root bypass without contacting OpenFGA
+///
+/// Configured `root_emails` decide on the user ID alone. The caller-supplied
+/// role string is only considered for a "root" role, as a migration path.
+fn root_bypass_decision(config: &OpenFGAConfig, user_id: &str, role: &str) -> RootBypass {
+    if config.root_emails.iter().any(|email| email.eq_ignore_ascii_case(user_id)) {
+        return RootBypass::Bypass;
+    }
+    if !role.eq_ignore_ascii_case("root") {
+        return RootBypass::Check;
+    }
+    if config.trust_root_role_string {
+        return RootBypass::TrustedRoleString;
+    }
+    RootBypass::Verify
+}
+
+/// Get a cached root verification result if it has not expired
+fn cached_root_admin(user_id: &str, clock: &dyn Clock) -> Option<bool> {
+    ROOT_ADMIN_CACHE
+        .get(user_id)
+        .filter(|entry| clock.timestamp() - entry.1 < ROOT_ADMIN_CACHE_TTL_SECS)
+        .map(|entry| entry.0)
+}
+
+/// Check whether the caller qualifies for the root bypass
+///
+/// Users in `root_emails` always bypass. Otherwise a "root" role bypasses
+/// when `trust_root_role_string` is set, logging a warning once per user so
+/// callers relying on it can be found, or, with it disabled, only if the user
+/// actually holds `admin` on `org:_meta` (cached for a short TTL).
+async fn is_root_bypass(config: &OpenFGAConfig, user_id: &str, role: &str) -> bool {
+    match root_bypass_decision(config, user_id, role) {
+        RootBypass::Bypass => return true,
+        RootBypass::Check => return false,
+        RootBypass::TrustedRoleString => {
+            if LEGACY_ROOT_WARNED.len() >= LEGACY_ROOT_WARNED_MAX {
+                // Start over rather than grow without bound; users are
+                // simply warned about again
+                LEGACY_ROOT_WARNED.clear();
+            }
+            if LEGACY_ROOT_WARNED.insert(user_id.to_string()) {
+                tracing::warn!(
+                    "[RBAC] Bypassing checks for {} based on the \"root\" role string; \
+                     add the user to root_emails and disable trust_root_role_string",
+                    user_id
+                );
+            }
+            return true;
+        }
+        RootBypass::Verify => {}
+    }
+    if let Some(is_admin) = cached_root_admin(user_id, &SystemClock) {
+        return is_admin;
+    }
+
+    let tuple_key = TupleKey::new(schema::user_type(user_id), "admin", schema::org_type("_meta"));
+    let is_admin = match Visdata::global().openfga().check(&tuple_key).await {
+        Ok(allowed) => allowed,
+        Err(e) => {
+            tracing::error!("[RBAC] Root verification failed: {}", e);
+            return false;
+        }
+    };
+
+    if !is_admin {
+        tracing::warn!("[RBAC] User {} claimed root role without _meta admin", user_id);
+    }
+    ROOT_ADMIN_CACHE.insert(user_id.to_string(), (is_admin, SystemClock.timestamp()));
+    is_admin
+}
 
 /// Check if a user has permission on an object
 ///
 /// Compatible with o2_openfga::authorizer::authz::is_allowed
 pub async fn is_allowed(
+    org_id: &str,
+    user_id: &str,
+    method: &str,
+    object: &str,        // Format: "resource_type:entity_id"
+    parent_id: &str,     // Not used in current implementation
+    role: &str,
+) -> Result<bool> {
+    is_allowed_with_consistency(
+        org_id,
+        user_id,
+        method,
+        object,
+        parent_id,
+        role,
+        ConsistencyPreference::MinimizeLatency,
+    )
+    .await
+}
+
+/// Check if a user has permission on an object with a consistency preference
+///
+/// Use `HigherConsistency` right after writing tuples (e.g. during user
+/// provisioning) so the check sees them; it also skips the result cache.
+pub async fn is_allowed_with_consistency(
     org_id: &str,
     user_id: &str,
     method: &str,
     object: &str,        // Format: "resource_type:entity_id"
     _parent_id: &str,    // Not used in current implementation
     role: &str,
+    consistency: ConsistencyPreference,
 ) -> Result<bool> {
+    explain_decision(org_id, user_id, method, object, role, consistency, false)
+        .await
+        .map(|decision| decision.allowed)
+}
+
+/// Check if a user has permission on an object and report how it was decided
+///
+/// Runs the same logic as `is_allowed`, but instead of only logging why a
+/// request was denied early (malformed object, unknown resource type, failed
+/// check) the reason is returned in `AuthDecision::reason`.
+pub async fn is_allowed_explained(
+    org_id: &str,
+    user_id: &str,
+    method: &str,
+    object: &str,        // Format: "resource_type:entity_id"
+    _parent_id: &str,    // Not used in current implementation
+    role: &str,
+) -> Result<AuthDecision> {
+    explain_decision(
+        org_id,
+        user_id,
+        method,
+        object,
+        role,
+        ConsistencyPreference::MinimizeLatency,
+        false,
+    )
+    .await
+}
+
+/// Decide a check request, recording check metrics
+///
+/// A failed OpenFGA check is reported as a denial with a reason, or returned
+/// as the error when `propagate_errors` is set.
+async fn explain_decision(
+    org_id: &str,
+    user_id: &str,
+    method: &str,
+    object: &str,
+    role: &str,
+    consistency: ConsistencyPreference,
+    propagate_errors: bool,
+) -> Result<AuthDecision> {
+    let started = Instant::now();
+    let result =
+        decide(org_id, user_id, method, object, role, consistency, propagate_errors).await;
+    if let Ok(decision) = &result {
+        metrics::record_check(decision.allowed, started.elapsed());
+    }
+    result
+}
+
+/// Decision logic behind `explain_decision`
+async fn decide(
+    org_id: &str,
+    user_id: &str,
+    method: &str,
+    object: &str,
+    role: &str,
+    consistency: ConsistencyPreference,
+    propagate_errors: bool,
+) -> Result<AuthDecision> {
     let visdata = Visdata::global();
     let config = visdata.openfga().config().await;
 
+    let mut decision = resolve_check(org_id, user_id, method, object);
+
     // Skip check if not enabled
     if !config.enabled {
-        return Ok(true);
+        decision.allowed = true;
+        decision.reason = Some("permission checking is disabled".to_string());
+        return Ok(decision);
     }
 
     // Root users bypass all checks
-    if role.eq_ignore_ascii_case("root") {
-        return Ok(true);
+    if is_root_bypass(&config, user_id, role).await {
+        decision.allowed = true;
+        decision.reason = Some("root user bypass".to_string());
+        return Ok(decision);
+    }
+
+    // Malformed object or unknown resource type
+    if let Some(reason) = &decision.reason {
+        tracing::warn!("[RBAC] {}", reason);
+        return Ok(decision);
+    }
+
+    // The method relations (GET, LIST, ...) already include ALLOW_ALL grants
+    let tuple_key = &decision.checked_tuple;
+
+    // Serve repeat checks from the cache
+    let model_id = config.model_id.as_deref();
+    let cached = match consistency {
+        ConsistencyPreference::MinimizeLatency if config.cache.enabled => {
+            let cached = PERMISSION_CACHE.get(&config.cache, tuple_key, model_id, &SystemClock);
+            metrics::record_cache_lookup(cached.is_some());
+            cached
+        }
+        _ => None,
+    };
+    if let Some(allowed) = cached {
+        decision.allowed = allowed;
+        decision.reason = Some("served from permission cache".to_string());
+        return Ok(decision);
+    }
+
+    // Perform check
+    match visdata.openfga().check_with_consistency(tuple_key, consistency).await {
+        Ok(allowed) => {
+            tracing::debug!(
+                "[RBAC] Check: user={}, relation={}, object={} -> {}",
+                user_id, tuple_key.relation, tuple_key.object, allowed
+            );
+            PERMISSION_CACHE.insert(&config.cache, tuple_key, model_id, allowed, &SystemClock);
+            decision.allowed = allowed;
+        }
+        Err(e) => {
+            tracing::error!("[RBAC] Check failed: {}", e);
+            if propagate_errors {
+                return Err(e);
+            }
+            decision.reason = Some(format!("check failed: {}", e));
+        }
     }
+    Ok(decision)
+}
+
+/// Parse and validate a check request into an (initially denied) decision
+///
+/// `reason` is set when the object is malformed or its resource type is
+/// unknown, in which case `checked_tuple` holds the raw object.
+///
+/// Parent-scoped objects ("dashboard:myfolder/mydash") are checked on the
+/// entity itself, which inherits folder permissions through its persisted
+/// `parent` tuple. The folder comes from the caller, so the derived
+/// `parent_tuple` is only reported, never sent as a contextual tuple.
+fn resolve_check(org_id: &str, user_id: &str, method: &str, object: &str) -> AuthDecision {
+    let denied = |resource_type: &str, reason: String| AuthDecision {
+        allowed: false,
+        checked_tuple: TupleKey::new(
+            schema::user_type(user_id),
+            Permission::from_method(method, false).to_internal_relation(),
+            object,
+        ),
+        resource_type: resource_type.to_string(),
+        is_list: false,
+        parent_tuple: None,
+        reason: Some(reason),
+    };
 
     // Parse object format: "resource_type:entity_id"
     let (resource_type, entity_id) = match resources::parse_object(object) {
         Some(parts) => parts,
-        None => {
-            tracing::warn!("[RBAC] Invalid object format: {}", object);
-            return Ok(false);
-        }
+        None => return denied("", format!("Invalid object format: {}", object)),
     };
 
-    // Validate resource type
-    if !resources::is_valid_resource_type(resource_type) {
-        tracing::warn!("[RBAC] Unknown resource type: {}", resource_type);
-        return Ok(false);
-    }
+    // Validate resource type, resolving legacy keys such as "serviceaccounts"
+    let Some(resource_type) = resources::resolve_resource_key(resource_type) else {
+        return denied(resource_type, format!("Unknown resource type: {}", resource_type));
+    };
+
+    // Split parent-scoped entities such as "myfolder/mydash"
+    let (folder_id, entity_id) = match resources::split_parent_scope(resource_type, entity_id) {
+        Some(parts) => parts,
+        None => return denied(resource_type, format!("Invalid object format: {}", object)),
+    };
 
     // Determine if this is a list operation
     let is_list = resources::is_all_org_entity(entity_id, org_id);
 
     // Convert HTTP method to permission
     let permission = Permission::from_method(method, is_list);
-    let relation = permission.to_relation();
 
-    // Build tuple key for check
+    AuthDecision {
+        allowed: false,
+        checked_tuple: check_tuple(org_id, user_id, resource_type, entity_id, permission),
+        resource_type: resource_type.to_string(),
+        is_list,
+        parent_tuple: folder_id.and_then(|folder_id| {
+            tuples::get_folder_parent_tuple(org_id, resource_type, folder_id, entity_id)
+        }),
+        reason: None,
+    }
+}
+
+/// Build the check tuple for a user, permission and "resource_type:entity_id" object
+fn check_tuple(
+    org_id: &str,
+    user_id: &str,
+    resource_type: &str,
+    entity_id: &str,
+    permission: Permission,
+) -> TupleKey {
     let user = schema::user_type(user_id);
-    let fga_object = if is_list {
+    let fga_object = if resources::is_all_org_entity(entity_id, org_id) {
         schema::resource_object_all(org_id, resource_type)
     } else {
         schema::resource_object(org_id, resource_type, entity_id)
     };
 
-    let tuple_key = TupleKey::new(&user, relation, &fga_object);
+    TupleKey::new(&user, permission.to_internal_relation(), &fga_object)
+}
 
-    // Perform check
-    match visdata.openfga().check(&tuple_key).await {
-        Ok(allowed) => {
-            tracing::debug!(
-                "[RBAC] Check: user={}, relation={}, object={} -> {}",
-                user_id, relation, fga_object, allowed
-            );
-            Ok(allowed)
-        }
-        Err(e) => {
-            tracing::error!("[RBAC] Check failed: {}", e);
-            Ok(false)
+/// Compute which of the required permissions a user lacks
+///
+/// Checks all `(object, permission)` pairs concurrently and returns the ones
+/// that are not granted, for pre-filling access requests. Objects use the
+/// "resource_type:entity_id" format; malformed objects or unknown resource
+/// types are reported as missing. Returns an empty list for root users or
+/// when permission checking is disabled.
+pub async fn missing_permissions(
+    org_id: &str,
+    user_id: &str,
+    required: &[(String, Permission)],
+    role: &str,
+) -> Result<Vec<(String, Permission)>> {
+    let visdata = Visdata::global();
+    let config = visdata.openfga().config().await;
+
+    if !config.enabled || is_root_bypass(&config, user_id, role).await {
+        return Ok(vec![]);
+    }
+
+    let checks = required.iter().map(|(object, permission)| async move {
+        let Some((resource_type, entity_id)) = resources::parse_object(object) else {
+            return Ok(false);
+        };
+        let Some(resource_type) = resources::resolve_resource_key(resource_type) else {
+            return Ok(false);
+        };
+        let tuple_key = match resources::split_parent_scope(resource_type, entity_id) {
+            Some((_, entity_id)) => {
+                check_tuple(org_id, user_id, resource_type, entity_id, *permission)
+            }
+            None => return Ok(false),
+        };
+        visdata.openfga().check(&tuple_key).await
+    });
+    let results = futures::future::join_all(checks).await;
+
+    let mut missing = Vec::new();
+    for ((object, permission), allowed) in required.iter().zip(results) {
+        if !allowed? {
+            missing.push((object.clone(), *permission));
         }
     }
+
+    Ok(missing)
+}
+
+/// Which top-level resource types a user can list, for the UI menu
+///
+/// Checks `LIST` on each top-level resource type's `_all_{org}` object in
+/// one `batch_check` and returns a map of resource key to whether it is
+/// allowed. Every type is allowed for
+/// root users or when permission checking is disabled.
+pub async fn check_menu_access(
+    org_id: &str,
+    user_id: &str,
+    role: &str,
+) -> Result<HashMap<String, bool>> {
+    let visdata = Visdata::global();
+    let config = visdata.openfga().config().await;
+    let keys: Vec<&str> = resources::get_top_level_resources()
+        .into_iter()
+        .map(|r| r.key.as_str())
+        .collect();
+
+    if !config.enabled || is_root_bypass(&config, user_id, role).await {
+        return Ok(keys.into_iter().map(|key| (key.to_string(), true)).collect());
+    }
+
+    let checks = menu_access_checks(org_id, user_id, &keys);
+    let tuple_keys = checks.iter().map(|(_, tuple_key)| tuple_key.clone()).collect();
+    let results = visdata.openfga().batch_check(tuple_keys).await?;
+    Ok(collect_menu_access(&checks, &results))
+}
+
+/// `LIST` check on each resource type's `_all_{org}` object
+///
+/// Returns one `(resource key, tuple)` pair per type; `LIST` already
+/// includes `ALLOW_ALL` grants.
+fn menu_access_checks(org_id: &str, user_id: &str, keys: &[&str]) -> Vec<(String, TupleKey)> {
+    let all_org = format!("_all_{}", org_id);
+    keys.iter()
+        .map(|key| {
+            let tuple_key = check_tuple(org_id, user_id, key, &all_org, Permission::AllowList);
+            (key.to_string(), tuple_key)
+        })
+        .collect()
+}
+
+/// Pair batch check results with their resource keys
+fn collect_menu_access(checks: &[(String, TupleKey)], results: &[bool]) -> HashMap<String, bool> {
+    checks
+        .iter()
+        .zip(results)
+        .map(|((key, _), allowed)| (key.clone(), *allowed))
+        .collect()
 }
 
 /// Check user permissions (for use in HTTP validator)
@@ -103,10 +475,44 @@ pub async fn check_permissions(
     }
 }
 
+/// Require permission on an object (for request guards)
+///
+/// Unlike `check_permissions`, a denial is told apart from a failed check:
+/// denials return `Error::PermissionDenied`, while OpenFGA errors are passed
+/// through so callers can answer 403 and 503 respectively.
+pub async fn authorize(
+    user_id: &str,
+    org_id: &str,
+    method: &str,
+    object: &str,
+    role: &str,
+) -> Result<()> {
+    let decision = explain_decision(
+        org_id,
+        user_id,
+        method,
+        object,
+        role,
+        ConsistencyPreference::MinimizeLatency,
+        true,
+    )
+    .await?;
+
+    if decision.allowed {
+        Ok(())
+    } else {
+        Err(Error::PermissionDenied(format!("{} on {}", method, object)))
+    }
+}
+
 /// List objects that a user can access with a specific permission
 ///
 /// Compatible with o2_openfga::authorizer::authz::list_objects
 ///
+/// Returns entity IDs ("my_dashboard" for "dashboard:my_dashboard"); a
+/// type-level grant shows up as "_all_{org_id}". See `list_permitted_objects`
+/// for full object IDs.
+///
 /// Returns:
 /// - Some(Vec<String>) if permission filtering is enabled
 /// - None if permission filtering is disabled (return all objects)
@@ -116,12 +522,29 @@ pub async fn list_objects_for_user(
     permission: &str,       // "AllowList" or "AllowGet"
     object_type: &str,      // "role", "group", "dashboard", etc.
     role: &str,
+) -> Result<Option<Vec<String>>> {
+    let objects = list_permitted_objects(org_id, user_id, permission, object_type, role).await?;
+    Ok(objects.map(|objects| strip_object_type(objects, object_type)))
+}
+
+/// List full object IDs ("dashboard:my_dashboard") a user can access
+///
+/// Same filtering as `list_objects_for_user`: `None` when permission checking
+/// or `list_only_permitted` is disabled, or the user is root. Objects are
+/// listed through the LIST or GET relation, which include `AllowAll` grants,
+/// and other orgs' "_all_{org}" objects are dropped.
+pub async fn list_permitted_objects(
+    org_id: &str,
+    user_id: &str,
+    permission: &str,       // "AllowList" or "AllowGet"
+    object_type: &str,      // "role", "group", "dashboard", etc.
+    role: &str,
 ) -> Result<Option<Vec<String>>> {
     let visdata = Visdata::global();
     let config = visdata.openfga().config().await;
 
     // Skip if not enabled or user is root
-    if !config.enabled || role.eq_ignore_ascii_case("root") {
+    if !config.enabled || is_root_bypass(&config, user_id, role).await {
         return Ok(None);
     }
 
@@ -130,36 +553,13 @@ pub async fn list_objects_for_user(
         return Ok(None);
     }
 
-    // Convert permission string to relation
-    let relation = match Permission::from_str(permission) {
-        Some(p) => p.to_relation(),
-        None => "can_read", // Default to read permission
-    };
-
-    // Build user type
+    // Default to read permission; LIST and GET also cover ALLOW_ALL grants
+    let permission = permission.parse().unwrap_or(Permission::AllowGet);
     let user = schema::user_type(user_id);
+    let relation = permission.to_internal_relation();
 
-    // The object type in OpenFGA is "resource" with org-scoped naming
-    let fga_type = "resource";
-
-    // List objects
-    match visdata.openfga().list_objects(&user, relation, fga_type).await {
-        Ok(objects) => {
-            // Filter to only include objects from this org and resource type
-            let prefix = format!("resource:{}_{}_{}", org_id, object_type, "");
-            let filtered: Vec<String> = objects
-                .into_iter()
-                .filter(|o| o.starts_with(&prefix))
-                .map(|o| {
-                    // Extract entity ID from "resource:{org}_{type}_{entity}"
-                    o.strip_prefix(&prefix)
-                        .unwrap_or(&o)
-                        .to_string()
-                })
-                .collect();
-
-            Ok(Some(filtered))
-        }
+    match visdata.openfga().list_objects(&user, relation, object_type).await {
+        Ok(objects) => Ok(Some(filter_org_objects(objects, org_id, object_type))),
         Err(e) => {
             tracing::error!("[RBAC] List objects failed: {}", e);
             Err(e)
@@ -167,18 +567,50 @@ pub async fn list_objects_for_user(
     }
 }
 
+/// Keep `object_type` objects visible to `org_id`, removing duplicates
+///
+/// Objects use the "{type}:{entity}" format. Entity IDs carry no org, so only
+/// "_all_{org}" objects of other orgs can be told apart and dropped.
+fn filter_org_objects(objects: Vec<String>, org_id: &str, object_type: &str) -> Vec<String> {
+    let own_all = schema::resource_object_all(org_id, object_type);
+    let mut seen = std::collections::HashSet::new();
+    objects
+        .into_iter()
+        .filter(|object| match resources::parse_object(object) {
+            Some((resource_type, entity_id)) => {
+                resource_type == object_type
+                    && (!entity_id.starts_with("_all_") || *object == own_all)
+            }
+            None => false,
+        })
+        .filter(|object| seen.insert(object.clone()))
+        .collect()
+}
+
+/// Strip the "{type}:" prefix from object IDs, leaving entity IDs
+fn strip_object_type(objects: Vec<String>, object_type: &str) -> Vec<String> {
+    let prefix = format!("{}:", object_type);
+    objects
+        .into_iter()
+        .map(|object| match object.strip_prefix(&prefix) {
+            Some(entity_id) => entity_id.to_string(),
+            None => object,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_permission_to_relation() {
-        assert_eq!(Permission::AllowAll.to_relation(), "admin");
-        assert_eq!(Permission::AllowGet.to_relation(), "can_read");
-        assert_eq!(Permission::AllowList.to_relation(), "can_list");
-        assert_eq!(Permission::AllowPost.to_relation(), "can_create");
-        assert_eq!(Permission::AllowPut.to_relation(), "can_update");
-        assert_eq!(Permission::AllowDelete.to_relation(), "can_delete");
+    fn test_permission_to_internal_relation() {
+        assert_eq!(Permission::AllowAll.to_internal_relation(), "ALLOW_ALL");
+        assert_eq!(Permission::AllowGet.to_internal_relation(), "GET");
+        assert_eq!(Permission::AllowList.to_internal_relation(), "LIST");
+        assert_eq!(Permission::AllowPost.to_internal_relation(), "POST");
+        assert_eq!(Permission::AllowPut.to_internal_relation(), "PUT");
+        assert_eq!(Permission::AllowDelete.to_internal_relation(), "DELETE");
     }
 
     #[test]
@@ -189,4 +621,192 @@ mod tests {
         assert_eq!(Permission::from_method("PUT", false), Permission::AllowPut);
         assert_eq!(Permission::from_method("DELETE", false), Permission::AllowDelete);
     }
+
+    #[test]
+    fn test_check_tuple() {
+        let tuple = check_tuple("default", "alice@example.com", "dashboard", "d1", Permission::AllowGet);
+        assert_eq!(tuple.user, "user:alice@example.com");
+        assert_eq!(tuple.relation, "GET");
+        assert_eq!(tuple.object, "dashboard:d1");
+
+        let tuple = check_tuple(
+            "default",
+            "alice@example.com",
+            "dashboard",
+            "_all_default",
+            Permission::AllowList,
+        );
+        assert_eq!(tuple.relation, "LIST");
+        assert_eq!(tuple.object, "dashboard:_all_default");
+    }
+
+    #[test]
+    fn test_menu_access() {
+        let checks = menu_access_checks("default", "alice@example.com", &["logs", "dashboard"]);
+        let relations: Vec<(&str, &str, &str)> = checks
+            .iter()
+            .map(|(key, t)| (key.as_str(), t.relation.as_str(), t.object.as_str()))
+            .collect();
+        assert_eq!(
+            relations,
+            vec![
+                ("logs", "LIST", "logs:_all_default"),
+                ("dashboard", "LIST", "dashboard:_all_default"),
+            ]
+        );
+
+        let access = collect_menu_access(&checks, &[true, false]);
+        assert_eq!(access.len(), 2);
+        assert!(access["logs"]);
+        assert!(!access["dashboard"]);
+    }
+
+    #[test]
+    fn test_resolve_check() {
+        let decision = resolve_check("default", "alice@example.com", "GET", "dashboard:_all_default");
+        assert!(!decision.allowed);
+        assert_eq!(decision.reason, None);
+        assert_eq!(decision.resource_type, "dashboard");
+        assert!(decision.is_list);
+        assert_eq!(decision.checked_tuple.relation, "LIST");
+        assert_eq!(decision.checked_tuple.object, "dashboard:_all_default");
+
+        let decision = resolve_check("default", "alice@example.com", "GET", "no-colon");
+        assert_eq!(decision.reason.as_deref(), Some("Invalid object format: no-colon"));
+        assert_eq!(decision.resource_type, "");
+        assert_eq!(decision.checked_tuple.object, "no-colon");
+
+        let decision = resolve_check("default", "alice@example.com", "DELETE", "spaceship:s1");
+        assert_eq!(decision.reason.as_deref(), Some("Unknown resource type: spaceship"));
+        assert_eq!(decision.resource_type, "spaceship");
+        assert_eq!(decision.checked_tuple.relation, "DELETE");
+
+        // Legacy keys are checked against the type they now refer to
+        let decision = resolve_check("default", "alice@example.com", "GET", "serviceaccounts:sa1");
+        assert_eq!(decision.reason, None);
+        assert_eq!(decision.resource_type, "service_accounts");
+        assert_eq!(decision.checked_tuple.object, "service_accounts:sa1");
+    }
+
+    #[test]
+    fn test_allow_all_grants_every_method() {
+        let model: serde_json::Value =
+            serde_json::from_str(schema::get_authorization_model_json()).unwrap();
+        let dashboard = model["type_definitions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|def| def["type"] == "dashboard")
+            .unwrap();
+        let cases = [
+            ("GET", "dashboard:d1"),
+            ("GET", "dashboard:_all_default"),
+            ("POST", "dashboard:d1"),
+            ("PUT", "dashboard:d1"),
+            ("DELETE", "dashboard:d1"),
+        ];
+        for (method, object) in cases {
+            let decision = resolve_check("default", "alice@example.com", method, object);
+
+            // The checked relation is a model relation that unions ALLOW_ALL
+            let relation = &dashboard["relations"][&decision.checked_tuple.relation];
+            let children = relation["union"]["child"].as_array().unwrap();
+            assert!(
+                children.iter().any(|c| c["computedUserset"]["relation"] == "ALLOW_ALL"),
+                "{} {}",
+                method,
+                object
+            );
+        }
+    }
+
+    #[test]
+    fn test_filter_org_objects() {
+        let objects = vec![
+            "dashboard:d1".to_string(),
+            "dashboard:_all_default".to_string(),
+            "dashboard:_all_other".to_string(),
+            "dfolder:f1".to_string(),
+            "dashboard:d1".to_string(),
+            "malformed".to_string(),
+        ];
+        let filtered = filter_org_objects(objects, "default", "dashboard");
+        assert_eq!(filtered, vec!["dashboard:d1", "dashboard:_all_default"]);
+        assert_eq!(strip_object_type(filtered, "dashboard"), vec!["d1", "_all_default"]);
+    }
+
+    #[test]
+    fn test_resolve_check_folder_scoped() {
+        let decision = resolve_check("default", "alice@example.com", "GET", "dashboard:myfolder/mydash");
+        assert_eq!(decision.reason, None);
+        assert_eq!(decision.resource_type, "dashboard");
+        assert!(!decision.is_list);
+        assert_eq!(
+            decision.checked_tuple,
+            TupleKey::new("user:alice@example.com", "GET", "dashboard:mydash")
+        );
+        assert_eq!(
+            decision.parent_tuple,
+            Some(TupleKey::new("dfolder:myfolder", "parent", "dashboard:mydash"))
+        );
+
+        let decision = resolve_check("default", "alice@example.com", "PUT", "alert:ops/cpu_high");
+        assert_eq!(decision.checked_tuple.object, "alert:cpu_high");
+        assert_eq!(
+            decision.parent_tuple,
+            Some(TupleKey::new("afolder:ops", "parent", "alert:cpu_high"))
+        );
+
+        let decision = resolve_check("default", "alice@example.com", "GET", "report:a/b/c");
+        assert_eq!(decision.reason.as_deref(), Some("Invalid object format: report:a/b/c"));
+
+        let decision = resolve_check("default", "alice@example.com", "GET", "dashboard:mydash");
+        assert_eq!(decision.parent_tuple, None);
+    }
+
+    #[test]
+    fn test_root_bypass_configured_emails() {
+        let config = OpenFGAConfig::default()
+            .with_root_emails(["Root@Example.com".to_string()].into_iter().collect())
+            .with_trust_root_role_string(false);
+        // Decided on the user ID, whatever role the caller passes
+        assert_eq!(root_bypass_decision(&config, "root@example.com", "viewer"), RootBypass::Bypass);
+        assert_eq!(root_bypass_decision(&config, "root@example.com", ""), RootBypass::Bypass);
+        assert_eq!(root_bypass_decision(&config, "alice@example.com", "admin"), RootBypass::Check);
+    }
+
+    #[test]
+    fn test_root_bypass_trusted() {
+        let config = OpenFGAConfig::default().with_trust_root_role_string(true);
+        assert_eq!(
+            root_bypass_decision(&config, "alice@example.com", "root"),
+            RootBypass::TrustedRoleString
+        );
+        assert_eq!(
+            root_bypass_decision(&config, "alice@example.com", "Root"),
+            RootBypass::TrustedRoleString
+        );
+        assert_eq!(root_bypass_decision(&config, "alice@example.com", "admin"), RootBypass::Check);
+    }
+
+    #[test]
+    fn test_root_bypass_untrusted_requires_verification() {
+        let config = OpenFGAConfig::default().with_trust_root_role_string(false);
+        assert_eq!(root_bypass_decision(&config, "alice@example.com", "root"), RootBypass::Verify);
+        assert_eq!(root_bypass_decision(&config, "alice@example.com", "ROOT"), RootBypass::Verify);
+        assert_eq!(root_bypass_decision(&config, "alice@example.com", "viewer"), RootBypass::Check);
+    }
+
+    #[test]
+    fn test_root_admin_cache() {
+        let clock = crate::common::clock::MockClock::new(1_700_000_000);
+        assert_eq!(cached_root_admin("cache-test@example.com", &clock), None);
+
+        ROOT_ADMIN_CACHE.insert("cache-test@example.com".to_string(), (false, clock.timestamp()));
+        assert_eq!(cached_root_admin("cache-test@example.com", &clock), Some(false));
+
+        // Expired entries are ignored
+        clock.advance(std::time::Duration::from_secs(ROOT_ADMIN_CACHE_TTL_SECS as u64));
+        assert_eq!(cached_root_admin("cache-test@example.com", &clock), None);
+    }
 }
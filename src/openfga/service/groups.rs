@@ -7,24 +7,41 @@
 
 //! Group management service
 
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 use crate::Visdata;
-use crate::common::generate_id;
+use crate::common::deterministic_id;
 use super::super::error::{Error, Result};
 use super::super::model::schema;
-use super::super::types::{TupleKey, TupleKeyFilter, GroupResponse};
+use super::super::types::{Tuple, TupleKey, TupleKeyFilter, GroupListOptions, GroupResponse};
 use super::tuples;
 
+/// Relation storing a group's display name as a `literal:` user
+const DISPLAY_NAME_RELATION: &str = "display_name";
+
+/// Relation storing a group's description as a `literal:` user
+const DESCRIPTION_RELATION: &str = "description";
+
+/// Maximum metadata value length in bytes
+///
+/// OpenFGA caps user identifiers at 256 characters; base64 encoding grows the
+/// value by a third, so this keeps `literal:{value}` under the limit.
+const MAX_METADATA_BYTES: usize = 180;
+
 /// Create a new group
 pub async fn create_group(
     org_id: &str,
     name: &str,
-    _display_name: Option<&str>,
-    _description: Option<&str>,
+    display_name: Option<&str>,
+    description: Option<&str>,
 ) -> Result<String> {
-    // Check if group already exists
-    let existing = list_groups(org_id).await?;
+    // Check if group already exists; the name prefix narrows the owningOrg
+    // read to candidates that can match case-insensitively
+    let options = GroupListOptions {
+        name_prefix: Some(name.to_string()),
+        ..Default::default()
+    };
+    let existing = list_groups_with_options(org_id, &options).await?;
     if existing.iter().any(|g| g.eq_ignore_ascii_case(name)) {
         return Err(Error::DuplicateEntry(format!(
             "Group '{}' already exists",
@@ -32,61 +49,104 @@ pub async fn create_group(
         )));
     }
 
-    // Generate ID for the group
-    let id = generate_id();
+    let id = group_id(org_id, name);
 
     // Create the group by writing an owningOrg tuple
     // This marks the group as existing in the organization
     let group_object = schema::group_type(org_id, name);
     let org_object = format!("org:{}", org_id);
 
-    let tuple = TupleKey {
+    let mut writes = metadata_tuples(&group_object, display_name, description)?;
+    writes.push(TupleKey {
         user: org_object,
         relation: "owningOrg".to_string(),
         object: group_object,
-    };
+    });
 
-    tuples::update_tuples(vec![tuple], vec![]).await?;
+    tuples::update_tuples(writes, vec![]).await?;
 
     tracing::info!("[RBAC] Created group: {} (id: {}) in org {}", name, id, org_id);
 
     Ok(id)
 }
 
+/// Create a group unless it already exists
+///
+/// Returns `true` if the group was created and `false` if it already
+/// existed, so bootstrap scripts can run repeatedly.
+pub async fn ensure_group(org_id: &str, name: &str) -> Result<bool> {
+    match create_group(org_id, name, None, None).await {
+        Ok(_) => Ok(true),
+        Err(Error::DuplicateEntry(_)) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
 /// List all groups in an organization
+///
+/// Reads only the org's `owningOrg` group tuples. Groups that only have
+/// members need `list_groups_with_options` with `include_member_only`, which
+/// scans the whole store.
 pub async fn list_groups(org_id: &str) -> Result<Vec<String>> {
+    list_groups_with_options(org_id, &GroupListOptions::default()).await
+}
+
+/// List groups in an organization with prefix filtering and pagination
+///
+/// By default only the org's `owningOrg` group tuples are read, through
+/// `read_by_object_type`, so OpenFGA does the filtering server-side.
+/// `include_member_only` also reads `member` tuples on every group; OpenFGA
+/// cannot filter those by object type without a user, so that scans the
+/// whole store.
+pub async fn list_groups_with_options(
+    org_id: &str,
+    options: &GroupListOptions,
+) -> Result<Vec<String>> {
     let visdata = Visdata::global();
 
-    // Read all tuples without filter (OpenFGA requires object type in filter)
-    let all_tuples = visdata.openfga().read(None).await?;
+    let filter = TupleKeyFilter {
+        user: (!options.include_member_only).then(|| schema::org_type(org_id)),
+        relation: (!options.include_member_only).then(|| "owningOrg".to_string()),
+        object: None,
+    };
+    let tuples = visdata.openfga().read_by_object_type("group", filter).await?;
+
+    Ok(filter_group_names(org_id, &tuples, options))
+}
 
-    // Extract unique group names for this org
+/// Extract, filter, sort and paginate group names from group tuples
+///
+/// Groups are found by their `owningOrg` tuple, and also by `member` tuples
+/// when `include_member_only` is set.
+pub fn filter_group_names(
+    org_id: &str,
+    tuples: &[Tuple],
+    options: &GroupListOptions,
+) -> Vec<String> {
     let prefix = format!("group:{}_", org_id);
-    let org_user = format!("org:{}", org_id);
-    let mut groups: HashSet<String> = HashSet::new();
-
-    for tuple in all_tuples {
-        // Find groups by owningOrg relation (created groups)
-        if tuple.key.relation == "owningOrg"
-            && tuple.key.user == org_user
-            && tuple.key.object.starts_with(&prefix)
-        {
-            if let Some(group_name) = tuple.key.object.strip_prefix(&prefix) {
-                groups.insert(group_name.to_string());
-            }
-        }
-        // Also include groups that have members (for backward compatibility)
-        if tuple.key.relation == "member" && tuple.key.object.starts_with(&prefix) {
-            if let Some(group_name) = tuple.key.object.strip_prefix(&prefix) {
-                groups.insert(group_name.to_string());
-            }
-        }
-    }
+    let org_user = schema::org_type(org_id);
+    let name_prefix = options.name_prefix.as_deref().map(str::to_lowercase);
 
-    let mut result: Vec<String> = groups.into_iter().collect();
-    result.sort();
+    let groups: BTreeSet<&str> = tuples
+        .iter()
+        .filter(|t| {
+            (t.key.relation == "owningOrg" && t.key.user == org_user)
+                || (options.include_member_only && t.key.relation == "member")
+        })
+        .filter_map(|t| t.key.object.strip_prefix(&prefix))
+        .filter(|name| {
+            name_prefix
+                .as_deref()
+                .is_none_or(|p| name.to_lowercase().starts_with(p))
+        })
+        .collect();
 
-    Ok(result)
+    groups
+        .into_iter()
+        .skip(options.offset)
+        .take(options.limit.unwrap_or(usize::MAX))
+        .map(str::to_string)
+        .collect()
 }
 
 /// Get group details
@@ -94,19 +154,21 @@ pub async fn get_group(org_id: &str, group_name: &str) -> Result<GroupResponse>
     let visdata = Visdata::global();
     let group_object = schema::group_type(org_id, group_name);
 
-    // Get all members of the group
-    let member_filter = TupleKeyFilter {
+    // Get all tuples on the group (members, metadata and ownership)
+    let group_filter = TupleKeyFilter {
         user: None,
-        relation: Some("member".to_string()),
+        relation: None,
         object: Some(group_object.clone()),
     };
 
-    let member_tuples = visdata.openfga().read(Some(member_filter)).await?;
+    let group_tuples = visdata.openfga().read(Some(group_filter)).await?;
 
-    let users: Vec<String> = member_tuples
-        .into_iter()
+    let users: Vec<String> = group_tuples
+        .iter()
+        .filter(|t| t.key.relation == "member")
         .filter_map(|t| t.key.user.strip_prefix("user:").map(|s| s.to_string()))
         .collect();
+    let (display_name, description) = group_metadata(&group_tuples);
 
     // Get all roles assigned to the group
     // Note: Uses "grp_assigned" relation to match the OpenFGA model in store.yaml
@@ -127,29 +189,18 @@ pub async fn get_group(org_id: &str, group_name: &str) -> Result<GroupResponse>
         })
         .collect();
 
-    // Check if group exists (has any members or roles)
-    if users.is_empty() && roles.is_empty() {
-        // Group might not exist - check by looking for any tuple mentioning it
-        let any_filter = TupleKeyFilter {
-            user: None,
-            relation: None,
-            object: Some(group_object),
-        };
-
-        let any_tuples = visdata.openfga().read(Some(any_filter)).await?;
-
-        if any_tuples.is_empty() {
-            return Err(Error::GroupNotFound(group_name.to_string()));
-        }
+    // Group exists if any tuple mentions it
+    if group_tuples.is_empty() && roles.is_empty() {
+        return Err(Error::GroupNotFound(group_name.to_string()));
     }
 
     let now = chrono::Utc::now().timestamp_micros();
 
     Ok(GroupResponse {
-        id: generate_id(), // Generate consistent ID
+        id: group_id(org_id, group_name),
         name: group_name.to_string(),
-        display_name: Some(capitalize(group_name)),
-        description: None,
+        display_name: display_name.or_else(|| Some(capitalize(group_name))),
+        description,
         roles,
         users,
         created_at: now,
@@ -157,6 +208,91 @@ pub async fn get_group(org_id: &str, group_name: &str) -> Result<GroupResponse>
     })
 }
 
+/// Update a group's display name and description
+///
+/// `None` leaves a field unchanged, `Some("")` clears it.
+pub async fn update_group_metadata(
+    org_id: &str,
+    group_name: &str,
+    display_name: Option<&str>,
+    description: Option<&str>,
+) -> Result<()> {
+    let visdata = Visdata::global();
+    let group_object = schema::group_type(org_id, group_name);
+
+    let group_filter = TupleKeyFilter {
+        user: None,
+        relation: None,
+        object: Some(group_object.clone()),
+    };
+    let group_tuples = visdata.openfga().read(Some(group_filter)).await?;
+    if group_tuples.is_empty() {
+        return Err(Error::GroupNotFound(group_name.to_string()));
+    }
+
+    let writes = metadata_tuples(&group_object, display_name, description)?;
+    let deletes: Vec<TupleKey> = group_tuples
+        .into_iter()
+        .map(|t| t.key)
+        .filter(|key| {
+            (key.relation == DISPLAY_NAME_RELATION && display_name.is_some())
+                || (key.relation == DESCRIPTION_RELATION && description.is_some())
+        })
+        .collect();
+
+    if writes.is_empty() && deletes.is_empty() {
+        return Ok(());
+    }
+
+    tuples::update_tuples(writes, deletes).await?;
+
+    tracing::info!("[RBAC] Updated metadata of group {} in org {}", group_name, org_id);
+
+    Ok(())
+}
+
+/// Stable group ID derived from the organization and group name
+fn group_id(org_id: &str, group_name: &str) -> String {
+    deterministic_id(&format!("group:{}", org_id), group_name)
+}
+
+/// Build the metadata tuples for a group, skipping unset or empty values
+fn metadata_tuples(
+    group_object: &str,
+    display_name: Option<&str>,
+    description: Option<&str>,
+) -> Result<Vec<TupleKey>> {
+    let mut tuples = Vec::new();
+    for (relation, value) in [
+        (DISPLAY_NAME_RELATION, display_name),
+        (DESCRIPTION_RELATION, description),
+    ] {
+        let Some(value) = value.filter(|v| !v.is_empty()) else {
+            continue;
+        };
+        if value.len() > MAX_METADATA_BYTES {
+            return Err(Error::Validation(format!(
+                "Group {} must be at most {} bytes",
+                relation.replace('_', " "),
+                MAX_METADATA_BYTES
+            )));
+        }
+        tuples.push(TupleKey::new(schema::literal_type(value), relation, group_object));
+    }
+    Ok(tuples)
+}
+
+/// Extract the display name and description from a group's tuples
+fn group_metadata(group_tuples: &[Tuple]) -> (Option<String>, Option<String>) {
+    let value_of = |relation: &str| {
+        group_tuples
+            .iter()
+            .filter(|t| t.key.relation == relation)
+            .find_map(|t| schema::parse_literal(&t.key.user))
+    };
+    (value_of(DISPLAY_NAME_RELATION), value_of(DESCRIPTION_RELATION))
+}
+
 /// Delete a group
 pub async fn delete_group(org_id: &str, group_name: &str) -> Result<()> {
     let visdata = Visdata::global();
@@ -195,6 +331,14 @@ pub async fn delete_group(org_id: &str, group_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Check whether a user is a direct member of a group
+///
+/// Uses a single read filtered on the exact membership tuple.
+pub async fn is_group_member(org_id: &str, group_name: &str, user_email: &str) -> Result<bool> {
+    let tuple = tuples::get_group_member_tuple(org_id, group_name, user_email);
+    tuples::tuple_exists(&tuple).await
+}
+
 /// Add users to a group
 pub async fn add_group_users(
     org_id: &str,
@@ -267,6 +411,73 @@ pub async fn remove_group_roles(
     tuples::update_tuples(vec![], deletes).await
 }
 
+/// Nest `child_group` inside `parent_group`
+///
+/// Members of the child inherit the parent's roles. Returns
+/// `Error::Validation` if the nesting would create a cycle.
+pub async fn add_subgroup(org_id: &str, parent_group: &str, child_group: &str) -> Result<()> {
+    let edges = read_subgroup_edges(org_id).await?;
+    if would_create_cycle(&edges, parent_group, child_group) {
+        return Err(Error::Validation(format!(
+            "Adding group '{}' to '{}' would create a cycle",
+            child_group, parent_group
+        )));
+    }
+
+    let tuple = tuples::get_subgroup_tuple(org_id, parent_group, child_group);
+    tuples::update_tuples(vec![tuple], vec![]).await
+}
+
+/// Remove `child_group` from `parent_group`
+pub async fn remove_subgroup(org_id: &str, parent_group: &str, child_group: &str) -> Result<()> {
+    let tuple = tuples::get_subgroup_tuple(org_id, parent_group, child_group);
+    tuples::update_tuples(vec![], vec![tuple]).await
+}
+
+/// Read the org's group nesting as (child, parent) group name pairs
+async fn read_subgroup_edges(org_id: &str) -> Result<Vec<(String, String)>> {
+    let visdata = Visdata::global();
+    let filter = TupleKeyFilter {
+        user: None,
+        relation: Some("member".to_string()),
+        object: None,
+    };
+    let member_tuples = visdata.openfga().read(Some(filter)).await?;
+    Ok(subgroup_edges(org_id, &member_tuples))
+}
+
+/// Extract (child, parent) group name pairs from `group#member member group` tuples
+fn subgroup_edges(org_id: &str, member_tuples: &[Tuple]) -> Vec<(String, String)> {
+    let prefix = format!("group:{}_", org_id);
+    member_tuples
+        .iter()
+        .filter_map(|t| {
+            let child = t.key.user.strip_suffix("#member")?.strip_prefix(&prefix)?;
+            let parent = t.key.object.strip_prefix(&prefix)?;
+            Some((child.to_string(), parent.to_string()))
+        })
+        .collect()
+}
+
+/// Groups reachable from `groups` by following nesting upwards, including `groups`
+fn ancestor_groups(edges: &[(String, String)], groups: &[String]) -> HashSet<String> {
+    let mut found: HashSet<String> = groups.iter().cloned().collect();
+    let mut pending: Vec<&str> = groups.iter().map(String::as_str).collect();
+    while let Some(group) = pending.pop() {
+        for (child, parent) in edges {
+            if child == group && found.insert(parent.clone()) {
+                pending.push(parent);
+            }
+        }
+    }
+    found
+}
+
+/// Whether nesting `child` inside `parent` would create a cycle
+fn would_create_cycle(edges: &[(String, String)], parent: &str, child: &str) -> bool {
+    parent == child || ancestor_groups(edges, &[parent.to_string()]).contains(child)
+}
+
 /// Get all groups a user belongs to
 pub async fn get_user_groups(org_id: &str, user_email: &str) -> Result<Vec<String>> {
     let visdata = Visdata::global();
@@ -298,50 +509,57 @@ pub async fn get_user_roles(org_id: &str, user_email: &str) -> Result<Vec<String
     let visdata = Visdata::global();
     let user = schema::user_type(user_email);
 
-    // Get directly assigned roles
-    // Note: Uses "assigned" to match the OpenFGA model in store.yaml
-    let direct_filter = TupleKeyFilter {
-        user: Some(user.clone()),
-        relation: Some("assigned".to_string()),
+    // The user's own tuples: direct role assignments and group memberships
+    let user_filter = TupleKeyFilter {
+        user: Some(user),
+        relation: None,
         object: None,
     };
+    let user_tuples = visdata.openfga().read(Some(user_filter)).await?;
+
+    // One read for all group role assignments and nesting, joined in memory,
+    // instead of one read per group
+    let group_tuples = if user_tuples.iter().any(|t| t.key.relation == "member") {
+        visdata.openfga().read(None).await?
+    } else {
+        Vec::new()
+    };
 
-    let direct_tuples = visdata.openfga().read(Some(direct_filter)).await?;
+    Ok(resolve_user_roles(org_id, &user_tuples, &group_tuples))
+}
 
+/// Resolve a user's roles from their own tuples and the store's group tuples
+///
+/// Combines direct `assigned` roles with `grp_assigned` roles of every group
+/// the user belongs to, directly or through nesting. Returns sorted role names.
+fn resolve_user_roles(org_id: &str, user_tuples: &[Tuple], group_tuples: &[Tuple]) -> Vec<String> {
     let role_prefix = format!("role:{}_", org_id);
-    let mut roles: HashSet<String> = direct_tuples
-        .into_iter()
-        .filter_map(|t| {
-            t.key.object.strip_prefix(&role_prefix).map(|s| s.to_string())
-        })
-        .collect();
-
-    // Get roles from group memberships
-    let groups = get_user_groups(org_id, user_email).await?;
-
-    for group_name in groups {
-        let group_object = schema::group_type(org_id, &group_name);
-
-        // Note: Uses "grp_assigned" to match the OpenFGA model in store.yaml
-        let group_role_filter = TupleKeyFilter {
-            user: Some(group_object),
-            relation: Some("grp_assigned".to_string()),
-            object: None,
-        };
-
-        let group_role_tuples = visdata.openfga().read(Some(group_role_filter)).await?;
-
-        for tuple in group_role_tuples {
-            if let Some(role_name) = tuple.key.object.strip_prefix(&role_prefix) {
-                roles.insert(role_name.to_string());
-            }
+    let group_prefix = format!("group:{}_", org_id);
+
+    // Note: Uses "assigned"/"grp_assigned" to match the OpenFGA model in store.yaml
+    let mut roles: BTreeSet<String> = BTreeSet::new();
+    let mut groups: Vec<String> = Vec::new();
+    for tuple in user_tuples {
+        match tuple.key.relation.as_str() {
+            "assigned" => roles.extend(tuple.key.object.strip_prefix(&role_prefix).map(str::to_string)),
+            "member" => groups.extend(tuple.key.object.strip_prefix(&group_prefix).map(str::to_string)),
+            _ => {}
         }
     }
 
-    let mut result: Vec<String> = roles.into_iter().collect();
-    result.sort();
+    let groups = ancestor_groups(&subgroup_edges(org_id, group_tuples), &groups);
+    for tuple in group_tuples.iter().filter(|t| t.key.relation == "grp_assigned") {
+        let in_group = tuple
+            .key
+            .user
+            .strip_prefix(&group_prefix)
+            .is_some_and(|group| groups.contains(group));
+        if in_group {
+            roles.extend(tuple.key.object.strip_prefix(&role_prefix).map(str::to_string));
+        }
+    }
 
-    Ok(result)
+    roles.into_iter().collect()
 }
 
 /// Capitalize first letter
@@ -362,4 +580,173 @@ mod tests {
         assert_eq!(capitalize("developers"), "Developers");
         assert_eq!(capitalize(""), "");
     }
+
+    #[test]
+    fn test_filter_group_names() {
+        let tuple = |user: &str, relation: &str, object: &str| Tuple {
+            key: TupleKey::new(user, relation, object),
+            timestamp: None,
+        };
+        let tuples = vec![
+            tuple("org:default", "owningOrg", "group:default_ops"),
+            tuple("org:default", "owningOrg", "group:default_dev"),
+            tuple("org:default", "owningOrg", "group:default_DevOps"),
+            tuple("user:a@b.com", "member", "group:default_dev"),
+            tuple("user:a@b.com", "member", "group:default_legacy"),
+            tuple("org:other", "owningOrg", "group:other_qa"),
+            tuple("org:default", "owningOrg", "role:default_dev"),
+        ];
+
+        let all = filter_group_names("default", &tuples, &GroupListOptions::default());
+        assert_eq!(all, vec!["DevOps", "dev", "ops"]);
+
+        let options = GroupListOptions {
+            include_member_only: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            filter_group_names("default", &tuples, &options),
+            vec!["DevOps", "dev", "legacy", "ops"]
+        );
+
+        let options = GroupListOptions {
+            name_prefix: Some("DEV".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(filter_group_names("default", &tuples, &options), vec!["DevOps", "dev"]);
+
+        let options = GroupListOptions {
+            limit: Some(1),
+            offset: 1,
+            ..Default::default()
+        };
+        assert_eq!(filter_group_names("default", &tuples, &options), vec!["dev"]);
+    }
+
+    #[test]
+    fn test_group_id_is_stable() {
+        assert_eq!(group_id("default", "developers"), group_id("default", "developers"));
+        assert_ne!(group_id("default", "developers"), group_id("other", "developers"));
+    }
+
+    fn edges(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(child, parent)| (child.to_string(), parent.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_subgroup_edges() {
+        let tuple = |user: &str, object: &str| Tuple {
+            key: TupleKey::new(user, "member", object),
+            timestamp: None,
+        };
+        let tuples = vec![
+            tuple("group:default_backend#member", "group:default_eng"),
+            tuple("user:a@b.com", "group:default_backend"),
+            tuple("group:other_x#member", "group:other_y"),
+        ];
+        assert_eq!(
+            subgroup_edges("default", &tuples),
+            edges(&[("backend", "eng")])
+        );
+    }
+
+    #[test]
+    fn test_ancestor_groups() {
+        let edges = edges(&[("backend", "eng"), ("eng", "all"), ("ops", "all")]);
+        let found = ancestor_groups(&edges, &["backend".to_string()]);
+        let expected: HashSet<String> =
+            ["backend", "eng", "all"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_would_create_cycle() {
+        let edges = edges(&[("backend", "eng"), ("eng", "all")]);
+        assert!(would_create_cycle(&edges, "backend", "all"));
+        assert!(would_create_cycle(&edges, "eng", "eng"));
+        assert!(!would_create_cycle(&edges, "all", "ops"));
+        assert!(!would_create_cycle(&edges, "eng", "frontend"));
+    }
+
+    #[test]
+    fn test_resolve_user_roles_across_groups() {
+        let tuple = |user: &str, relation: &str, object: &str| Tuple {
+            key: TupleKey::new(user, relation, object),
+            timestamp: None,
+        };
+        let user_tuples = vec![
+            tuple("user:a@b.com", "assigned", "role:default_auditor"),
+            tuple("user:a@b.com", "member", "group:default_backend"),
+            tuple("user:a@b.com", "member", "group:default_oncall"),
+            tuple("user:a@b.com", "member", "group:default_security"),
+            tuple("user:a@b.com", "member", "group:other_ops"),
+        ];
+        let group_tuples = vec![
+            tuple("group:default_backend", "grp_assigned", "role:default_developer"),
+            tuple("group:default_oncall", "grp_assigned", "role:default_operator"),
+            tuple("group:default_security", "grp_assigned", "role:default_auditor"),
+            tuple("group:default_security", "grp_assigned", "role:default_reviewer"),
+            tuple("group:default_frontend", "grp_assigned", "role:default_designer"),
+            tuple("group:other_ops", "grp_assigned", "role:other_admin"),
+        ];
+
+        assert_eq!(
+            resolve_user_roles("default", &user_tuples, &group_tuples),
+            vec!["auditor", "developer", "operator", "reviewer"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_user_roles_through_nested_group() {
+        let tuple = |user: &str, relation: &str, object: &str| Tuple {
+            key: TupleKey::new(user, relation, object),
+            timestamp: None,
+        };
+        let user_tuples = vec![tuple("user:a@b.com", "member", "group:default_backend")];
+        let group_tuples = vec![
+            tuple("group:default_backend#member", "member", "group:default_eng"),
+            tuple("group:default_eng", "grp_assigned", "role:default_viewer_plus"),
+        ];
+
+        assert_eq!(
+            resolve_user_roles("default", &user_tuples, &group_tuples),
+            vec!["viewer_plus"]
+        );
+    }
+
+    #[test]
+    fn test_metadata_tuples_round_trip() {
+        let group = "group:default_sre";
+        let writes = metadata_tuples(group, Some("Site Reliability"), Some("")).unwrap();
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].relation, DISPLAY_NAME_RELATION);
+        assert_eq!(writes[0].object, group);
+
+        let tuples: Vec<Tuple> = writes
+            .into_iter()
+            .chain(metadata_tuples(group, None, Some("Owns paging and runbooks")).unwrap())
+            .chain([TupleKey::new("user:a@b.com", "member", group)])
+            .map(|key| Tuple { key, timestamp: None })
+            .collect();
+        assert_eq!(
+            group_metadata(&tuples),
+            (
+                Some("Site Reliability".to_string()),
+                Some("Owns paging and runbooks".to_string())
+            )
+        );
+        assert_eq!(group_metadata(&[]), (None, None));
+    }
+
+    #[test]
+    fn test_metadata_tuples_rejects_long_values() {
+        let long = "x".repeat(MAX_METADATA_BYTES + 1);
+        assert!(matches!(
+            metadata_tuples("group:default_sre", None, Some(&long)),
+            Err(Error::Validation(_))
+        ));
+    }
 }
@@ -7,22 +7,27 @@
 
 //! RBAC service layer
 
+pub mod cache;
 pub mod checker;
 pub mod tuples;
 pub mod roles;
 pub mod groups;
 
 // Re-export checker functions
-pub use checker::{is_allowed, check_permissions, list_objects_for_user};
+pub use checker::{is_allowed, is_allowed_with_consistency, is_allowed_explained, check_permissions, authorize, list_objects_for_user, list_permitted_objects, missing_permissions, check_menu_access};
 
 // Re-export tuples functions
 pub use tuples::{
-    update_tuples, get_add_user_to_org_tuples, get_user_crole_tuple,
-    get_org_creation_tuples, get_ownership_tuple, get_resource_parent_tuple,
-    get_org_resource_permission_tuple, get_group_member_tuple, get_group_role_tuple,
+    update_tuples, dedupe_tuple_changes, tuple_exists, get_add_user_to_org_tuples, get_user_crole_tuple,
+    get_org_creation_tuples, write_resource_type_bootstrap_tuples, register_resource_for_org,
+    get_ownership_tuple, get_resource_parent_tuple, get_folder_parent_tuple,
+    get_org_resource_permission_tuple, get_group_member_tuple, get_subgroup_tuple,
+    get_group_role_tuple, get_public_access_tuple,
     get_service_account_creation_tuple, get_delete_user_from_org_tuples,
     // System role sync functions
-    role_to_fga_relation, get_delete_user_system_role_tuples,
+    role_to_fga_relation, resolve_role_relation, default_role_relations,
+    validate_role_relation_overrides, system_role_relations, diff_add_user_to_org_tuples,
+    get_delete_user_system_role_tuples,
     update_user_role, get_delete_all_user_from_org_tuples,
     // Custom role functions
     get_role_key, get_user_crole_removal_tuples,
@@ -30,15 +35,20 @@ pub use tuples::{
 
 // Re-export roles functions
 pub use roles::{
-    create_role, list_roles, list_system_roles, list_custom_roles,
-    delete_role, get_role_users, get_role_permissions,
-    add_role_permissions, remove_role_permissions,
-    add_role_users, remove_role_users,
+    create_role, ensure_role, list_roles, list_roles_with_options, filter_role_names,
+    list_roles_with_summary, summarize_roles,
+    list_system_roles, list_custom_roles,
+    delete_role, clone_role, clone_role_tuples, get_role_users, get_role_permissions,
+    get_role_full, group_permissions_by_resource_type,
+    update_role, role_update_tuples, add_role_permissions, remove_role_permissions,
+    add_role_users, remove_role_users, is_role_assigned,
 };
 
 // Re-export groups functions
 pub use groups::{
-    create_group, list_groups, get_group, delete_group,
+    create_group, ensure_group, list_groups, list_groups_with_options, filter_group_names,
+    get_group, update_group_metadata, delete_group,
     add_group_users, remove_group_users, add_group_roles, remove_group_roles,
+    add_subgroup, remove_subgroup, is_group_member,
     get_user_groups, get_user_roles,
 };
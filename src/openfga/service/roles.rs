@@ -7,12 +7,15 @@
 
 //! Role management service
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use crate::Visdata;
 use super::super::error::{Error, Result};
-use super::super::model::schema;
-use super::super::types::{TupleKey, TupleKeyFilter, PermissionEntry, UserRoleOption};
+use super::super::model::{resources, schema};
+use super::super::types::{
+    Permission, Tuple, TupleKey, TupleKeyFilter, PermissionEntry, ResourceObject, RoleFullResponse,
+    RoleListOptions, RoleSummary, UserRoleOption,
+};
 use super::tuples;
 
 /// System roles that cannot be deleted
@@ -55,37 +58,134 @@ pub async fn create_role(org_id: &str, role_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Create a role unless it already exists
+///
+/// Returns `true` if the role was created and `false` if it already existed,
+/// so bootstrap scripts can run repeatedly. System role names are still
+/// rejected.
+pub async fn ensure_role(org_id: &str, role_name: &str) -> Result<bool> {
+    match create_role(org_id, role_name).await {
+        Ok(()) => Ok(true),
+        Err(Error::DuplicateEntry(_)) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
 /// List all roles in an organization (excludes system roles - they are fixed)
 pub async fn list_roles(org_id: &str) -> Result<Vec<String>> {
+    list_roles_with_options(org_id, &RoleListOptions::default()).await
+}
+
+/// List custom roles in an organization with prefix filtering and pagination
+///
+/// Only the org's `owningOrg` role tuples are read, through
+/// `read_by_object_type`, so OpenFGA does the filtering server-side.
+pub async fn list_roles_with_options(
+    org_id: &str,
+    options: &RoleListOptions,
+) -> Result<Vec<String>> {
     let visdata = Visdata::global();
 
-    // Read all tuples without filter (OpenFGA requires object type in filter)
+    let filter = TupleKeyFilter {
+        user: Some(schema::org_type(org_id)),
+        relation: Some("owningOrg".to_string()),
+        object: None,
+    };
+    let role_tuples = visdata.openfga().read_by_object_type("role", filter).await?;
+
+    Ok(filter_role_names(org_id, &role_tuples, options))
+}
+
+/// Extract, filter, sort and paginate custom role names from owningOrg tuples
+pub fn filter_role_names(org_id: &str, tuples: &[Tuple], options: &RoleListOptions) -> Vec<String> {
+    let prefix = format!("role:{}_", org_id);
+    let org_user = schema::org_type(org_id);
+    let name_prefix = options.name_prefix.as_deref().map(str::to_lowercase);
+
+    let roles: BTreeSet<&str> = tuples
+        .iter()
+        .filter(|t| t.key.relation == "owningOrg" && t.key.user == org_user)
+        .filter_map(|t| t.key.object.strip_prefix(&prefix))
+        // Skip system roles - they have fixed permissions and shouldn't be edited
+        .filter(|name| !SYSTEM_ROLES.iter().any(|r| r.eq_ignore_ascii_case(name)))
+        .filter(|name| {
+            name_prefix
+                .as_deref()
+                .is_none_or(|p| name.to_lowercase().starts_with(p))
+        })
+        .collect();
+
+    roles
+        .into_iter()
+        .skip(options.offset)
+        .take(options.limit.unwrap_or(usize::MAX))
+        .map(str::to_string)
+        .collect()
+}
+
+/// List all custom roles with user counts and granted resource types
+///
+/// Uses a single read of all tuples instead of one permission query per role
+/// and resource type.
+pub async fn list_roles_with_summary(org_id: &str) -> Result<Vec<RoleSummary>> {
+    let visdata = Visdata::global();
     let all_tuples = visdata.openfga().read(None).await?;
+    Ok(summarize_roles(org_id, &all_tuples))
+}
 
-    // Extract unique role names for this org (excluding system roles)
+/// Bucket role tuples (owningOrg, assigned, #has) by role for an organization
+pub fn summarize_roles(org_id: &str, all_tuples: &[Tuple]) -> Vec<RoleSummary> {
     let prefix = format!("role:{}_", org_id);
-    let org_user = format!("org:{}", org_id);
-    let mut roles: HashSet<String> = HashSet::new();
+    let org_user = schema::org_type(org_id);
+
+    let mut roles: BTreeMap<String, (BTreeSet<String>, BTreeSet<String>)> = BTreeMap::new();
+    let mut users: Vec<(&str, &str)> = Vec::new();
+    let mut grants: Vec<(&str, &str)> = Vec::new();
 
     for tuple in all_tuples {
-        // Find roles by owningOrg relation (created roles)
-        if tuple.key.relation == "owningOrg"
-            && tuple.key.user == org_user
-            && tuple.key.object.starts_with(&prefix)
-        {
-            if let Some(role_name) = tuple.key.object.strip_prefix(&prefix) {
-                // Skip system roles - they have fixed permissions and shouldn't be edited
-                if !SYSTEM_ROLES.iter().any(|r| r.eq_ignore_ascii_case(role_name)) {
-                    roles.insert(role_name.to_string());
+        let key = &tuple.key;
+        match (key.relation.as_str(), key.object.strip_prefix(&prefix)) {
+            ("owningOrg", Some(role_name))
+                if key.user == org_user
+                    && !SYSTEM_ROLES.iter().any(|r| r.eq_ignore_ascii_case(role_name)) =>
+            {
+                roles.entry(role_name.to_string()).or_default();
+            }
+            ("assigned", Some(role_name)) => users.push((role_name, key.user.as_str())),
+            (relation, _) if relation.starts_with("ALLOW_") => {
+                let role_name = key
+                    .user
+                    .strip_suffix("#has")
+                    .and_then(|r| r.strip_prefix(&prefix));
+                let resource_type = key.object.split_once(':').map(|(t, _)| t);
+                if let (Some(role_name), Some(resource_type)) = (role_name, resource_type) {
+                    grants.push((role_name, resource_type));
                 }
             }
+            _ => {}
         }
     }
 
-    let mut result: Vec<String> = roles.into_iter().collect();
-    result.sort();
+    for (role_name, user) in users {
+        if let Some((role_users, _)) = roles.get_mut(role_name) {
+            role_users.insert(user.to_string());
+        }
+    }
+    for (role_name, resource_type) in grants {
+        if let Some((_, resource_types)) = roles.get_mut(role_name) {
+            resource_types.insert(resource_type.to_string());
+        }
+    }
 
-    Ok(result)
+    roles
+        .into_iter()
+        .map(|(name, (role_users, resource_types))| RoleSummary {
+            label: capitalize(&name),
+            name,
+            user_count: role_users.len(),
+            resource_types_granted: resource_types.into_iter().collect(),
+        })
+        .collect()
 }
 
 /// List system roles (for user assignment dropdown)
@@ -162,6 +262,100 @@ pub async fn delete_role(org_id: &str, role_name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Clone a custom role's permissions (and optionally its users) into a new role
+///
+/// Fails if `new_role` already exists or is a system role name, or if
+/// `source_role` does not exist.
+pub async fn clone_role(
+    org_id: &str,
+    source_role: &str,
+    new_role: &str,
+    copy_users: bool,
+) -> Result<()> {
+    if SYSTEM_ROLES.iter().any(|r| r.eq_ignore_ascii_case(new_role)) {
+        return Err(Error::Validation(format!(
+            "Cannot create role with system name: {}",
+            new_role
+        )));
+    }
+
+    let existing = list_roles(org_id).await?;
+    if existing.iter().any(|r| r.eq_ignore_ascii_case(new_role)) {
+        return Err(Error::DuplicateEntry(format!(
+            "Role '{}' already exists",
+            new_role
+        )));
+    }
+    if !existing.iter().any(|r| r == source_role) {
+        return Err(Error::RoleNotFound(source_role.to_string()));
+    }
+
+    let visdata = Visdata::global();
+    let source_object = schema::role_type(org_id, source_role);
+
+    // Permission grants (role#has) and assignments (object = role)
+    let mut source_tuples = visdata
+        .openfga()
+        .read(Some(TupleKeyFilter {
+            user: Some(format!("{}#has", source_object)),
+            relation: None,
+            object: None,
+        }))
+        .await?;
+    source_tuples.extend(
+        visdata
+            .openfga()
+            .read(Some(TupleKeyFilter {
+                user: None,
+                relation: None,
+                object: Some(source_object),
+            }))
+            .await?,
+    );
+
+    let writes = clone_role_tuples(org_id, source_role, new_role, &source_tuples, copy_users);
+    tuples::update_tuples(writes, vec![]).await?;
+
+    tracing::info!(
+        "[RBAC] Cloned role: {} -> {} in org {}",
+        source_role, new_role, org_id
+    );
+
+    Ok(())
+}
+
+/// Build the tuples for a cloned role from the source role's tuples
+///
+/// Always includes the `owningOrg` tuple for the new role and copies of the
+/// source's `#has` grants; user `assigned` tuples are copied when `copy_users`.
+pub fn clone_role_tuples(
+    org_id: &str,
+    source_role: &str,
+    new_role: &str,
+    source_tuples: &[Tuple],
+    copy_users: bool,
+) -> Vec<TupleKey> {
+    let source_object = schema::role_type(org_id, source_role);
+    let source_has = format!("{}#has", source_object);
+    let new_object = schema::role_type(org_id, new_role);
+    let new_has = format!("{}#has", new_object);
+
+    let mut writes = vec![TupleKey::new(schema::org_type(org_id), "owningOrg", &new_object)];
+    for tuple in source_tuples {
+        let key = &tuple.key;
+        if key.user == source_has {
+            writes.push(TupleKey::new(&new_has, &key.relation, &key.object));
+        } else if copy_users
+            && key.object == source_object
+            && key.relation == "assigned"
+            && key.user.starts_with("user:")
+        {
+            writes.push(TupleKey::new(&key.user, "assigned", &new_object));
+        }
+    }
+    writes
+}
+
 /// Get users assigned to a role
 pub async fn get_role_users(org_id: &str, role_name: &str) -> Result<Vec<String>> {
     let visdata = Visdata::global();
@@ -199,7 +393,7 @@ pub async fn get_role_permissions(
     // Use role#has relation for permission queries (as defined in store.yaml)
     let role_has = format!("{}#has", role_object);
 
-    println!(
+    tracing::debug!(
         "[RBAC] get_role_permissions: org={}, role={}, resource_type={}, role_has={}",
         org_id, role_name, resource_type, role_has
     );
@@ -213,14 +407,14 @@ pub async fn get_role_permissions(
 
     let tuples = visdata.openfga().read(Some(filter)).await?;
 
-    println!(
+    tracing::debug!(
         "[RBAC] Found {} tuples for role_has={}",
         tuples.len(), role_has
     );
 
     // Log all tuples for debugging
     for tuple in &tuples {
-        println!(
+        tracing::trace!(
             "[RBAC] Tuple: user={}, relation={}, object={}",
             tuple.key.user, tuple.key.relation, tuple.key.object
         );
@@ -245,16 +439,17 @@ pub async fn get_role_permissions(
             );
             is_this_type
         })
-        .map(|t| {
+        .filter_map(|t| {
             let permission = relation_to_permission(&t.key.relation);
 
             // Return format expected by frontend
             // Object format is already correct: "resource_type:entity_id" or "resource_type:_all_{org}"
-            let object = t.key.object.clone();
-
-            PermissionEntry {
-                object,
-                permission,
+            match t.key.object.parse() {
+                Ok(object) => Some(PermissionEntry { object, permission }),
+                Err(e) => {
+                    tracing::warn!("[RBAC] Skipping invalid permission object: {}", e);
+                    None
+                }
             }
         })
         .collect();
@@ -267,6 +462,54 @@ pub async fn get_role_permissions(
     Ok(permissions)
 }
 
+/// Get a role with its users and all permissions grouped by resource type
+///
+/// Reads the role's `#has` grants once instead of one `get_role_permissions`
+/// call per resource type.
+pub async fn get_role_full(org_id: &str, role_name: &str) -> Result<RoleFullResponse> {
+    let visdata = Visdata::global();
+    let role_has = format!("{}#has", schema::role_type(org_id, role_name));
+
+    let grants_filter = TupleKeyFilter {
+        user: Some(role_has),
+        relation: None,
+        object: None,
+    };
+    let (users, grants) = futures::future::try_join(
+        get_role_users(org_id, role_name),
+        visdata.openfga().read(Some(grants_filter)),
+    )
+    .await?;
+
+    let now = chrono::Utc::now().timestamp_micros();
+    Ok(RoleFullResponse {
+        name: role_name.to_string(),
+        label: capitalize(role_name),
+        users,
+        permissions: group_permissions_by_resource_type(&grants),
+        created_at: now,
+        updated_at: now,
+    })
+}
+
+/// Group `#has` grant tuples into permission entries keyed by resource type
+pub fn group_permissions_by_resource_type(grants: &[Tuple]) -> HashMap<String, Vec<PermissionEntry>> {
+    let mut permissions: HashMap<String, Vec<PermissionEntry>> = HashMap::new();
+    for tuple in grants {
+        match tuple.key.object.parse::<ResourceObject>() {
+            Ok(object) => permissions
+                .entry(object.resource_type().to_string())
+                .or_default()
+                .push(PermissionEntry {
+                    object,
+                    permission: relation_to_permission(&tuple.key.relation),
+                }),
+            Err(e) => tracing::warn!("[RBAC] Skipping invalid permission object: {}", e),
+        }
+    }
+    permissions
+}
+
 /// Add permissions to a role
 pub async fn add_role_permissions(
     org_id: &str,
@@ -277,33 +520,8 @@ pub async fn add_role_permissions(
         return Ok(());
     }
 
-    let role_object = schema::role_type(org_id, role_name);
-    // Use role#has relation for permission assignment (as defined in store.yaml)
-    let role_has = format!("{}#has", role_object);
-
-    let mut writes = Vec::new();
-
-    for perm in permissions {
-        // Parse object format: "resource_type:entity_id"
-        let (resource_type, entity_id) = match perm.object.split_once(':') {
-            Some(parts) => parts,
-            None => continue,
-        };
-
-        let relation = permission_to_relation(&perm.permission);
-        let resource = if entity_id.starts_with("_all") {
-            schema::resource_object_all(org_id, resource_type)
-        } else {
-            schema::resource_object(org_id, resource_type, entity_id)
-        };
-
-        println!(
-            "[RBAC] add_role_permissions: perm.object={}, resource_type={}, entity_id={}, resource={}, role_has={}, relation={}",
-            perm.object, resource_type, entity_id, resource, role_has, relation
-        );
-
-        writes.push(TupleKey::new(&role_has, relation, &resource));
-    }
+    // Validate every entry before writing anything
+    let writes = permission_tuples(org_id, role_name, permissions)?;
 
     if !writes.is_empty() {
         tuples::update_tuples(writes, vec![]).await?;
@@ -322,35 +540,112 @@ pub async fn remove_role_permissions(
         return Ok(());
     }
 
-    let role_object = schema::role_type(org_id, role_name);
-    // Use role#has relation for permission assignment (as defined in store.yaml)
-    let role_has = format!("{}#has", role_object);
+    let deletes = permission_tuples(org_id, role_name, permissions)?;
 
-    let mut deletes = Vec::new();
+    if !deletes.is_empty() {
+        tuples::update_tuples(vec![], deletes).await?;
+    }
 
-    for perm in permissions {
-        let (resource_type, entity_id) = match perm.object.split_once(':') {
-            Some(parts) => parts,
-            None => continue,
-        };
+    Ok(())
+}
 
-        let relation = permission_to_relation(&perm.permission);
-        let resource = if entity_id.starts_with("_all") {
-            schema::resource_object_all(org_id, resource_type)
-        } else {
-            schema::resource_object(org_id, resource_type, entity_id)
-        };
+/// Update a role's permissions and users in a single write
+///
+/// All entries are validated before anything is written, and the changes
+/// are sent as one OpenFGA write so the update applies entirely or not at all.
+pub async fn update_role(
+    org_id: &str,
+    role_name: &str,
+    add_permissions: &[PermissionEntry],
+    remove_permissions: &[PermissionEntry],
+    add_users: &HashSet<String>,
+    remove_users: &HashSet<String>,
+) -> Result<()> {
+    let (writes, deletes) = role_update_tuples(
+        org_id,
+        role_name,
+        add_permissions,
+        remove_permissions,
+        add_users,
+        remove_users,
+    )?;
 
-        deletes.push(TupleKey::new(&role_has, relation, &resource));
-    }
+    tuples::update_tuples(writes, deletes).await?;
 
-    if !deletes.is_empty() {
-        tuples::update_tuples(vec![], deletes).await?;
-    }
+    tracing::debug!(
+        "[RBAC] Updated role {} in org {}: +{}/-{} permissions, +{}/-{} users",
+        role_name,
+        org_id,
+        add_permissions.len(),
+        remove_permissions.len(),
+        add_users.len(),
+        remove_users.len()
+    );
 
     Ok(())
 }
 
+/// Build the writes and deletes for a role update, validating every entry
+pub fn role_update_tuples(
+    org_id: &str,
+    role_name: &str,
+    add_permissions: &[PermissionEntry],
+    remove_permissions: &[PermissionEntry],
+    add_users: &HashSet<String>,
+    remove_users: &HashSet<String>,
+) -> Result<(Vec<TupleKey>, Vec<TupleKey>)> {
+    let mut writes = permission_tuples(org_id, role_name, add_permissions)?;
+    let mut deletes = permission_tuples(org_id, role_name, remove_permissions)?;
+
+    let user_tuple = |email: &String| tuples::get_user_crole_tuple(org_id, role_name, email);
+    writes.extend(add_users.iter().map(user_tuple));
+    deletes.extend(remove_users.iter().map(user_tuple));
+
+    Ok((writes, deletes))
+}
+
+/// Build the `role#has` permission tuples for a role, failing on the first invalid entry
+fn permission_tuples(
+    org_id: &str,
+    role_name: &str,
+    permissions: &[PermissionEntry],
+) -> Result<Vec<TupleKey>> {
+    // Use role#has relation for permission assignment (as defined in store.yaml)
+    let role_has = format!("{}#has", schema::role_type(org_id, role_name));
+    permissions
+        .iter()
+        .map(|perm| permission_tuple(org_id, &role_has, perm))
+        .collect()
+}
+
+/// Build the `role#has` permission tuple for a permission entry
+///
+/// Returns `Error::InvalidResourceType` for a resource type that is neither
+/// a known type nor a legacy key and `Error::InvalidPermission` for a
+/// permission that does not parse as a `Permission`.
+fn permission_tuple(org_id: &str, role_has: &str, perm: &PermissionEntry) -> Result<TupleKey> {
+    let Some(resource_type) = resources::resolve_resource_key(perm.object.resource_type()) else {
+        return Err(Error::InvalidResourceType(perm.object.resource_type().to_string()));
+    };
+    let relation = permission_to_relation(&perm.permission)
+        .ok_or_else(|| Error::InvalidPermission(perm.permission.clone()))?;
+    let resource = if perm.object.is_all() {
+        schema::resource_object_all(org_id, resource_type)
+    } else {
+        schema::resource_object(org_id, resource_type, perm.object.entity_id())
+    };
+
+    Ok(TupleKey::new(role_has, relation, &resource))
+}
+
+/// Check whether a user is directly assigned to a role
+///
+/// Uses a single read filtered on the exact assignment tuple.
+pub async fn is_role_assigned(org_id: &str, role_name: &str, user_email: &str) -> Result<bool> {
+    let tuple = tuples::get_user_crole_tuple(org_id, role_name, user_email);
+    tuples::tuple_exists(&tuple).await
+}
+
 /// Add users to a role
 pub async fn add_role_users(
     org_id: &str,
@@ -389,31 +684,16 @@ pub async fn remove_role_users(
 
 /// Convert permission string to OpenFGA relation
 /// Maps frontend permission names to store.yaml relation names
-fn permission_to_relation(permission: &str) -> &'static str {
-    match permission.to_lowercase().as_str() {
-        "allowall" => "ALLOW_ALL",
-        "allowlist" => "ALLOW_LIST",
-        "allowget" => "ALLOW_GET",
-        "allowpost" => "ALLOW_POST",
-        "allowput" => "ALLOW_PUT",
-        "allowdelete" => "ALLOW_DELETE",
-        _ => "ALLOW_GET",
-    }
+fn permission_to_relation(permission: &str) -> Option<&'static str> {
+    permission.parse::<Permission>().ok().map(|p| p.to_allow_relation())
 }
 
 /// Convert OpenFGA relation to permission string
 /// Maps store.yaml relation names to frontend permission names
 fn relation_to_permission(relation: &str) -> String {
-    match relation {
-        "ALLOW_ALL" => "AllowAll",
-        "ALLOW_LIST" => "AllowList",
-        "ALLOW_GET" => "AllowGet",
-        "ALLOW_POST" => "AllowPost",
-        "ALLOW_PUT" => "AllowPut",
-        "ALLOW_DELETE" => "AllowDelete",
-        _ => "AllowGet",
-    }
-    .to_string()
+    Permission::from_allow_relation(relation)
+        .unwrap_or(Permission::AllowGet)
+        .to_string()
 }
 
 /// Capitalize first letter
@@ -431,16 +711,224 @@ mod tests {
 
     #[test]
     fn test_permission_conversion() {
-        assert_eq!(permission_to_relation("AllowAll"), "ALLOW_ALL");
-        assert_eq!(permission_to_relation("AllowGet"), "ALLOW_GET");
+        assert_eq!(permission_to_relation("AllowAll"), Some("ALLOW_ALL"));
+        assert_eq!(permission_to_relation("AllowGet"), Some("ALLOW_GET"));
+        assert_eq!(permission_to_relation("AllowEverything"), None);
         assert_eq!(relation_to_permission("ALLOW_ALL"), "AllowAll");
         assert_eq!(relation_to_permission("ALLOW_GET"), "AllowGet");
     }
 
+    #[test]
+    fn test_summarize_roles() {
+        let tuple = |user: &str, relation: &str, object: &str| Tuple {
+            key: TupleKey::new(user, relation, object),
+            timestamp: None,
+        };
+        let all_tuples = vec![
+            tuple("org:default", "owningOrg", "role:default_developer"),
+            tuple("org:default", "owningOrg", "role:default_auditor"),
+            tuple("org:default", "owningOrg", "role:default_admin"),
+            tuple("org:other", "owningOrg", "role:other_developer"),
+            tuple("user:alice@example.com", "assigned", "role:default_developer"),
+            tuple("user:bob@example.com", "assigned", "role:default_developer"),
+            tuple("user:carol@example.com", "assigned", "role:other_developer"),
+            tuple("role:default_developer#has", "ALLOW_ALL", "logs:_all_default"),
+            tuple("role:default_developer#has", "ALLOW_GET", "logs:my_stream"),
+            tuple("role:default_developer#has", "ALLOW_GET", "dashboard:abc"),
+            tuple("role:other_developer#has", "ALLOW_GET", "alert:_all_other"),
+        ];
+
+        let summary = summarize_roles("default", &all_tuples);
+
+        // System roles are excluded, result is sorted by name
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].name, "auditor");
+        assert_eq!(summary[0].user_count, 0);
+        assert!(summary[0].resource_types_granted.is_empty());
+
+        assert_eq!(summary[1].name, "developer");
+        assert_eq!(summary[1].label, "Developer");
+        assert_eq!(summary[1].user_count, 2);
+        assert_eq!(summary[1].resource_types_granted, vec!["dashboard", "logs"]);
+    }
+
+    #[test]
+    fn test_permission_tuple() {
+        let perm = PermissionEntry {
+            object: "logs:_all_default".parse().unwrap(),
+            permission: "AllowGet".to_string(),
+        };
+        let tuple = permission_tuple("default", "role:default_dev#has", &perm).unwrap();
+        assert_eq!(tuple.user, "role:default_dev#has");
+        assert_eq!(tuple.relation, "ALLOW_GET");
+        assert_eq!(tuple.object, "logs:_all_default");
+
+        let perm = PermissionEntry {
+            object: "dashboard:d1".parse().unwrap(),
+            permission: "AllowDelete".to_string(),
+        };
+        let tuple = permission_tuple("default", "role:default_dev#has", &perm).unwrap();
+        assert_eq!(tuple.relation, "ALLOW_DELETE");
+        assert_eq!(tuple.object, "dashboard:d1");
+    }
+
+    #[test]
+    fn test_permission_tuple_rejects_malformed_entry() {
+        let perm = PermissionEntry {
+            object: "logs:my_stream".parse().unwrap(),
+            permission: "AllowEverything".to_string(),
+        };
+        let err = permission_tuple("default", "role:default_dev#has", &perm).unwrap_err();
+        assert!(matches!(err, Error::InvalidPermission(_)));
+
+        // Malformed objects are rejected before reaching the service
+        let json = r#"{"object":"logs","permission":"AllowGet"}"#;
+        assert!(serde_json::from_str::<PermissionEntry>(json).is_err());
+    }
+
+    #[test]
+    fn test_role_update_tuples() {
+        let perm = |object: &str, permission: &str| PermissionEntry {
+            object: object.parse().unwrap(),
+            permission: permission.to_string(),
+        };
+        let users = |emails: &[&str]| emails.iter().map(|e| e.to_string()).collect::<HashSet<_>>();
+
+        let (writes, deletes) = role_update_tuples(
+            "default",
+            "dev",
+            &[perm("logs:_all_default", "AllowGet")],
+            &[perm("dashboard:d1", "AllowDelete")],
+            &users(&["a@b.com"]),
+            &users(&["c@d.com"]),
+        )
+        .unwrap();
+        assert_eq!(
+            writes,
+            vec![
+                TupleKey::new("role:default_dev#has", "ALLOW_GET", "logs:_all_default"),
+                TupleKey::new("user:a@b.com", "assigned", "role:default_dev"),
+            ]
+        );
+        assert_eq!(
+            deletes,
+            vec![
+                TupleKey::new("role:default_dev#has", "ALLOW_DELETE", "dashboard:d1"),
+                TupleKey::new("user:c@d.com", "assigned", "role:default_dev"),
+            ]
+        );
+
+        // One invalid entry rejects the whole update
+        let err = role_update_tuples(
+            "default",
+            "dev",
+            &[perm("logs:_all_default", "AllowGet")],
+            &[perm("dashboard:d1", "AllowEverything")],
+            &users(&["a@b.com"]),
+            &HashSet::new(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::InvalidPermission(_)));
+    }
+
     #[test]
     fn test_capitalize() {
         assert_eq!(capitalize("admin"), "Admin");
         assert_eq!(capitalize("viewer"), "Viewer");
         assert_eq!(capitalize(""), "");
     }
+
+    #[test]
+    fn test_filter_role_names() {
+        let tuple = |user: &str, object: &str| Tuple {
+            key: TupleKey::new(user, "owningOrg", object),
+            timestamp: None,
+        };
+        let tuples = vec![
+            tuple("org:default", "role:default_ops"),
+            tuple("org:default", "role:default_dev"),
+            tuple("org:default", "role:default_DevOps"),
+            tuple("org:default", "role:default_admin"),
+            tuple("org:other", "role:other_dev"),
+            tuple("org:default", "role:other_qa"),
+        ];
+
+        let all = filter_role_names("default", &tuples, &RoleListOptions::default());
+        assert_eq!(all, vec!["DevOps", "dev", "ops"]);
+
+        let options = RoleListOptions {
+            name_prefix: Some("DEV".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(filter_role_names("default", &tuples, &options), vec!["DevOps", "dev"]);
+
+        let options = RoleListOptions {
+            limit: Some(1),
+            offset: 1,
+            name_prefix: None,
+        };
+        assert_eq!(filter_role_names("default", &tuples, &options), vec!["dev"]);
+    }
+
+    #[test]
+    fn test_clone_role_tuples() {
+        let tuple = |user: &str, relation: &str, object: &str| Tuple {
+            key: TupleKey::new(user, relation, object),
+            timestamp: None,
+        };
+        let source = vec![
+            tuple("org:default", "owningOrg", "role:default_template"),
+            tuple("role:default_template#has", "ALLOW_GET", "dashboard:_all_default"),
+            tuple("role:default_template#has", "ALLOW_ALL", "logs:app"),
+            tuple("user:a@b.com", "assigned", "role:default_template"),
+            tuple("group:default_ops", "grp_assigned", "role:default_template"),
+        ];
+
+        let cloned = clone_role_tuples("default", "template", "team_b", &source, false);
+        assert_eq!(
+            cloned,
+            vec![
+                TupleKey::new("org:default", "owningOrg", "role:default_team_b"),
+                TupleKey::new("role:default_team_b#has", "ALLOW_GET", "dashboard:_all_default"),
+                TupleKey::new("role:default_team_b#has", "ALLOW_ALL", "logs:app"),
+            ]
+        );
+
+        // Same permissions as the source, for the new role
+        let source_grants: Vec<_> = source
+            .iter()
+            .filter(|t| t.key.user == "role:default_template#has")
+            .map(|t| (t.key.relation.clone(), t.key.object.clone()))
+            .collect();
+        let cloned_grants: Vec<_> = cloned
+            .iter()
+            .filter(|t| t.user == "role:default_team_b#has")
+            .map(|t| (t.relation.clone(), t.object.clone()))
+            .collect();
+        assert_eq!(source_grants, cloned_grants);
+
+        let cloned = clone_role_tuples("default", "template", "team_b", &source, true);
+        assert_eq!(cloned.len(), 4);
+        assert!(cloned.contains(&TupleKey::new("user:a@b.com", "assigned", "role:default_team_b")));
+    }
+
+    #[test]
+    fn test_group_permissions_by_resource_type() {
+        let tuple = |relation: &str, object: &str| Tuple {
+            key: TupleKey::new("role:default_dev#has", relation, object),
+            timestamp: None,
+        };
+        let grants = vec![
+            tuple("ALLOW_GET", "dashboard:_all_default"),
+            tuple("ALLOW_PUT", "dashboard:d1"),
+            tuple("ALLOW_ALL", "logs:app"),
+            tuple("ALLOW_GET", "not_a_type:x"),
+        ];
+
+        let grouped = group_permissions_by_resource_type(&grants);
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped["dashboard"].len(), 2);
+        assert_eq!(grouped["dashboard"][1].permission, "AllowPut");
+        assert_eq!(grouped["logs"][0].object.to_string(), "logs:app");
+    }
 }
@@ -7,10 +7,14 @@
 
 //! Tuple operations service (compatible with o2_openfga::authorizer::authz)
 
+use std::collections::{HashMap, HashSet};
+
 use crate::Visdata;
-use super::super::error::Result;
-use super::super::model::schema;
-use super::super::types::TupleKey;
+use super::super::error::{Error, Result};
+use super::super::meta::mapping::{self, Resource};
+use super::super::model::{resources, schema};
+use super::super::types::{Permission, TupleKey, TupleKeyFilter};
+use super::cache::PERMISSION_CACHE;
 
 /// Batch update tuples (add and/or delete)
 ///
@@ -19,29 +23,163 @@ pub async fn update_tuples(
     writes: Vec<TupleKey>,
     deletes: Vec<TupleKey>,
 ) -> Result<()> {
+    let (writes, deletes) = dedupe_tuple_changes(writes, deletes);
     if writes.is_empty() && deletes.is_empty() {
         return Ok(());
     }
 
     let visdata = Visdata::global();
-    visdata.openfga().write(writes, deletes).await
+    let result = visdata.openfga().write(writes.clone(), deletes.clone()).await;
+
+    // Flush cached checks even on failure, since a partial write may have applied
+    PERMISSION_CACHE.invalidate_tuples(writes.iter().chain(deletes.iter()));
+    result
 }
 
-/// Map UserRole to OpenFGA relation on org type
+/// Check whether an exact tuple exists
+///
+/// Issues a single read filtered on user, relation and object.
+pub async fn tuple_exists(tuple: &TupleKey) -> Result<bool> {
+    let filter = TupleKeyFilter {
+        user: Some(tuple.user.clone()),
+        relation: Some(tuple.relation.clone()),
+        object: Some(tuple.object.clone()),
+    };
+    let found = Visdata::global().openfga().read(Some(filter)).await?;
+    Ok(!found.is_empty())
+}
+
+/// Remove duplicate tuples and tuples present in both lists
+///
+/// OpenFGA rejects a write containing duplicates or the same tuple as both a
+/// write and a delete. Order of first occurrence is preserved.
+pub fn dedupe_tuple_changes(
+    writes: Vec<TupleKey>,
+    deletes: Vec<TupleKey>,
+) -> (Vec<TupleKey>, Vec<TupleKey>) {
+    let write_set: HashSet<&TupleKey> = writes.iter().collect();
+    let conflicts: HashSet<TupleKey> = deletes
+        .iter()
+        .filter(|t| write_set.contains(t))
+        .cloned()
+        .collect();
+    for tuple in &conflicts {
+        tracing::debug!(
+            "[RBAC] Dropping tuple in both writes and deletes: {} {} {}",
+            tuple.user, tuple.relation, tuple.object
+        );
+    }
+
+    let dedupe = |tuples: Vec<TupleKey>| {
+        let mut seen = HashSet::new();
+        tuples
+            .into_iter()
+            .filter(|t| !conflicts.contains(t) && seen.insert(t.clone()))
+            .collect::<Vec<_>>()
+    };
+    (dedupe(writes), dedupe(deletes))
+}
+
+/// Directly assignable org relations a system role may map to
+///
+/// The org type's method relations (GET, POST, ...) are computed and cannot be
+/// written, and ALLOW_* relations grant org-wide resource access.
+const ROLE_MEMBERSHIP_RELATIONS: &[&str] = &["admin", "editor", "viewer", "allowed_user"];
+
+/// Default mapping from system role (lowercase) to OpenFGA relation on org type
 ///
 /// Based on store.yaml org type definition:
 /// - admin: [user] and org_context
 /// - editor: [user] and org_context
 /// - viewer: [user] and org_context
 /// - allowed_user: [user] and org_context (for User/ServiceAccount roles)
-pub fn role_to_fga_relation(role: &str) -> &'static str {
-    match role.to_lowercase().as_str() {
-        "root" | "admin" => "admin",
-        "editor" => "editor",
-        "viewer" => "viewer",
-        "user" | "serviceaccount" | "service_account" => "allowed_user",
-        _ => "allowed_user",
+pub fn default_role_relations() -> HashMap<String, String> {
+    [
+        ("root", "admin"),
+        ("admin", "admin"),
+        ("editor", "editor"),
+        ("viewer", "viewer"),
+        ("user", "allowed_user"),
+        ("serviceaccount", "allowed_user"),
+        ("service_account", "allowed_user"),
+    ]
+    .into_iter()
+    .map(|(role, relation)| (role.to_string(), relation.to_string()))
+    .collect()
+}
+
+/// Resolve a role to its org relation, consulting `overrides` before the defaults
+///
+/// Unknown roles fall back to `allowed_user`.
+pub fn resolve_role_relation(role: &str, overrides: &HashMap<String, String>) -> String {
+    let role = role.to_lowercase();
+    if let Some(relation) = overrides.get(&role) {
+        return relation.clone();
+    }
+    default_role_relations()
+        .remove(&role)
+        .unwrap_or_else(|| "allowed_user".to_string())
+}
+
+/// Validate that every override maps to an org membership relation
+///
+/// Only `ROLE_MEMBERSHIP_RELATIONS` that the org type defines are accepted.
+pub fn validate_role_relation_overrides(overrides: &HashMap<String, String>) -> Result<()> {
+    let org_relations = schema::get_type_relations("org");
+    for (role, relation) in overrides {
+        let is_membership = ROLE_MEMBERSHIP_RELATIONS.contains(&relation.as_str());
+        if !is_membership || !org_relations.contains(relation) {
+            return Err(Error::Config(format!(
+                "role_relation_overrides: role '{}' maps to '{}', expected one of {}",
+                role,
+                relation,
+                ROLE_MEMBERSHIP_RELATIONS.join(", ")
+            )));
+        }
     }
+    Ok(())
+}
+
+/// Map UserRole to OpenFGA relation on org type
+///
+/// Uses the defaults from [`default_role_relations`], overridden by
+/// `OpenFGAConfig::role_relation_overrides` when VisData is initialized.
+pub fn role_to_fga_relation(role: &str) -> String {
+    match crate::Visdata::try_global() {
+        Some(v) => resolve_role_relation(role, &v.openfga_config().role_relation_overrides),
+        None => resolve_role_relation(role, &HashMap::new()),
+    }
+}
+
+/// All org relations that represent a system role (defaults plus overrides)
+pub fn system_role_relations(overrides: &HashMap<String, String>) -> HashSet<String> {
+    default_role_relations()
+        .into_values()
+        .chain(overrides.values().cloned())
+        .collect()
+}
+
+/// Compute the writes and deletes needed to bring a user's org tuples to `desired`
+///
+/// `existing` are the tuples currently stored for the user on the org. Tuples
+/// that already exist are not written again (OpenFGA rejects duplicate writes),
+/// and existing system role tuples not in `desired` are deleted so that
+/// re-adding a user with a different role replaces the old role.
+pub fn diff_add_user_to_org_tuples(
+    existing: &[TupleKey],
+    desired: Vec<TupleKey>,
+    role_relations: &HashSet<String>,
+) -> (Vec<TupleKey>, Vec<TupleKey>) {
+    let deletes = existing
+        .iter()
+        .filter(|t| role_relations.contains(&t.relation) && !desired.contains(t))
+        .cloned()
+        .collect();
+    let writes = desired
+        .into_iter()
+        .filter(|t| !existing.contains(t))
+        .collect();
+    (writes, deletes)
 }
 
 /// Add user to organization with a system role
@@ -70,7 +208,7 @@ pub fn get_add_user_to_org_tuples(
     let relation = role_to_fga_relation(role);
 
     // Add role tuple
-    tuples.push(TupleKey::new(&user, relation, &org));
+    tuples.push(TupleKey::new(&user, &relation, &org));
     // Add org_context tuple (required for the intersection)
     tuples.push(TupleKey::new(&user, "org_context", &org));
 }
@@ -114,9 +252,37 @@ pub fn get_org_creation_tuples(org_id: &str, tuples: &mut Vec<TupleKey>) {
     // Create organization object
     let org = schema::org_type(org_id);
 
-    // Organization is self-referential for member relation
+    // Organization is self-referential for the selfParent relation
     // This allows inheriting permissions from organization to resources
-    tuples.push(TupleKey::new(&org, "member", &org));
+    tuples.push(TupleKey::new(&org, "selfParent", &org));
+}
+
+/// Write the `owningOrg` bootstrap tuple for a resource type in an org
+///
+/// Built-in types get these tuples at store initialization; registered
+/// types need them for every org that should use the type.
+pub async fn write_resource_type_bootstrap_tuples(
+    resource_type: &str,
+    org_id: &str,
+) -> Result<()> {
+    let Some(resource_type) = resources::resolve_resource_key(resource_type) else {
+        return Err(Error::InvalidResourceType(resource_type.to_string()));
+    };
+    let tuple = schema::org_resource_ownership_tuple(org_id, resource_type);
+    update_tuples(vec![tuple], vec![]).await
+}
+
+/// Register a custom resource type and bootstrap it for `org_id`
+///
+/// The registration is rolled back if the bootstrap tuples cannot be written.
+pub async fn register_resource_for_org(resource: Resource, org_id: &str) -> Result<()> {
+    let key = resource.key.clone();
+    mapping::register_resource(resource)?;
+    if let Err(e) = write_resource_type_bootstrap_tuples(&key, org_id).await {
+        mapping::unregister_resource(&key);
+        return Err(e);
+    }
+    Ok(())
 }
 
 /// Get tuple for resource ownership
@@ -144,6 +310,33 @@ pub fn get_resource_parent_tuple(
     TupleKey::new(&org, "parent", &resource)
 }
 
+/// Get tuple linking a resource to its parent folder (e.g. dfolder -> dashboard)
+///
+/// Returns `None` if the resource type has no parent type.
+pub fn get_folder_parent_tuple(
+    org_id: &str,
+    resource_type: &str,
+    folder_id: &str,
+    entity_id: &str,
+) -> Option<TupleKey> {
+    let parent_type = resources::get_parent_type(resource_type)?;
+    let folder = schema::resource_object(org_id, parent_type, folder_id);
+    let resource = schema::resource_object(org_id, resource_type, entity_id);
+
+    Some(TupleKey::new(&folder, "parent", &resource))
+}
+
+/// Get tuple granting a permission on a resource to every user (`user:*`)
+pub fn get_public_access_tuple(
+    org_id: &str,
+    resource_type: &str,
+    entity_id: &str,
+    permission: Permission,
+) -> TupleKey {
+    let resource = schema::resource_object(org_id, resource_type, entity_id);
+    TupleKey::new(schema::public_user(), permission.to_allow_relation(), &resource)
+}
+
 /// Get tuple for organization-wide resource permission
 /// This grants permission to all resources of a type in an org
 ///
@@ -180,6 +373,16 @@ pub fn get_group_member_tuple(org_id: &str, group_name: &str, user_email: &str)
     TupleKey::new(&user, "member", &group)
 }
 
+/// Get tuple for nesting a group inside another group
+///
+/// Members of `child` become members of `parent` (and inherit its roles).
+pub fn get_subgroup_tuple(org_id: &str, parent_group: &str, child_group: &str) -> TupleKey {
+    let child_members = format!("{}#member", schema::group_type(org_id, child_group));
+    let parent = schema::group_type(org_id, parent_group);
+
+    TupleKey::new(&child_members, "member", &parent)
+}
+
 /// Get tuple for assigning a role to a group
 ///
 /// Note: Uses "grp_assigned" relation to match the OpenFGA model in store.yaml
@@ -241,7 +444,7 @@ pub fn get_delete_user_system_role_tuples(
     let relation = role_to_fga_relation(role);
 
     // Delete role tuple
-    tuples.push(TupleKey::new(&user, relation, &org));
+    tuples.push(TupleKey::new(&user, &relation, &org));
     // Delete org_context tuple
     tuples.push(TupleKey::new(&user, "org_context", &org));
 }
@@ -273,9 +476,9 @@ pub async fn update_user_role(
     let mut deletes = vec![];
 
     // Delete old role tuple
-    deletes.push(TupleKey::new(&user, old_relation, &org));
+    deletes.push(TupleKey::new(&user, &old_relation, &org));
     // Add new role tuple
-    writes.push(TupleKey::new(&user, new_relation, &org));
+    writes.push(TupleKey::new(&user, &new_relation, &org));
 
     // org_context stays, no need to update
 
@@ -284,7 +487,8 @@ pub async fn update_user_role(
 
 /// Delete all user tuples from org (for user removal)
 ///
-/// Removes all possible role relations for a user from an org
+/// Removes all possible role relations for a user from an org, including
+/// those only reachable through `OpenFGAConfig::role_relation_overrides`
 pub fn get_delete_all_user_from_org_tuples(
     org_id: &str,
     user_email: &str,
@@ -294,10 +498,15 @@ pub fn get_delete_all_user_from_org_tuples(
     let org = schema::org_type(org_id);
 
     // Remove all possible role relations
-    tuples.push(TupleKey::new(&user, "admin", &org));
-    tuples.push(TupleKey::new(&user, "editor", &org));
-    tuples.push(TupleKey::new(&user, "viewer", &org));
-    tuples.push(TupleKey::new(&user, "allowed_user", &org));
+    let overrides = match crate::Visdata::try_global() {
+        Some(v) => v.openfga_config().role_relation_overrides.clone(),
+        None => HashMap::new(),
+    };
+    let mut relations: Vec<String> = system_role_relations(&overrides).into_iter().collect();
+    relations.sort();
+    for relation in relations {
+        tuples.push(TupleKey::new(&user, &relation, &org));
+    }
     tuples.push(TupleKey::new(&user, "org_context", &org));
 }
 
@@ -346,6 +555,15 @@ mod tests {
         assert_eq!(tuple.object, "role:default_developer");
     }
 
+    #[test]
+    fn test_get_public_access_tuple() {
+        let tuple = get_public_access_tuple("default", "dashboard", "d1", Permission::AllowGet);
+
+        assert_eq!(tuple.user, "user:*");
+        assert_eq!(tuple.relation, "ALLOW_GET");
+        assert_eq!(tuple.object, "dashboard:d1");
+    }
+
     #[test]
     fn test_get_group_member_tuple() {
         let tuple = get_group_member_tuple("default", "developers", "alice@example.com");
@@ -366,6 +584,91 @@ mod tests {
         assert_eq!(role_to_fga_relation("ServiceAccount"), "allowed_user");
     }
 
+    #[test]
+    fn test_resolve_role_relation_with_overrides() {
+        let mut overrides = HashMap::new();
+        overrides.insert("auditor".to_string(), "viewer".to_string());
+        overrides.insert("editor".to_string(), "viewer".to_string());
+
+        // Custom role
+        assert_eq!(resolve_role_relation("Auditor", &overrides), "viewer");
+        // Override of a default role
+        assert_eq!(resolve_role_relation("Editor", &overrides), "viewer");
+        // Defaults still apply for roles without an override
+        assert_eq!(resolve_role_relation("Admin", &overrides), "admin");
+        // Unknown roles fall back to allowed_user
+        assert_eq!(resolve_role_relation("unknown", &overrides), "allowed_user");
+    }
+
+    #[test]
+    fn test_validate_role_relation_overrides() {
+        let mut overrides = HashMap::new();
+        overrides.insert("auditor".to_string(), "viewer".to_string());
+        assert!(validate_role_relation_overrides(&overrides).is_ok());
+
+        overrides.insert("superuser".to_string(), "superadmin".to_string());
+        let err = validate_role_relation_overrides(&overrides).unwrap_err();
+        assert!(matches!(err, Error::Config(_)));
+        assert!(err.to_string().contains("superadmin"));
+
+        // Computed and org-wide grant relations exist on org but are rejected
+        for relation in ["GET", "DELETE", "ALLOW_ALL", "org_context"] {
+            let overrides: HashMap<String, String> =
+                [("auditor".to_string(), relation.to_string())].into_iter().collect();
+            let err = validate_role_relation_overrides(&overrides).unwrap_err();
+            assert!(matches!(err, Error::Config(_)), "{}", relation);
+        }
+    }
+
+    #[test]
+    fn test_default_role_relations_are_valid() {
+        assert!(validate_role_relation_overrides(&default_role_relations()).is_ok());
+    }
+
+    #[test]
+    fn test_get_org_creation_tuples_use_model_relations() {
+        let mut tuples = Vec::new();
+        get_org_creation_tuples("acme", &mut tuples);
+
+        let relations = schema::get_type_relations("org");
+        assert_eq!(tuples, vec![TupleKey::new("org:acme", "selfParent", "org:acme")]);
+        assert!(tuples.iter().all(|t| relations.contains(&t.relation)));
+    }
+
+    #[test]
+    fn test_diff_add_user_to_org_tuples_double_add() {
+        let role_relations = system_role_relations(&HashMap::new());
+
+        let mut first = Vec::new();
+        get_add_user_to_org_tuples("default", "alice@example.com", "admin", &mut first);
+
+        // Re-adding with the same role is a no-op
+        let mut again = Vec::new();
+        get_add_user_to_org_tuples("default", "alice@example.com", "admin", &mut again);
+        let (writes, deletes) = diff_add_user_to_org_tuples(&first, again, &role_relations);
+        assert!(writes.is_empty());
+        assert!(deletes.is_empty());
+
+        // Re-adding with a different role swaps only the role tuple
+        let mut editor = Vec::new();
+        get_add_user_to_org_tuples("default", "alice@example.com", "editor", &mut editor);
+        let (writes, deletes) = diff_add_user_to_org_tuples(&first, editor, &role_relations);
+        assert_eq!(writes.len(), 1);
+        assert_eq!(writes[0].relation, "editor");
+        assert_eq!(deletes.len(), 1);
+        assert_eq!(deletes[0].relation, "admin");
+    }
+
+    #[test]
+    fn test_diff_add_user_to_org_tuples_first_add() {
+        let mut desired = Vec::new();
+        get_add_user_to_org_tuples("default", "alice@example.com", "viewer", &mut desired);
+        let (writes, deletes) =
+            diff_add_user_to_org_tuples(&[], desired, &system_role_relations(&HashMap::new()));
+        assert_eq!(writes.len(), 2);
+        assert!(deletes.is_empty());
+    }
+
     #[test]
     fn test_get_add_user_to_org_tuples_with_editor() {
         let mut tuples = Vec::new();
@@ -400,4 +703,26 @@ mod tests {
         assert!(relations.contains(&"allowed_user"));
         assert!(relations.contains(&"org_context"));
     }
+
+    #[test]
+    fn test_dedupe_tuple_changes() {
+        let a = TupleKey::new("user:a@b.com", "org_context", "org:default");
+        let b = TupleKey::new("user:a@b.com", "admin", "org:default");
+        let c = TupleKey::new("user:a@b.com", "viewer", "org:default");
+
+        let (writes, deletes) = dedupe_tuple_changes(
+            vec![a.clone(), b.clone(), a.clone()],
+            vec![c.clone(), c.clone()],
+        );
+        assert_eq!(writes, vec![a.clone(), b.clone()]);
+        assert_eq!(deletes, vec![c.clone()]);
+
+        // Tuples in both lists are dropped from both
+        let (writes, deletes) = dedupe_tuple_changes(
+            vec![a.clone(), b.clone()],
+            vec![b.clone(), c.clone(), b.clone()],
+        );
+        assert_eq!(writes, vec![a]);
+        assert_eq!(deletes, vec![c]);
+    }
 }
@@ -10,6 +10,9 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
+use super::error::Error;
+use super::model::resources;
+
 // ============================================================================
 // OpenFGA Types
 // ============================================================================
@@ -40,12 +43,36 @@ pub struct Tuple {
     pub timestamp: Option<String>,
 }
 
+/// OpenFGA query consistency preference
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConsistencyPreference {
+    /// Serve from caches where possible (OpenFGA default)
+    #[default]
+    #[serde(rename = "MINIMIZE_LATENCY")]
+    MinimizeLatency,
+    /// Bypass caches so recent writes are visible
+    #[serde(rename = "HIGHER_CONSISTENCY")]
+    HigherConsistency,
+}
+
+impl ConsistencyPreference {
+    /// Whether this is the server default (omitted from requests)
+    pub fn is_minimize_latency(&self) -> bool {
+        *self == ConsistencyPreference::MinimizeLatency
+    }
+}
+
 /// OpenFGA check request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckRequest {
     pub tuple_key: TupleKey,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub authorization_model_id: Option<String>,
+    #[serde(default, skip_serializing_if = "ConsistencyPreference::is_minimize_latency")]
+    pub consistency: ConsistencyPreference,
+    /// Tuples assumed to exist for this check only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contextual_tuples: Option<TupleKeys>,
 }
 
 /// OpenFGA check response
@@ -56,6 +83,37 @@ pub struct CheckResponse {
     pub resolution: Option<String>,
 }
 
+/// Single check within a batch check request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchCheckItem {
+    pub tuple_key: TupleKey,
+    pub correlation_id: String,
+}
+
+/// OpenFGA batch check request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchCheckRequest {
+    pub checks: Vec<BatchCheckItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_model_id: Option<String>,
+}
+
+/// Result of a single check within a batch, keyed by correlation ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchCheckSingleResult {
+    #[serde(default)]
+    pub allowed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<serde_json::Value>,
+}
+
+/// OpenFGA batch check response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchCheckResponse {
+    #[serde(default)]
+    pub result: std::collections::HashMap<String, BatchCheckSingleResult>,
+}
+
 /// OpenFGA write request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WriteRequest {
@@ -73,6 +131,64 @@ pub struct TupleKeys {
     pub tuple_keys: Vec<TupleKey>,
 }
 
+/// Expected check result stored with an authorization model
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Assertion {
+    pub tuple_key: TupleKey,
+    /// Whether the check should be allowed
+    pub expectation: bool,
+    /// Tuples assumed to exist when evaluating the assertion
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub contextual_tuples: Vec<TupleKey>,
+}
+
+impl Assertion {
+    /// Assertion that `user` has `relation` on `object`
+    pub fn allow(
+        user: impl Into<String>,
+        relation: impl Into<String>,
+        object: impl Into<String>,
+    ) -> Self {
+        Self {
+            tuple_key: TupleKey::new(user, relation, object),
+            expectation: true,
+            contextual_tuples: Vec::new(),
+        }
+    }
+
+    /// Assertion that `user` does not have `relation` on `object`
+    pub fn deny(
+        user: impl Into<String>,
+        relation: impl Into<String>,
+        object: impl Into<String>,
+    ) -> Self {
+        Self {
+            expectation: false,
+            ..Self::allow(user, relation, object)
+        }
+    }
+
+    /// Evaluate the assertion with `tuples` as contextual tuples
+    pub fn with_contextual_tuples(mut self, tuples: Vec<TupleKey>) -> Self {
+        self.contextual_tuples = tuples;
+        self
+    }
+}
+
+/// OpenFGA write assertions request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WriteAssertionsRequest {
+    pub assertions: Vec<Assertion>,
+}
+
+/// OpenFGA read assertions response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadAssertionsResponse {
+    pub authorization_model_id: String,
+    #[serde(default)]
+    pub assertions: Vec<Assertion>,
+}
+
 /// OpenFGA list objects request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListObjectsRequest {
@@ -82,6 +198,8 @@ pub struct ListObjectsRequest {
     pub type_: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub authorization_model_id: Option<String>,
+    #[serde(default, skip_serializing_if = "ConsistencyPreference::is_minimize_latency")]
+    pub consistency: ConsistencyPreference,
 }
 
 /// OpenFGA list objects response
@@ -90,6 +208,96 @@ pub struct ListObjectsResponse {
     pub objects: Vec<String>,
 }
 
+/// Typed object reference ("type:id" split into parts)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FgaObject {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub id: String,
+}
+
+/// User type filter for list-users ("user" or "group#member")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserTypeFilter {
+    #[serde(rename = "type")]
+    pub type_: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relation: Option<String>,
+}
+
+impl UserTypeFilter {
+    /// Parse "type" or "type#relation"
+    pub fn parse(filter: &str) -> Self {
+        match filter.split_once('#') {
+            Some((type_, relation)) => Self {
+                type_: type_.to_string(),
+                relation: Some(relation.to_string()),
+            },
+            None => Self {
+                type_: filter.to_string(),
+                relation: None,
+            },
+        }
+    }
+}
+
+/// OpenFGA list users request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListUsersRequest {
+    pub object: FgaObject,
+    pub relation: String,
+    pub user_filters: Vec<UserTypeFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_model_id: Option<String>,
+}
+
+/// OpenFGA list users response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListUsersResponse {
+    #[serde(default)]
+    pub users: Vec<ListUser>,
+}
+
+/// Userset returned by list-users
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListUsersUserset {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub id: String,
+    pub relation: String,
+}
+
+/// Type-bound wildcard returned by list-users (e.g., "user:*")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListUsersWildcard {
+    #[serde(rename = "type")]
+    pub type_: String,
+}
+
+/// User returned by list-users: exactly one of the fields is set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListUser {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub object: Option<FgaObject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub userset: Option<ListUsersUserset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wildcard: Option<ListUsersWildcard>,
+}
+
+impl ListUser {
+    /// Format as an OpenFGA user string ("user:alice", "group:x#member", "user:*")
+    pub fn to_fga_user(&self) -> Option<String> {
+        if let Some(object) = &self.object {
+            return Some(format!("{}:{}", object.type_, object.id));
+        }
+        if let Some(userset) = &self.userset {
+            return Some(format!("{}:{}#{}", userset.type_, userset.id, userset.relation));
+        }
+        self.wildcard.as_ref().map(|w| format!("{}:*", w.type_))
+    }
+}
+
 /// OpenFGA read request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReadRequest {
@@ -99,6 +307,8 @@ pub struct ReadRequest {
     pub page_size: Option<i32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub continuation_token: Option<String>,
+    #[serde(default, skip_serializing_if = "ConsistencyPreference::is_minimize_latency")]
+    pub consistency: ConsistencyPreference,
 }
 
 /// Tuple key filter for read operations
@@ -120,6 +330,186 @@ pub struct ReadResponse {
     pub continuation_token: Option<String>,
 }
 
+/// Tuple key for expand requests (no user)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpandTupleKey {
+    pub relation: String,
+    pub object: String,
+}
+
+/// OpenFGA expand request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpandRequest {
+    pub tuple_key: ExpandTupleKey,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_model_id: Option<String>,
+}
+
+/// OpenFGA expand response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpandResponse {
+    pub tree: UsersetTree,
+}
+
+/// Userset resolution tree for a relation on an object
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsersetTree {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root: Option<UsersetTreeNode>,
+}
+
+/// Node of a userset tree, named "object#relation"
+///
+/// Exactly one of `leaf`, `union`, `intersection` or `difference` is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsersetTreeNode {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub leaf: Option<UsersetTreeLeaf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub union: Option<UsersetTreeNodes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intersection: Option<UsersetTreeNodes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub difference: Option<UsersetTreeDifference>,
+}
+
+/// Child nodes of a union or intersection
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsersetTreeNodes {
+    #[serde(default)]
+    pub nodes: Vec<UsersetTreeNode>,
+}
+
+/// Difference node ("base but not subtract")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsersetTreeDifference {
+    pub base: Box<UsersetTreeNode>,
+    pub subtract: Box<UsersetTreeNode>,
+}
+
+/// Leaf of a userset tree
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsersetTreeLeaf {
+    /// Directly assigned users or usersets (e.g., "user:alice", "group:x#member")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub users: Option<UsersetTreeUsers>,
+    /// Computed userset on the same object (e.g., "dashboard:d1#editor")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub computed: Option<UsersetTreeComputed>,
+    /// Relation inherited through another object (e.g., a parent folder)
+    #[serde(rename = "tupleToUserset", skip_serializing_if = "Option::is_none")]
+    pub tuple_to_userset: Option<UsersetTreeTupleToUserset>,
+}
+
+/// Users of a leaf node
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UsersetTreeUsers {
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+/// Computed userset reference
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsersetTreeComputed {
+    pub userset: String,
+}
+
+/// Tuple-to-userset leaf
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsersetTreeTupleToUserset {
+    pub tupleset: String,
+    #[serde(default)]
+    pub computed: Vec<UsersetTreeComputed>,
+}
+
+/// Edge in a userset tree: `from` contributes to `to`, optionally via a tupleset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsersetEdge {
+    pub from: String,
+    pub to: String,
+    pub via: Option<String>,
+}
+
+impl std::fmt::Display for UsersetEdge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.via {
+            Some(via) => write!(f, "{} -> {} (via {})", self.from, self.to, via),
+            None => write!(f, "{} -> {}", self.from, self.to),
+        }
+    }
+}
+
+impl UsersetTreeNode {
+    /// Collect the edges of this subtree
+    ///
+    /// Union, intersection and difference children (including the subtracted
+    /// side) all contribute edges towards this node's name.
+    pub fn edges(&self, out: &mut Vec<UsersetEdge>) {
+        let edge = |from: &str, via: Option<&str>| UsersetEdge {
+            from: from.to_string(),
+            to: self.name.clone(),
+            via: via.map(str::to_string),
+        };
+
+        if let Some(leaf) = &self.leaf {
+            if let Some(users) = &leaf.users {
+                out.extend(users.users.iter().map(|u| edge(u, None)));
+            }
+            if let Some(computed) = &leaf.computed {
+                out.push(edge(&computed.userset, None));
+            }
+            if let Some(ttu) = &leaf.tuple_to_userset {
+                out.extend(ttu.computed.iter().map(|c| edge(&c.userset, Some(&ttu.tupleset))));
+            }
+        }
+
+        let children = self
+            .union
+            .iter()
+            .chain(self.intersection.iter())
+            .flat_map(|nodes| nodes.nodes.iter())
+            .chain(
+                self.difference
+                    .iter()
+                    .flat_map(|d| [d.base.as_ref(), d.subtract.as_ref()]),
+            );
+        for child in children {
+            // Child nodes usually share this node's name; link them when not
+            if child.name != self.name {
+                out.push(edge(&child.name, None));
+            }
+            child.edges(out);
+        }
+    }
+}
+
+/// Tuple change operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TupleOperation {
+    #[serde(rename = "TUPLE_OPERATION_WRITE")]
+    Write,
+    #[serde(rename = "TUPLE_OPERATION_DELETE")]
+    Delete,
+}
+
+/// A tuple write or delete recorded by OpenFGA
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TupleChange {
+    pub tuple_key: TupleKey,
+    pub operation: TupleOperation,
+    pub timestamp: String,
+}
+
+/// OpenFGA read changes response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadChangesResponse {
+    #[serde(default)]
+    pub changes: Vec<TupleChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub continuation_token: Option<String>,
+}
+
 /// OpenFGA store
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Store {
@@ -175,11 +565,91 @@ pub struct UpdateRoleRequest {
     pub remove_users: Option<HashSet<String>>,
 }
 
+/// Validated permission object in format "{resource_type}:{entity_id}"
+///
+/// The resource type must be a known type or legacy key (stored as the type it
+/// resolves to) and the entity ID must be non-empty. Serialized as the plain
+/// "type:entity" string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ResourceObject {
+    resource_type: String,
+    entity_id: String,
+}
+
+impl ResourceObject {
+    /// Create a validated resource object
+    pub fn new(resource_type: &str, entity_id: &str) -> Result<Self, Error> {
+        let Some(resource_type) = resources::resolve_resource_key(resource_type) else {
+            return Err(Error::InvalidResourceType(resource_type.to_string()));
+        };
+        if entity_id.is_empty() {
+            return Err(Error::Validation(format!(
+                "Missing entity ID in object '{}:'",
+                resource_type
+            )));
+        }
+        Ok(Self {
+            resource_type: resource_type.to_string(),
+            entity_id: entity_id.to_string(),
+        })
+    }
+
+    /// Resource type (e.g., "logs")
+    pub fn resource_type(&self) -> &str {
+        &self.resource_type
+    }
+
+    /// Entity ID (e.g., "my_stream" or "_all_default")
+    pub fn entity_id(&self) -> &str {
+        &self.entity_id
+    }
+
+    /// Whether this object refers to all entities of its type in an org
+    pub fn is_all(&self) -> bool {
+        self.entity_id.starts_with("_all")
+    }
+}
+
+impl std::str::FromStr for ResourceObject {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (resource_type, entity_id) = resources::parse_object(s).ok_or_else(|| {
+            Error::Validation(format!(
+                "Invalid object '{}': expected format 'resource_type:entity_id'",
+                s
+            ))
+        })?;
+        Self::new(resource_type, entity_id)
+    }
+}
+
+impl TryFrom<String> for ResourceObject {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<ResourceObject> for String {
+    fn from(object: ResourceObject) -> Self {
+        object.to_string()
+    }
+}
+
+impl std::fmt::Display for ResourceObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.resource_type, self.entity_id)
+    }
+}
+
 /// Permission entry
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct PermissionEntry {
     /// Resource object in format "resource:entity" (e.g., "logs:my_stream")
-    pub object: String,
+    pub object: ResourceObject,
     /// Permission type: AllowAll, AllowList, AllowGet, AllowPost, AllowPut, AllowDelete
     pub permission: String,
 }
@@ -239,63 +709,154 @@ pub struct RoleResponse {
     pub updated_at: i64,
 }
 
-/// Resource definition (compatible with OFGA_MODELS)
+/// Role with users and permissions grouped by resource type
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Resource {
-    pub key: String,
-    /// Display name (serialized as display_name for frontend compatibility)
-    #[serde(rename = "display_name")]
+pub struct RoleFullResponse {
+    pub name: String,
     pub label: String,
+    pub users: Vec<String>,
+    /// Resource type -> permissions granted on that type
+    pub permissions: std::collections::HashMap<String, Vec<PermissionEntry>>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Options for listing custom roles
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleListOptions {
+    /// Maximum number of roles to return (all if None)
     #[serde(default)]
-    pub parent: Option<String>,
-    pub order: i32,
-    pub visible: bool,
-    /// Whether this is a top-level resource (no parent)
+    pub limit: Option<usize>,
+    /// Number of roles to skip, after sorting by name
     #[serde(default)]
-    pub top_level: bool,
-    /// Whether this resource can have entity instances
+    pub offset: usize,
+    /// Only include roles whose name starts with this (case-insensitive)
     #[serde(default)]
-    pub has_entities: bool,
+    pub name_prefix: Option<String>,
+}
+
+/// Options for listing groups
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupListOptions {
+    /// Maximum number of groups to return (all if None)
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Number of groups to skip, after sorting by name
+    #[serde(default)]
+    pub offset: usize,
+    /// Only include groups whose name starts with this (case-insensitive)
+    #[serde(default)]
+    pub name_prefix: Option<String>,
+    /// Also find groups that have members but no `owningOrg` tuple
+    ///
+    /// Such groups predate `create_group` writing `owningOrg`, or were only
+    /// ever given members. Finding them needs a full store scan.
+    #[serde(default)]
+    pub include_member_only: bool,
+}
+
+/// Role summary for the roles admin page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleSummary {
+    pub name: String,
+    pub label: String,
+    /// Number of users directly assigned to the role
+    pub user_count: usize,
+    /// Resource types the role has at least one permission on (sorted)
+    pub resource_types_granted: Vec<String>,
 }
 
+/// Permission check decision with the reason it was reached
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthDecision {
+    pub allowed: bool,
+    /// Tuple that was (or would have been) checked against OpenFGA
+    pub checked_tuple: TupleKey,
+    /// Resource type parsed from the object (empty if the object was malformed)
+    pub resource_type: String,
+    /// Whether the check targeted the org-wide `_all_{org}` object
+    pub is_list: bool,
+    /// Folder link for parent-scoped objects such as "dashboard:myfolder/mydash"
+    pub parent_tuple: Option<TupleKey>,
+    /// Why the decision was made without a plain OpenFGA check, if it was
+    pub reason: Option<String>,
+}
+
+/// Resource definition (compatible with OFGA_MODELS)
+pub use super::meta::mapping::Resource;
+
 // ============================================================================
 // Permission Types
 // ============================================================================
 
 /// Permission type enumeration
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Also exported as `dex::meta::auth::Permission`. A permission maps to two
+/// relation names: the `ALLOW_*` relation granted to roles
+/// (`to_allow_relation`) and the method relation checked for a request
+/// (`to_internal_relation`), which also accepts `ALLOW_ALL` grants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "PascalCase")]
 pub enum Permission {
+    /// Full access to the resource
     AllowAll,
+    /// Permission to list resources
     AllowList,
+    /// Permission to read/get resources
     AllowGet,
+    /// Permission to create resources
     AllowPost,
+    /// Permission to update resources
     AllowPut,
+    /// Permission to delete resources
     AllowDelete,
 }
 
 impl Permission {
-    /// Convert from string
-    pub fn from_str(s: &str) -> Option<Self> {
-        match s.to_lowercase().as_str() {
-            "allowall" | "allow_all" => Some(Permission::AllowAll),
-            "allowlist" | "allow_list" => Some(Permission::AllowList),
-            "allowget" | "allow_get" => Some(Permission::AllowGet),
-            "allowpost" | "allow_post" => Some(Permission::AllowPost),
-            "allowput" | "allow_put" => Some(Permission::AllowPut),
-            "allowdelete" | "allow_delete" => Some(Permission::AllowDelete),
-            _ => None,
+    /// All permissions
+    pub const ALL: [Permission; 6] = [
+        Permission::AllowAll,
+        Permission::AllowList,
+        Permission::AllowGet,
+        Permission::AllowPost,
+        Permission::AllowPut,
+        Permission::AllowDelete,
+    ];
+
+    /// All permissions, as a list
+    pub fn all() -> Vec<Permission> {
+        Self::ALL.to_vec()
+    }
+
+    /// `ALLOW_*` relation granted to roles in the authorization model
+    pub fn to_allow_relation(&self) -> &'static str {
+        match self {
+            Permission::AllowAll => "ALLOW_ALL",
+            Permission::AllowList => "ALLOW_LIST",
+            Permission::AllowGet => "ALLOW_GET",
+            Permission::AllowPost => "ALLOW_POST",
+            Permission::AllowPut => "ALLOW_PUT",
+            Permission::AllowDelete => "ALLOW_DELETE",
         }
     }
 
-    /// Convert to OpenFGA relation name
-    pub fn to_relation(&self) -> &'static str {
+    /// Parse an `ALLOW_*` relation name (exact match)
+    pub fn from_allow_relation(relation: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.to_allow_relation() == relation)
+    }
+
+    /// Relation checked for a request (e.g. "GET")
+    ///
+    /// The method relations of every resource type union `ALLOW_ALL` with
+    /// their own `ALLOW_*` grant, so checking them covers `AllowAll`.
+    pub fn to_internal_relation(&self) -> &'static str {
         match self {
-            Permission::AllowAll => "admin",
-            Permission::AllowList => "can_list",
-            Permission::AllowGet => "can_read",
-            Permission::AllowPost => "can_create",
-            Permission::AllowPut => "can_update",
-            Permission::AllowDelete => "can_delete",
+            Permission::AllowAll => "ALLOW_ALL",
+            Permission::AllowList => "LIST",
+            Permission::AllowGet => "GET",
+            Permission::AllowPost => "POST",
+            Permission::AllowPut => "PUT",
+            Permission::AllowDelete => "DELETE",
         }
     }
 
@@ -318,6 +879,61 @@ impl Permission {
             _ => self == other,
         }
     }
+
+    /// Power of the permission, for sorting; `AllowAll` ranks highest
+    pub fn rank(&self) -> u8 {
+        match self {
+            Permission::AllowList => 0,
+            Permission::AllowGet => 1,
+            Permission::AllowPost => 2,
+            Permission::AllowPut => 3,
+            Permission::AllowDelete => 4,
+            Permission::AllowAll => 5,
+        }
+    }
+
+    /// Concrete permissions this one grants (all of them for `AllowAll`)
+    pub fn expand(&self) -> Vec<Permission> {
+        match self {
+            Permission::AllowAll => Self::ALL
+                .into_iter()
+                .filter(|p| *p != Permission::AllowAll)
+                .collect(),
+            _ => vec![*self],
+        }
+    }
+}
+
+impl PartialOrd for Permission {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Permission {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+/// Accepts permission names ("AllowGet", "allow_get"), both relation names
+/// ("ALLOW_GET", "GET") and the legacy "can_*" names.
+impl std::str::FromStr for Permission {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "allowall" | "allow_all" | "admin" => Ok(Permission::AllowAll),
+            "allowlist" | "allow_list" | "list" | "can_list" => Ok(Permission::AllowList),
+            "allowget" | "allow_get" | "get" | "can_read" => Ok(Permission::AllowGet),
+            "allowpost" | "allow_post" | "post" | "can_create" => Ok(Permission::AllowPost),
+            "allowput" | "allow_put" | "put" | "can_update" => Ok(Permission::AllowPut),
+            "allowdelete" | "allow_delete" | "delete" | "can_delete" => {
+                Ok(Permission::AllowDelete)
+            }
+            _ => Err(Error::InvalidPermission(s.to_string())),
+        }
+    }
 }
 
 impl std::fmt::Display for Permission {
@@ -397,46 +1013,84 @@ mod tests {
 
     #[test]
     fn test_permission_from_str_camel_case() {
-        assert_eq!(Permission::from_str("AllowAll"), Some(Permission::AllowAll));
-        assert_eq!(Permission::from_str("AllowList"), Some(Permission::AllowList));
-        assert_eq!(Permission::from_str("AllowGet"), Some(Permission::AllowGet));
-        assert_eq!(Permission::from_str("AllowPost"), Some(Permission::AllowPost));
-        assert_eq!(Permission::from_str("AllowPut"), Some(Permission::AllowPut));
-        assert_eq!(Permission::from_str("AllowDelete"), Some(Permission::AllowDelete));
+        assert_eq!("AllowAll".parse::<Permission>().ok(), Some(Permission::AllowAll));
+        assert_eq!("AllowList".parse::<Permission>().ok(), Some(Permission::AllowList));
+        assert_eq!("AllowGet".parse::<Permission>().ok(), Some(Permission::AllowGet));
+        assert_eq!("AllowPost".parse::<Permission>().ok(), Some(Permission::AllowPost));
+        assert_eq!("AllowPut".parse::<Permission>().ok(), Some(Permission::AllowPut));
+        assert_eq!("AllowDelete".parse::<Permission>().ok(), Some(Permission::AllowDelete));
     }
 
     #[test]
     fn test_permission_from_str_snake_case() {
-        assert_eq!(Permission::from_str("allow_all"), Some(Permission::AllowAll));
-        assert_eq!(Permission::from_str("allow_list"), Some(Permission::AllowList));
-        assert_eq!(Permission::from_str("allow_get"), Some(Permission::AllowGet));
-        assert_eq!(Permission::from_str("allow_post"), Some(Permission::AllowPost));
-        assert_eq!(Permission::from_str("allow_put"), Some(Permission::AllowPut));
-        assert_eq!(Permission::from_str("allow_delete"), Some(Permission::AllowDelete));
+        assert_eq!("allow_all".parse::<Permission>().ok(), Some(Permission::AllowAll));
+        assert_eq!("allow_list".parse::<Permission>().ok(), Some(Permission::AllowList));
+        assert_eq!("allow_get".parse::<Permission>().ok(), Some(Permission::AllowGet));
+        assert_eq!("allow_post".parse::<Permission>().ok(), Some(Permission::AllowPost));
+        assert_eq!("allow_put".parse::<Permission>().ok(), Some(Permission::AllowPut));
+        assert_eq!("allow_delete".parse::<Permission>().ok(), Some(Permission::AllowDelete));
     }
 
     #[test]
     fn test_permission_from_str_lowercase() {
-        assert_eq!(Permission::from_str("allowall"), Some(Permission::AllowAll));
-        assert_eq!(Permission::from_str("allowget"), Some(Permission::AllowGet));
+        assert_eq!("allowall".parse::<Permission>().ok(), Some(Permission::AllowAll));
+        assert_eq!("allowget".parse::<Permission>().ok(), Some(Permission::AllowGet));
     }
 
     #[test]
     fn test_permission_from_str_invalid() {
-        assert_eq!(Permission::from_str("invalid"), None);
-        assert_eq!(Permission::from_str(""), None);
-        assert_eq!(Permission::from_str("read"), None);
-        assert_eq!(Permission::from_str("write"), None);
+        assert_eq!("invalid".parse::<Permission>().ok(), None);
+        assert_eq!("".parse::<Permission>().ok(), None);
+        assert_eq!("read".parse::<Permission>().ok(), None);
+        assert_eq!("write".parse::<Permission>().ok(), None);
+    }
+
+    #[test]
+    fn test_permission_rank_and_expand() {
+        let mut permissions = Permission::ALL.to_vec();
+        permissions.sort();
+        assert_eq!(permissions.last(), Some(&Permission::AllowAll));
+        assert!(Permission::AllowAll > Permission::AllowDelete);
+        assert!(Permission::AllowGet > Permission::AllowList);
+
+        let expanded = Permission::AllowAll.expand();
+        assert_eq!(expanded.len(), 5);
+        assert!(!expanded.contains(&Permission::AllowAll));
+        assert!(expanded.iter().all(|p| Permission::AllowAll.implies(p)));
+        assert_eq!(Permission::AllowPut.expand(), vec![Permission::AllowPut]);
+    }
+
+    #[test]
+    fn test_permission_from_str_round_trip() {
+        for permission in Permission::ALL {
+            let parsed: Permission = permission.to_string().parse().unwrap();
+            assert_eq!(parsed, permission);
+        }
+        assert!(matches!(
+            "ReadWrite".parse::<Permission>(),
+            Err(Error::InvalidPermission(s)) if s == "ReadWrite"
+        ));
     }
 
     #[test]
-    fn test_permission_to_relation() {
-        assert_eq!(Permission::AllowAll.to_relation(), "admin");
-        assert_eq!(Permission::AllowList.to_relation(), "can_list");
-        assert_eq!(Permission::AllowGet.to_relation(), "can_read");
-        assert_eq!(Permission::AllowPost.to_relation(), "can_create");
-        assert_eq!(Permission::AllowPut.to_relation(), "can_update");
-        assert_eq!(Permission::AllowDelete.to_relation(), "can_delete");
+    fn test_permission_relations() {
+        let cases = [
+            (Permission::AllowAll, "ALLOW_ALL", "ALLOW_ALL"),
+            (Permission::AllowList, "ALLOW_LIST", "LIST"),
+            (Permission::AllowGet, "ALLOW_GET", "GET"),
+            (Permission::AllowPost, "ALLOW_POST", "POST"),
+            (Permission::AllowPut, "ALLOW_PUT", "PUT"),
+            (Permission::AllowDelete, "ALLOW_DELETE", "DELETE"),
+        ];
+        for (permission, allow, internal) in cases {
+            assert_eq!(permission.to_allow_relation(), allow);
+            assert_eq!(permission.to_internal_relation(), internal);
+            assert_eq!(Permission::from_allow_relation(allow), Some(permission));
+            assert_eq!(allow.parse::<Permission>().ok(), Some(permission));
+            assert_eq!(internal.parse::<Permission>().ok(), Some(permission));
+        }
+        assert_eq!(Permission::from_allow_relation("allow_get"), None);
+        assert_eq!(Permission::all().len(), 6);
     }
 
     #[test]
@@ -526,18 +1180,59 @@ mod tests {
     // PermissionEntry Tests
     // ========================================================================
 
+    // ========================================================================
+    // ResourceObject Tests
+    // ========================================================================
+
+    #[test]
+    fn test_resource_object_parse() {
+        let object: ResourceObject = "logs:my_stream".parse().unwrap();
+        assert_eq!(object.resource_type(), "logs");
+        assert_eq!(object.entity_id(), "my_stream");
+        assert!(!object.is_all());
+        assert_eq!(object.to_string(), "logs:my_stream");
+
+        let all: ResourceObject = "dfolder:_all_default".parse().unwrap();
+        assert!(all.is_all());
+
+        // Legacy keys resolve to the current resource type
+        let legacy: ResourceObject = "serviceaccounts:sa1".parse().unwrap();
+        assert_eq!(legacy.resource_type(), "service_accounts");
+        assert_eq!(legacy.to_string(), "service_accounts:sa1");
+    }
+
+    #[test]
+    fn test_resource_object_malformed() {
+        // Missing colon
+        let err = "logs_my_stream".parse::<ResourceObject>().unwrap_err();
+        assert!(matches!(err, Error::Validation(_)));
+        // Unknown resource type
+        assert!(matches!(
+            "nonexistent:foo".parse::<ResourceObject>(),
+            Err(Error::InvalidResourceType(_))
+        ));
+        // Missing entity
+        assert!(matches!("logs:".parse::<ResourceObject>(), Err(Error::Validation(_))));
+    }
+
+    #[test]
+    fn test_permission_entry_rejects_malformed_object() {
+        let json = r#"{"object":"no_colon_here","permission":"AllowGet"}"#;
+        assert!(serde_json::from_str::<PermissionEntry>(json).is_err());
+    }
+
     #[test]
     fn test_permission_entry_equality() {
         let entry1 = PermissionEntry {
-            object: "logs:my_stream".to_string(),
+            object: "logs:my_stream".parse().unwrap(),
             permission: "AllowGet".to_string(),
         };
         let entry2 = PermissionEntry {
-            object: "logs:my_stream".to_string(),
+            object: "logs:my_stream".parse().unwrap(),
             permission: "AllowGet".to_string(),
         };
         let entry3 = PermissionEntry {
-            object: "logs:other_stream".to_string(),
+            object: "logs:other_stream".parse().unwrap(),
             permission: "AllowGet".to_string(),
         };
 
@@ -549,11 +1244,11 @@ mod tests {
     fn test_permission_entry_hash() {
         let mut set = HashSet::new();
         set.insert(PermissionEntry {
-            object: "logs:stream1".to_string(),
+            object: "logs:stream1".parse().unwrap(),
             permission: "AllowGet".to_string(),
         });
         set.insert(PermissionEntry {
-            object: "logs:stream1".to_string(),
+            object: "logs:stream1".parse().unwrap(),
             permission: "AllowGet".to_string(),
         }); // duplicate
 
@@ -563,7 +1258,7 @@ mod tests {
     #[test]
     fn test_permission_entry_serialization() {
         let entry = PermissionEntry {
-            object: "dashboard:my_dash".to_string(),
+            object: "dashboard:my_dash".parse().unwrap(),
             permission: "AllowAll".to_string(),
         };
 
@@ -602,7 +1297,7 @@ mod tests {
 
         let req = UpdateRoleRequest {
             add: Some(vec![PermissionEntry {
-                object: "logs:_all_default".to_string(),
+                object: "logs:_all_default".parse().unwrap(),
                 permission: "AllowGet".to_string(),
             }]),
             remove: None,
@@ -734,7 +1429,7 @@ mod tests {
     fn test_resource_serialization_display_name() {
         let resource = Resource {
             key: "logs".to_string(),
-            label: "Logs".to_string(),
+            display_name: "Logs".to_string(),
             parent: Some("stream".to_string()),
             order: 11,
             visible: true,
@@ -747,4 +1442,101 @@ mod tests {
         assert!(json.contains("\"display_name\":\"Logs\""));
         assert!(!json.contains("\"label\""));
     }
+
+    #[test]
+    fn test_list_user_to_fga_user() {
+        let response: ListUsersResponse = serde_json::from_value(serde_json::json!({
+            "users": [
+                {"object": {"type": "user", "id": "alice@example.com"}},
+                {"userset": {"type": "group", "id": "default_devs", "relation": "member"}},
+                {"wildcard": {"type": "user"}},
+                {}
+            ]
+        }))
+        .unwrap();
+
+        let users: Vec<String> = response.users.iter().filter_map(ListUser::to_fga_user).collect();
+        assert_eq!(
+            users,
+            vec!["user:alice@example.com", "group:default_devs#member", "user:*"]
+        );
+    }
+
+    #[test]
+    fn test_user_type_filter_parse() {
+        let filter = UserTypeFilter::parse("user");
+        assert_eq!(filter.type_, "user");
+        assert_eq!(filter.relation, None);
+
+        let filter = UserTypeFilter::parse("group#member");
+        assert_eq!(filter.type_, "group");
+        assert_eq!(filter.relation.as_deref(), Some("member"));
+    }
+
+    #[test]
+    fn test_read_changes_response_deserialize() {
+        let response: ReadChangesResponse = serde_json::from_value(serde_json::json!({
+            "changes": [
+                {
+                    "tuple_key": {"user": "user:a@b.com", "relation": "member", "object": "group:default_x"},
+                    "operation": "TUPLE_OPERATION_WRITE",
+                    "timestamp": "2025-01-01T00:00:00Z"
+                },
+                {
+                    "tuple_key": {"user": "user:a@b.com", "relation": "member", "object": "group:default_x"},
+                    "operation": "TUPLE_OPERATION_DELETE",
+                    "timestamp": "2025-01-02T00:00:00Z"
+                }
+            ],
+            "continuation_token": "abc"
+        }))
+        .unwrap();
+
+        assert_eq!(response.changes.len(), 2);
+        assert_eq!(response.changes[0].operation, TupleOperation::Write);
+        assert_eq!(response.changes[1].operation, TupleOperation::Delete);
+        assert_eq!(response.continuation_token.as_deref(), Some("abc"));
+    }
+
+    #[test]
+    fn test_consistency_serialization() {
+        let mut req = CheckRequest {
+            tuple_key: TupleKey::new("user:a@b.com", "can_read", "dashboard:d1"),
+            authorization_model_id: None,
+            consistency: ConsistencyPreference::default(),
+            contextual_tuples: None,
+        };
+        let json = serde_json::to_value(&req).unwrap();
+        assert!(json.get("consistency").is_none());
+        assert!(json.get("contextual_tuples").is_none());
+
+        req.consistency = ConsistencyPreference::HigherConsistency;
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["consistency"], "HIGHER_CONSISTENCY");
+    }
+
+    #[test]
+    fn test_assertion_serialization() {
+        let assertion = Assertion::deny("user:a@b.com", "GET", "org:default");
+        let json = serde_json::to_value(&assertion).unwrap();
+        assert_eq!(json["expectation"], false);
+        assert!(json.get("contextual_tuples").is_none());
+
+        let assertion = Assertion::allow("user:a@b.com", "GET", "org:default")
+            .with_contextual_tuples(vec![TupleKey::new("user:a@b.com", "viewer", "org:default")]);
+        let json = serde_json::to_value(&assertion).unwrap();
+        assert_eq!(json["contextual_tuples"][0]["relation"], "viewer");
+
+        let response: ReadAssertionsResponse = serde_json::from_str(
+            r#"{"authorization_model_id":"m1","assertions":[{"tuple_key":
+                {"user":"user:a@b.com","relation":"GET","object":"org:default"},
+                "expectation":true}]}"#,
+        )
+        .unwrap();
+        assert_eq!(response.authorization_model_id, "m1");
+        assert_eq!(
+            response.assertions,
+            vec![Assertion::allow("user:a@b.com", "GET", "org:default")]
+        );
+    }
 }